@@ -0,0 +1,281 @@
+#![allow(clippy::needless_return)]
+
+use std::path::Path;
+
+use toml_edit::{Array, Document, Item, Table, Value};
+
+use crate::configs::Dependency;
+
+/// A library's or a workspace's Cargo.toml file that can be edited without changing the parts of the file that godot_rust_helper
+/// doesn't manage. Comments, formatting, and any keys, dependencies, features, or profiles are kept as they are.
+#[derive(Debug)]
+pub struct CargoToml {
+    /// The parsed document.
+    document: Document,
+}
+
+impl CargoToml {
+    /// Parses the contents of a Cargo.toml file.
+    ///
+    /// # Arguments
+    ///
+    /// `contents` - The contents of the file.
+    pub fn parse(contents: &str) -> Result<CargoToml, String> {
+        let document = contents
+            .parse::<Document>()
+            .map_err(|e| format!("Unable to parse Cargo.toml: {}", e))?;
+
+        return Ok(CargoToml { document });
+    }
+
+    /// Makes sure that the crate is built as a dynamic library that Godot can load by adding `cdylib` to the
+    /// crate types under [lib]. Any other crate types are kept. If there's no [lib] table then one is added right
+    /// after the [package] table.
+    pub fn add_cdylib_crate_type(&mut self) {
+        if !self.document.contains_table("lib") {
+            let package_position = self
+                .document
+                .get("package")
+                .and_then(Item::as_table)
+                .and_then(Table::position);
+
+            let mut lib = Table::new();
+            if let Some(position) = package_position {
+                shift_table_positions(self.document.as_table_mut(), position);
+                lib.set_position(position + 1);
+            }
+            self.document.insert("lib", Item::Table(lib));
+        }
+
+        let lib = &mut self.document["lib"];
+        match lib.get_mut("crate-type").and_then(Item::as_array_mut) {
+            Some(crate_types) => {
+                if !crate_types.iter().any(|t| t.as_str() == Some("cdylib")) {
+                    crate_types.push("cdylib");
+                }
+            }
+            None => {
+                let mut crate_types = Array::new();
+                crate_types.push("cdylib");
+                lib["crate-type"] = Item::Value(Value::Array(crate_types));
+            }
+        }
+    }
+
+    /// Returns the name of the crate's library, which is the `name` under [lib] if it's set or the name of the package
+    /// otherwise.
+    pub fn library_name(&self) -> Option<String> {
+        return ["lib", "package"].iter().find_map(|table| {
+            self.document
+                .get(table)
+                .and_then(|t| t.get("name"))
+                .and_then(Item::as_str)
+                .map(str::to_string)
+        });
+    }
+
+    /// Returns whether the Cargo.toml file is the root of a workspace.
+    pub fn is_workspace(&self) -> bool {
+        return self.document.contains_table("workspace");
+    }
+
+    /// Returns the members under [workspace] as they are written, which can include glob patterns like `crates/*`.
+    pub fn workspace_members(&self) -> Vec<String> {
+        return self
+            .document
+            .get("workspace")
+            .and_then(|workspace| workspace.get("members"))
+            .and_then(Item::as_array)
+            .map(|members| {
+                members
+                    .iter()
+                    .filter_map(|m| m.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+    }
+
+    /// Adds a member under [workspace] if it isn't already one, either by itself or through a glob pattern like
+    /// `crates/*`.
+    ///
+    /// # Arguments
+    ///
+    /// `member` - The path of the member relative to the root of the workspace.
+    pub fn add_workspace_member(&mut self, member: &str) {
+        let member_dir = Path::new(member).parent();
+        let workspace = &mut self.document["workspace"];
+        match workspace.get_mut("members").and_then(Item::as_array_mut) {
+            Some(members) => {
+                let is_member = members
+                    .iter()
+                    .filter_map(|m| m.as_str())
+                    .any(|m| m == member || m.strip_suffix("/*").map(Path::new) == member_dir);
+                if !is_member {
+                    members.push(member);
+                }
+            }
+            None => {
+                let mut members = Array::new();
+                members.push(member);
+                workspace["members"] = Item::Value(Value::Array(members));
+            }
+        }
+    }
+
+    /// Returns the version requirement of a dependency, whether it's written as a version or as a table with a
+    /// `version` key.
+    ///
+    /// # Arguments
+    ///
+    /// `name` - The name of the dependency.
+    pub fn dependency_version(&self, name: &str) -> Option<String> {
+        let dependency = self
+            .document
+            .get("dependencies")
+            .and_then(|dependencies| dependencies.get(name))?;
+
+        return dependency
+            .as_str()
+            .or_else(|| dependency.get("version").and_then(Item::as_str))
+            .map(str::to_string);
+    }
+
+    /// Returns whether a dependency is under [dependencies].
+    ///
+    /// # Arguments
+    ///
+    /// `name` - The name of the dependency.
+    pub fn has_dependency(&self, name: &str) -> bool {
+        return self
+            .document
+            .get("dependencies")
+            .and_then(|dependencies| dependencies.get(name))
+            .is_some();
+    }
+
+    /// Adds or replaces a dependency under [dependencies], or removes it if `dependency` is `None`. The
+    /// [dependencies] table is created if it doesn't exist.
+    ///
+    /// # Arguments
+    ///
+    /// `name` - The name of the dependency.
+    /// `dependency` - Where the dependency comes from.
+    pub fn set_dependency(&mut self, name: &str, dependency: Option<&Dependency>) {
+        match dependency {
+            Some(dependency) => {
+                let value = dependency
+                    .to_string()
+                    .parse::<Value>()
+                    .expect("Unable to convert dependency to toml");
+                let dependencies = self.dependencies_mut();
+                match dependencies.get_mut(name) {
+                    // Replacing the value of an existing key keeps the comments around it.
+                    Some(Item::Value(current)) => {
+                        let decor = current.decor().clone();
+                        *current = value;
+                        *current.decor_mut() = decor;
+                    }
+                    _ => dependencies[name] = Item::Value(value),
+                }
+            }
+            None => {
+                if let Some(dependencies) = self
+                    .document
+                    .get_mut("dependencies")
+                    .and_then(Item::as_table_like_mut)
+                {
+                    dependencies.remove(name);
+                }
+            }
+        }
+    }
+
+    /// Changes the version requirement of a dependency, whether it's written as a version or as a table with a
+    /// `version` key. Returns whether the dependency had a version that could be changed.
+    ///
+    /// # Arguments
+    ///
+    /// `name` - The name of the dependency.
+    /// `version` - The new version requirement.
+    pub fn set_dependency_version(&mut self, name: &str, version: &str) -> bool {
+        let dependency = match self
+            .document
+            .get_mut("dependencies")
+            .and_then(|dependencies| dependencies.get_mut(name))
+        {
+            Some(dependency) => dependency,
+            None => return false,
+        };
+
+        if dependency.is_str() {
+            let decor = dependency.as_value().unwrap().decor().clone();
+            *dependency = toml_edit::value(version);
+            *dependency.as_value_mut().unwrap().decor_mut() = decor;
+            return true;
+        }
+        match dependency
+            .as_table_like_mut()
+            .and_then(|table| table.get_mut("version"))
+        {
+            Some(current) if current.is_str() => {
+                let decor = current.as_value().unwrap().decor().clone();
+                *current = toml_edit::value(version);
+                *current.as_value_mut().unwrap().decor_mut() = decor;
+                return true;
+            }
+            _ => return false,
+        }
+    }
+
+    /// Returns the [dependencies] table, creating it if it doesn't exist.
+    fn dependencies_mut(&mut self) -> &mut Table {
+        if !self.document.contains_table("dependencies") {
+            self.document
+                .insert("dependencies", Item::Table(Table::new()));
+        }
+
+        return self.document["dependencies"]
+            .as_table_mut()
+            .expect("The dependencies in Cargo.toml aren't a table");
+    }
+}
+
+/// The contents of the Cargo.toml file with the changes made to it.
+impl std::fmt::Display for CargoToml {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        return write!(f, "{}", self.document);
+    }
+}
+
+/// Moves every table that comes after a position in the document down by one so that a new table can be inserted
+/// at the position after it.
+///
+/// # Arguments
+///
+/// `table` - The table whose child tables are moved.
+/// `after` - The position of the table that the new table goes after.
+fn shift_table_positions(table: &mut Table, after: usize) {
+    for (_, item) in table.iter_mut() {
+        match item {
+            Item::Table(child) => {
+                if let Some(position) = child.position() {
+                    if position > after {
+                        child.set_position(position + 1);
+                    }
+                }
+                shift_table_positions(child, after);
+            }
+            Item::ArrayOfTables(children) => {
+                for child in children.iter_mut() {
+                    if let Some(position) = child.position() {
+                        if position > after {
+                            child.set_position(position + 1);
+                        }
+                    }
+                    shift_table_positions(child, after);
+                }
+            }
+            _ => (),
+        }
+    }
+}
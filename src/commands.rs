@@ -1,25 +1,78 @@
+#![allow(clippy::needless_return)]
+
+use std::collections::BTreeMap;
 use std::env::{current_dir, set_current_dir};
-use std::fs::{read_to_string, remove_file, write};
+use std::fs::{create_dir_all, read_to_string, remove_file, write};
 use std::path::{Path, PathBuf};
 use std::process::{exit, Command};
 use std::sync::mpsc::channel;
 
+use crate::cargo_toml::CargoToml;
+use crate::config_file::ConfigFile;
 use crate::configs::{
-    Cargo, Config, ConfigGeneral, ConfigGeneralV3, ConfigPaths, ConfigPathsV2, ConfigV1, ConfigV2,
-    ConfigV3, PluginConfig, PluginConfigFields,
+    self, Config, ConfigDependencies, ConfigGeneral, ConfigGeneralV3, ConfigModule, ConfigPaths,
+    ConfigPathsV2, ConfigProject, ConfigV1, ConfigV2, ConfigV3, Dependency, GdnativeVersion,
+    GodotVersion, PluginConfig, PluginConfigFields, WorkspaceConfig,
 };
-use crate::content;
+use crate::content::{self, ResourceProperty, UserData};
+use crate::gdscript;
+use crate::godot_classes;
+use crate::migrate;
+use crate::naming;
+use crate::rust_source;
+use crate::scene_file::SceneFile;
 use crate::utils;
 
 use chrono::prelude::*;
 use colored::*;
 use convert_case::{Case, Casing};
 use notify::{op, raw_watcher, RawEvent, RecursiveMode, Watcher};
+use path_slash::PathBufExt;
 use pathdiff::diff_paths;
+use serde::Serialize;
+
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The options of the code that is generated for a library created with `new`.
+#[derive(Debug, Default)]
+pub struct LibraryOptions {
+    /// A prefix to add to the class names of the modules created in the library.
+    pub class_prefix: Option<String>,
+    /// The version of gdnative that the generated code is written for.
+    pub gdnative_version: GdnativeVersion,
+    /// The version of Godot that the library is for. If it's not set then it's detected from the Godot project.
+    pub godot: Option<GodotVersion>,
+    /// The sources of the library's dependencies.
+    pub dependencies: ConfigDependencies,
+    /// The package fields and version control of the library's crate.
+    pub crate_options: CrateOptions,
+}
+
+/// The [package] fields and version control of the crate that is created for a new library.
+#[derive(Debug)]
+pub struct CrateOptions {
+    /// The Rust edition that the crate uses.
+    pub edition: String,
+    /// The authors of the crate.
+    pub authors: Vec<String>,
+    /// The SPDX license expression of the crate.
+    pub license: Option<String>,
+    /// Whether a git repository is initialized in the library.
+    pub git: bool,
+}
 
-const VERSION: &'static str = env!("CARGO_PKG_VERSION");
+impl Default for CrateOptions {
+    fn default() -> Self {
+        return CrateOptions {
+            edition: "2021".to_string(),
+            authors: vec![],
+            license: None,
+            git: false,
+        };
+    }
+}
 
-/// Runs `cargo new --lib` to create the library that will contain your Rust components.
+/// Creates the library crate that will contain your Rust components.
 ///
 /// # Arguments
 ///
@@ -28,15 +81,32 @@ const VERSION: &'static str = env!("CARGO_PKG_VERSION");
 /// `targets` - The build targets that should be set. As of writing this, the available targets are windows, linux, and osx with the default being just windows.
 /// `output` - A directory within the godot project to place the gdnlib and build files.
 /// `nativescript_path` - A directory within the godot project to place the nativescript files.
+/// `options` - The options of the code that is generated for the library.
 pub fn create_library(
     destination: PathBuf,
     godot_project_dir: PathBuf,
     targets: String,
     output: PathBuf,
     nativescript_path: PathBuf,
+    options: LibraryOptions,
 ) {
     println!("{}", "creating library".white());
 
+    let LibraryOptions {
+        class_prefix,
+        gdnative_version,
+        godot,
+        dependencies,
+        crate_options,
+    } = options;
+
+    if let Some(prefix) = &class_prefix {
+        if let Err(e) = naming::validate_class_prefix(prefix) {
+            println!("{}", e);
+            exit(1);
+        }
+    }
+
     // Make the destination directory is an absolute path if it is not already one.
     let dest_path = if !destination.is_absolute() {
         utils::absolute_path(destination)
@@ -58,8 +128,8 @@ pub fn create_library(
     };
 
     // Make the output path is an absolute path if it is not already one.
-    let output_as_path_buf = PathBuf::from(output);
-    let output_path = if output_as_path_buf == PathBuf::from("") {
+    let output_as_path_buf = output;
+    let output_path = if output_as_path_buf.as_os_str().is_empty() {
         PathBuf::from(&godot_path)
     } else if !output_as_path_buf.is_absolute() {
         utils::absolute_path(output_as_path_buf)
@@ -71,8 +141,8 @@ pub fn create_library(
     };
 
     // Make the nativescript path an absolute path if it is not already one.
-    let ns_path_as_path_buf = PathBuf::from(nativescript_path);
-    let ns_path = if ns_path_as_path_buf == PathBuf::from("") {
+    let ns_path_as_path_buf = nativescript_path;
+    let ns_path = if ns_path_as_path_buf.as_os_str().is_empty() {
         PathBuf::from(&godot_path)
     } else if !ns_path_as_path_buf.is_absolute() {
         utils::absolute_path(ns_path_as_path_buf)
@@ -83,6 +153,14 @@ pub fn create_library(
         Path::new(&ns_path_as_path_buf).to_path_buf()
     };
 
+    // A library that's created at the root of a workspace is added to the workspace's members.
+    let workspace_dir = current_dir().expect("Unable to get current directory");
+    let workspace_config = read_workspace_config(&workspace_dir);
+    if workspace_config.is_some() && !dest_path.starts_with(&workspace_dir) {
+        println!("Libraries that are created at the root of a workspace have to be inside of it");
+        exit(1);
+    }
+
     // Check to see if the destination directory already exists, we don't want to overwrite an existing project.
     if dest_path.exists() {
         println!("A library with the specified destination already exists, please choose another destination for the library.");
@@ -95,51 +173,33 @@ pub fn create_library(
         println!("The godot project dir provided is not valid.");
         exit(1);
     }
-
-    // Run the `cargo new --lib` command in the destination directory to create the library.
-    let dest_parent = dest_path
-        .parent()
-        .expect("Unable to get destination parent");
-    let dest_basename = dest_path
-        .file_stem()
-        .expect("Unable to get destination basename");
-    set_current_dir(&dest_parent).expect("Unable to change to destination parent directory");
-
-    let dest_basename_string = dest_basename
-        .to_str()
-        .expect("Unable to convert destination basename to string");
-    match Command::new("cargo")
-        .arg("new")
-        .arg(dest_basename_string)
-        .arg("--lib")
-        .output()
+    let godot_version = godot.unwrap_or_else(|| detect_godot_version(&godot_project_path));
+    if class_prefix.is_some()
+        && godot_version == GodotVersion::V3
+        && gdnative_version == GdnativeVersion::V0_9
     {
-        Ok(_v) => (),
-        Err(e) => {
-            println!("{}", e);
-            exit(1);
-        }
+        println!("--class-prefix can only be used with --gdnative 0.10 since gdnative 0.9 registers classes under the names of their structs");
+        exit(1);
     }
 
-    // Set up the Cargo.toml file of the library to have the required tags and dependencies.
-    set_current_dir(&dest_basename).expect("Unable to change to library directory");
-    let cargo_toml_string = read_to_string("Cargo.toml").expect("Unable to read Cargo.toml");
-    let cargo_toml: Cargo = toml::from_str(&cargo_toml_string).expect("Unable to parse Cargo.toml");
-    let cargo_toml_str = toml::to_string(&cargo_toml).expect("Unable to convert to toml to string");
-
-    match write(
-        "Cargo.toml",
-        cargo_toml_str
-            .replace("\\", "")
-            .replace("\"{", "{")
-            .replace("}\"", "}"),
-    ) {
-        Ok(_v) => (),
-        Err(e) => {
-            println!("There was a problem creating the library: {}", e);
-            exit(1);
-        }
+    // Create the crate of the library in the destination directory.
+    let dest_basename_string = dest_path
+        .file_stem()
+        .and_then(|basename| basename.to_str())
+        .expect("Unable to get destination basename");
+    if let Err(e) = naming::validate_crate_name(dest_basename_string) {
+        println!("{}", e);
+        exit(1);
     }
+    create_crate(
+        &dest_path,
+        dest_basename_string,
+        &crate_options,
+        godot_version,
+        gdnative_version,
+        &dependencies,
+    );
+    set_current_dir(&dest_path).expect("Unable to change to library directory");
 
     // Make sure the targets provided are in the list of accepted targets.
     let valid_targets = &["windows", "linux", "osx"];
@@ -157,16 +217,23 @@ pub fn create_library(
         godot: godot_path.to_owned(),
         output: output_path.to_owned(),
         nativescript: ns_path.to_owned(),
+        library_file: None,
+        projects: Vec::new(),
     };
     let config_general = ConfigGeneral {
         name: dest_basename_string.to_string(),
         modules: vec![],
         targets: targets_split,
         plugin: false,
+        class_prefix,
+        gdnative_version,
+        godot_version,
     };
     let config = Config {
         general: config_general,
         paths: config_paths,
+        dependencies,
+        modules: BTreeMap::new(),
     };
     let config_string = toml::to_string(&config).expect("Unable to convert config to string");
 
@@ -179,7 +246,13 @@ pub fn create_library(
     }
 
     // Create the initial src/lib.rs file for the library that will contain all of the created modules.
-    match write("src/lib.rs", content::create_initial_lib_file()) {
+    match write(
+        "src/lib.rs",
+        content::create_initial_lib_file(
+            config.general.godot_version,
+            config.general.gdnative_version,
+        ),
+    ) {
         Ok(_v) => (),
         Err(e) => {
             println!("There was a problem writing to the lib file: {}", e);
@@ -215,658 +288,3377 @@ pub fn create_library(
     let targets_vec: Vec<String> = targets.split(",").map(|s| s.to_string()).collect();
     let targets_str: Vec<&str> = targets_vec.iter().map(AsRef::as_ref).collect();
 
-    // Create the contents of the gdnlib file and write it to the output directory.
-    let gdnlib = content::create_gdnlib_file(
-        dest_basename_string,
+    // Create the contents of the gdnlib or gdextension file and write it to the output directory.
+    let (library_file_name, library_file) = create_library_file(
+        &config,
         &diff_paths(&output_path, &godot_path).expect("Unable to get output path diff"),
         &targets_str,
     );
-    let gdnlib_file_name = format!("{}.gdnlib", dest_basename_string);
-
-    std::fs::File::create(&config.paths.output.join(&gdnlib_file_name))
-        .expect("Unable to create gdnlib file");
 
-    match write(config.paths.output.join(gdnlib_file_name), gdnlib) {
+    match write(config.paths.output.join(library_file_name), library_file) {
         Ok(_v) => (),
         Err(e) => {
-            println!("There was a problem creating the gdnlib file: {}", e);
+            println!("There was a problem creating the library file: {}", e);
             exit(1);
         }
     }
 
+    if let Some(workspace_config) = workspace_config {
+        add_workspace_member(&workspace_dir, workspace_config, &dest_path);
+    }
+
     println!("{}", "library created".green());
 }
 
-/// Creates a new module inside of the library.
+/// Sets up godot_rust_helper in an existing crate. The crate is made to build as a `cdylib`, its existing
+/// gdnlib or gdextension file and .gdns files in the Godot project are found and recorded in the config instead of
+/// being overwritten, and its NativeClass structs are added as modules.
 ///
 /// # Arguments
 ///
-/// `name` - The class name of the module to create; examples include 'Player', 'Princess', 'Mob', 'HUD', etc.
-pub fn create_module(name: &str) {
-    println!("{}", "creating module".white());
+/// `godot_project_dir` - The directory that contains the Godot project. If it's not set then the Godot project is
+/// looked for in the crate's directory and the directories above it.
+/// `targets` - The build targets that should be set. If they're not set then they're taken from the existing library
+/// file, or windows is used if there isn't one.
+/// `output` - A directory within the godot project to place the library file and build files if there isn't an
+/// existing library file.
+/// `nativescript_path` - A directory within the godot project to place the .gdns files that have to be created.
+pub fn init_library(
+    godot_project_dir: Option<PathBuf>,
+    targets: Option<String>,
+    output: PathBuf,
+    nativescript_path: PathBuf,
+) {
+    println!("{}", "initializing library".white());
 
-    // Check to make sure we are in a godot_rust_helper project by checking for the presence of a godot-rust-helper.toml file.
-    let current_dir_path = std::env::current_dir().expect("Unable to get current directory");
-    let config_path = Path::new(&current_dir_path).join("godot-rust-helper.toml");
-    if !config_path.exists() {
-        println!(
-            "The create command can only be used inside of a library created with the new command"
-        );
+    let current_dir_path = current_dir().expect("Unable to get current directory");
+    let config_path = current_dir_path.join("godot-rust-helper.toml");
+    if config_path.exists() {
+        println!("The crate already has a godot-rust-helper.toml config file");
         exit(1);
     }
 
-    // Split the module name on capital letters and then make it all lowercase to use in some places.
-    let name_normalized = name.to_case(Case::Snake);
+    let cargo_toml_path = current_dir_path.join("Cargo.toml");
+    let cargo_toml_string = match read_to_string(&cargo_toml_path) {
+        Ok(v) => v,
+        Err(_e) => {
+            println!("The init command can only be used inside of an existing crate");
+            exit(1);
+        }
+    };
+    let mut cargo_toml = match CargoToml::parse(&cargo_toml_string) {
+        Ok(v) => v,
+        Err(e) => {
+            println!("{}", e);
+            exit(1);
+        }
+    };
+    if cargo_toml.is_workspace() && cargo_toml.library_name().is_none() {
+        init_workspace(&current_dir_path, &cargo_toml);
+        return;
+    }
+    let name = match cargo_toml.library_name() {
+        Some(v) => v.replace("-", "_"),
+        None => {
+            println!("The Cargo.toml file doesn't have a package name");
+            exit(1);
+        }
+    };
 
-    // Check the config to see if a module with the same name was already created.
-    let config_string =
-        read_to_string(&config_path).expect("Unable to read godot-rust-helper.toml config file");
-    let mut config: Config = toml::from_str(&config_string).expect("Unable to parse config");
+    // Make sure the targets provided are in the list of accepted targets.
+    let valid_targets = &["windows", "linux", "osx"];
+    let targets_split: Option<Vec<String>> =
+        targets.map(|targets| targets.split(",").map(|s| s.to_string()).collect());
+    for t in targets_split.iter().flatten() {
+        if !valid_targets.iter().any(|&i| i == t) {
+            println!("An invalid target was specified: {}", t);
+            exit(1);
+        }
+    }
 
-    if config.general.modules.iter().any(|i| i == name) {
-        println!("A module with the same name already exists");
+    // Find the Godot project, either the one that was passed or the first one in the directories above the crate.
+    let godot_path = match godot_project_dir {
+        Some(dir) => {
+            utils::absolute_path(dir).expect("Unable to create absolute path from godot path")
+        }
+        None => match current_dir_path
+            .ancestors()
+            .find(|dir| dir.join("project.godot").exists())
+        {
+            Some(dir) => dir.to_path_buf(),
+            None => {
+                println!("Unable to find the Godot project, use --godot to pass its directory");
+                exit(1);
+            }
+        },
+    };
+    let godot_project_path = godot_path.join("project.godot");
+    if !godot_project_path.exists() {
+        println!("The godot project dir provided is not valid.");
         exit(1);
     }
+    let godot_version = detect_godot_version(&godot_project_path);
+    let gdnative_version = match cargo_toml.dependency_version("gdnative") {
+        Some(version)
+            if version
+                .trim_start_matches(&['^', '=', '~'][..])
+                .starts_with("0.10") =>
+        {
+            GdnativeVersion::V0_10
+        }
+        _ => GdnativeVersion::default(),
+    };
 
-    // Save the module name to the config file so that it can be worked with later.
-    config.general.modules.push(name.to_string());
-    let new_config_string = toml::to_string(&config).expect("Unable to convert config to string");
-
-    match write(config_path, new_config_string) {
-        Ok(_v) => (),
-        Err(e) => {
-            println!("There was a problem creating the module: {}", e);
-            exit(1);
+    // Make sure that the crate builds a dynamic library with the bindings that the generated code uses.
+    cargo_toml.add_cdylib_crate_type();
+    match godot_version {
+        GodotVersion::V3 if !cargo_toml.has_dependency("gdnative") => {
+            let gdnative = Dependency::Version(gdnative_version.dependency().to_string());
+            cargo_toml.set_dependency("gdnative", Some(&gdnative));
+        }
+        GodotVersion::V4 if !cargo_toml.has_dependency("godot") => {
+            cargo_toml.set_dependency("godot", Some(&configs::add_gdext_dep()));
+        }
+        _ => (),
+    }
+    let new_cargo_toml_string = cargo_toml.to_string();
+    if new_cargo_toml_string != cargo_toml_string {
+        match write(&cargo_toml_path, new_cargo_toml_string) {
+            Ok(_v) => println!("updated Cargo.toml"),
+            Err(e) => {
+                println!("There was a problem updating Cargo.toml: {}", e);
+                exit(1);
+            }
         }
     }
 
-    // Create a new src/lib.rs file with the new module added to it.
-    let lib_file = content::create_lib_file(&config.general.modules, config.general.plugin);
-    let lib_file_path = current_dir_path.join("src").join("lib.rs");
+    let mut config = Config {
+        general: ConfigGeneral {
+            name: name.to_owned(),
+            modules: vec![],
+            targets: vec![],
+            plugin: false,
+            class_prefix: None,
+            gdnative_version,
+            godot_version,
+        },
+        paths: ConfigPaths {
+            lib: current_dir_path.to_owned(),
+            godot: godot_path.to_owned(),
+            output: if output.as_os_str().is_empty() {
+                godot_path.to_owned()
+            } else {
+                godot_path.join(&output)
+            },
+            nativescript: if nativescript_path.as_os_str().is_empty() {
+                godot_path.to_owned()
+            } else {
+                godot_path.join(&nativescript_path)
+            },
+            library_file: None,
+            projects: Vec::new(),
+        },
+        dependencies: ConfigDependencies {
+            ext: godot_version == GodotVersion::V4
+                || cargo_toml.has_dependency("godot_rust_helper_ext"),
+            ..Default::default()
+        },
+        modules: BTreeMap::new(),
+    };
 
-    match write(lib_file_path, lib_file) {
-        Ok(_v) => (),
-        Err(e) => {
-            println!("There was a problem creating the lib file: {}", e);
-            exit(1);
+    // Look for a library file that loads the crate's dynamic library. The dynamic library is deployed next to the
+    // file that it references and the targets are the platforms that it has entries for.
+    let library_file_ext = match godot_version {
+        GodotVersion::V3 => "gdnlib",
+        GodotVersion::V4 => "gdextension",
+    };
+    let mut found_targets: Vec<String> = vec![];
+    for library_file_path in utils::find_files_with_extensions(&godot_path, &[library_file_ext]) {
+        let library_file = read_to_string(&library_file_path).unwrap_or_default();
+        let dynamic_library_paths = content::dynamic_library_paths(&library_file, &name);
+        let (_target, dynamic_library_path) = match dynamic_library_paths.first() {
+            Some(v) => v,
+            None => continue,
+        };
+
+        if config.paths.library_file.is_some() {
+            println!(
+                "Skipping {} since the library is already loaded by another file",
+                library_file_path.display()
+            );
+            continue;
+        }
+        println!("found {}", library_file_path.display());
+
+        config.paths.output = godot_path
+            .join(dynamic_library_path)
+            .parent()
+            .expect("Unable to get the directory of the dynamic library")
+            .to_path_buf();
+        config.paths.library_file = Some(library_file_path);
+        for (target, _path) in dynamic_library_paths {
+            if !found_targets.contains(&target) {
+                found_targets.push(target);
+            }
         }
     }
+    if config.paths.library_file.as_ref()
+        == Some(&config.paths.output.join(library_file_name(&config)))
+    {
+        config.paths.library_file = None;
+    }
 
-    // Create the default module file for the module.
-    let mod_file = content::create_mod_file(&name);
-    let mod_file_path = current_dir_path
-        .join("src")
-        .join(format!("{}.rs", name_normalized));
+    config.general.targets = match targets_split {
+        Some(targets) => targets,
+        None if !found_targets.is_empty() => found_targets,
+        None => vec!["windows".to_string()],
+    };
 
-    std::fs::File::create(&mod_file_path).expect("Unable to create module file");
+    // Find the .gdns files that use the library so that the classes keep using them.
+    let mut gdns_files: BTreeMap<String, PathBuf> = BTreeMap::new();
+    if godot_version == GodotVersion::V3 {
+        let class_name_re = regex::Regex::new(r#"(?m)^class_name = "([^"]+)""#)
+            .expect("Unable to create regex pattern");
+        let gdnlib_reference = format!("path=\"{}\"", gdnlib_res_path(&config));
+        for gdns_file_path in
+            utils::find_files_containing(&godot_path, &["gdns"], &gdnlib_reference)
+        {
+            let gdns_file = read_to_string(&gdns_file_path).unwrap_or_default();
+            if let Some(captures) = class_name_re.captures(&gdns_file) {
+                gdns_files.insert(captures[1].to_string(), gdns_file_path);
+            }
+        }
+    }
 
-    match write(mod_file_path, mod_file) {
-        Ok(_v) => (),
-        Err(e) => {
-            println!("There was a problem creating the mod file: {}", e);
-            exit(1);
+    // The .gdns files of the classes that don't have one yet go where most of the existing ones are.
+    if nativescript_path.as_os_str().is_empty() {
+        let mut gdns_dirs: BTreeMap<&Path, usize> = BTreeMap::new();
+        for gdns_file_path in gdns_files.values() {
+            if let Some(dir) = gdns_file_path.parent() {
+                *gdns_dirs.entry(dir).or_insert(0) += 1;
+            }
+        }
+        if let Some((dir, _count)) = gdns_dirs.iter().max_by_key(|(_dir, count)| **count) {
+            config.paths.nativescript = dir.to_path_buf();
         }
     }
 
-    // Create the gdns file.
-    let gdns_file_contents = content::create_gdns_file(
-        &config.general.name,
-        &name,
-        &diff_paths(&config.paths.output, &config.paths.godot)
-            .expect("Unable to get output path diff"),
-    );
-    let gdns_file_name = format!("{}.gdns", name_normalized);
-    let gdns_file_path = &config.paths.nativescript.join(gdns_file_name);
+    // Add the NativeClass structs as modules along with the .gdns files that were found for them.
+    let icon_re = regex::Regex::new(r#"(?m)^script_class_icon_path = "([^"]+)""#)
+        .expect("Unable to create regex pattern");
+    for class in rust_source::find_native_classes(&current_dir_path.join("src")) {
+        if config.general.modules.contains(&class.name) {
+            println!(
+                "Skipping {} in {} since a class with the same name was already found",
+                class.name,
+                class.file.display()
+            );
+            continue;
+        }
 
-    std::fs::File::create(&gdns_file_path).expect("Unable to create gdns file");
+        let mut module_config = ConfigModule {
+            base: class.base.to_owned(),
+            ..Default::default()
+        };
+        if class.mod_path != naming::mod_name(&class.name) {
+            module_config.path = Some(class.mod_path.to_owned());
+        }
+        if let Some(gdns_file_path) = gdns_files.remove(&class.name) {
+            println!("found {}", gdns_file_path.display());
+            let gdns_file = read_to_string(&gdns_file_path).unwrap_or_default();
+            module_config.named = gdns_file.contains("\nscript_class_name = ");
+            module_config.icon = icon_re
+                .captures(&gdns_file)
+                .map(|captures| captures[1].to_string());
+            if gdns_file_path
+                != config
+                    .paths
+                    .nativescript
+                    .join(naming::gdns_file_name(&class.name))
+            {
+                module_config.gdns = Some(gdns_file_path);
+            }
+        }
+        if module_config != ConfigModule::default() {
+            config.modules.insert(class.name.to_owned(), module_config);
+        }
+
+        println!(
+            "found {} ({}) in {}",
+            class.name,
+            class.base.as_deref().unwrap_or("Node"),
+            diff_paths(&class.file, &current_dir_path)
+                .unwrap_or_else(|| class.file.to_owned())
+                .display()
+        );
+        config.general.modules.push(class.name);
+    }
+    for (class_name, gdns_file_path) in &gdns_files {
+        println!(
+            "{} uses the class {} which isn't in the library",
+            gdns_file_path.display(),
+            class_name
+        );
+    }
 
-    match write(gdns_file_path, gdns_file_contents) {
+    let config_string = toml::to_string(&config).expect("Unable to convert config to string");
+    match write(&config_path, config_string) {
         Ok(_v) => (),
         Err(e) => {
-            println!("There was a problem creating the gdns file: {}", e);
+            println!("There was a problem creating the config file: {}", e);
             exit(1);
         }
     }
 
-    println!("{}", "module created".green());
-}
-
-/// Removes a created module from the config and filesystem.
-///
-/// # Arguments
-///
-/// `name` The name of the module to destroy. This should be the same name that was provided when it was created.
-pub fn destroy_module(name: &str) {
-    println!("{}", "destroying module".white());
+    // Create the library file and the .gdns files that don't exist yet.
+    for dir in &[&config.paths.output, &config.paths.nativescript] {
+        if let Err(e) = create_dir_all(dir) {
+            println!(
+                "There was a problem creating the {} directory: {}",
+                dir.display(),
+                e
+            );
+            exit(1);
+        }
+    }
 
-    // Check to see if we are in the directory of a library created with the `new` command by checking for the presence of a godot-rust-helper.toml file.
-    let current_dir_path = std::env::current_dir().expect("Unable to get current directory");
-    let config_path = Path::new(&current_dir_path).join("godot-rust-helper.toml");
-    if !config_path.exists() {
-        println!(
-            "The create command can only be used inside of a library created with the new command"
+    if config.paths.library_file.is_none() && !library_file_path(&config).exists() {
+        let targets_str: Vec<&str> = config.general.targets.iter().map(AsRef::as_ref).collect();
+        let (_library_file_name, library_file) = create_library_file(
+            &config,
+            &diff_paths(&config.paths.output, &godot_path).expect("Unable to get output path diff"),
+            &targets_str,
         );
-        exit(1);
-    }
 
-    // Split the module name on capital letters and then make it all lowercase to use when removing the module file.
-    let name_normalized = name.to_case(Case::Snake);
+        match write(library_file_path(&config), library_file) {
+            Ok(_v) => println!("created {}", library_file_path(&config).display()),
+            Err(e) => {
+                println!("There was a problem creating the library file: {}", e);
+                exit(1);
+            }
+        }
+    }
 
-    // Remove the module from the config file and save it again.
-    let config_string =
-        read_to_string(&config_path).expect("Unable to read godot-rust-helper.toml config file");
-    let mut config: Config = toml::from_str(&config_string).expect("Unable to parse config");
+    if godot_version == GodotVersion::V3 {
+        for name in &config.general.modules {
+            let gdns_file_path = gdns_file_path(&config, name);
+            if gdns_file_path.exists() {
+                continue;
+            }
 
-    config.general.modules.retain(|x| *x != name);
-    let new_config_string = toml::to_string(&config).expect("Unable to convert config to string");
+            let gdns_file_contents = content::create_gdns_file(
+                &content::godot_class_name(name, &config.modules),
+                &gdnlib_res_path(&config),
+                config.modules.get(name),
+            );
+            match write(&gdns_file_path, gdns_file_contents) {
+                Ok(_v) => println!("created {}", gdns_file_path.display()),
+                Err(e) => {
+                    println!("There was a problem creating the gdns file: {}", e);
+                    exit(1);
+                }
+            }
+        }
+    }
 
-    match write(config_path, new_config_string) {
-        Ok(_v) => (),
-        Err(e) => {
-            println!("There was a problem destroying the module: {}", e);
-            exit(1);
+    // The lib file is only generated for crates that don't have one. An existing lib file is left as it is and
+    // create and destroy can only update it once it has the generated regions.
+    let lib_file_path = current_dir_path.join("src").join("lib.rs");
+    match read_to_string(&lib_file_path) {
+        Ok(lib_file) => {
+            if content::check_lib_file(&lib_file, godot_version).is_err() {
+                println!("{} doesn't have the begin and end markers of the generated code so it was left as it is. Register the modules that were found in the `init` function if they aren't already, and add the markers around the `mod` declarations and the class registrations so that create and destroy can update them.", lib_file_path.display());
+            }
+        }
+        Err(_e) => {
+            if let Err(e) = create_dir_all(current_dir_path.join("src")).and_then(|_v| {
+                write(
+                    &lib_file_path,
+                    content::create_initial_lib_file(godot_version, gdnative_version),
+                )
+            }) {
+                println!("There was a problem writing to the lib file: {}", e);
+                exit(1);
+            }
+            update_lib_file(&lib_file_path, &config);
         }
     }
 
-    // Create a new src/lib.rs file based on the modules that are left over.
-    let lib_file = if config.general.modules.len() == 0 {
-        content::create_initial_lib_file()
-    } else {
-        content::create_lib_file(&config.general.modules, config.general.plugin)
+    println!("{}", "library initialized".green());
+}
+
+/// Writes the config of a workspace whose members are libraries that were set up with `new` or `init`. The members
+/// of the Cargo workspace that don't have a godot-rust-helper.toml config are skipped.
+///
+/// # Arguments
+///
+/// `workspace_dir` - The root of the workspace.
+/// `cargo_toml` - The workspace's Cargo.toml file.
+fn init_workspace(workspace_dir: &Path, cargo_toml: &CargoToml) {
+    let mut workspace_config = WorkspaceConfig {
+        workspace: Default::default(),
     };
 
-    let lib_file_path = current_dir_path.join("src").join("lib.rs");
+    for member in cargo_toml.workspace_members() {
+        // Members can be listed with a glob for the directories in a directory, e.g. `crates/*`.
+        let member_dirs = match member.strip_suffix("/*") {
+            Some(dir) => {
+                let mut member_dirs: Vec<PathBuf> = std::fs::read_dir(workspace_dir.join(dir))
+                    .map(|entries| entries.flatten().map(|e| e.path()).collect())
+                    .unwrap_or_default();
+                member_dirs.sort();
+                member_dirs
+            }
+            None => vec![workspace_dir.join(&member)],
+        };
 
-    match write(lib_file_path, lib_file) {
-        Ok(_v) => (),
-        Err(e) => {
-            println!("There was a problem creating the lib file: {}", e);
-            exit(1);
+        for member_dir in member_dirs {
+            let member_path = diff_paths(&member_dir, workspace_dir)
+                .expect("Unable to get member path diff")
+                .to_slash_lossy()
+                .to_string();
+            match read_to_string(member_dir.join("godot-rust-helper.toml")) {
+                Ok(config_string) if parse_workspace_config(&config_string).is_none() => {
+                    println!("found {}", member_path);
+                    workspace_config.workspace.members.push(member_path);
+                }
+                _ => println!(
+                    "Skipping {} since it doesn't have a godot-rust-helper.toml config, use init in it first",
+                    member_path
+                ),
+            }
         }
     }
 
-    // Remove the module's .rs file.
-    let mod_file_path = current_dir_path
-        .join("src")
-        .join(format!("{}.rs", name_normalized));
-    match remove_file(mod_file_path) {
+    let config_string =
+        toml::to_string(&workspace_config).expect("Unable to convert config to string");
+    match write(workspace_dir.join("godot-rust-helper.toml"), config_string) {
         Ok(_v) => (),
         Err(e) => {
-            println!("There was a problem removing the module file: {}", e);
+            println!("There was a problem creating the config file: {}", e);
             exit(1);
         }
     }
 
-    // Remove the corresponding .gdns file from the Godot project directory.
-    let gdns_file_name = format!("{}.gdns", name.to_lowercase());
-    Command::new("rm")
-        .arg(&config.paths.nativescript.join(gdns_file_name))
-        .status()
-        .expect("Unable to remove the corresponding gdns file from the Godot project.");
-
-    println!("{}", "module destroyed".green());
+    println!("{}", "workspace initialized".green());
 }
 
-/// Runs the `cargo build` command and copies the target files into the Godot project directory.
-pub fn build_library() {
-    let version_notice = format!(
-        "{}{}",
-        "godot_rust_helper v".white().underline(),
-        VERSION.white().underline()
-    );
-    println!("{}", version_notice);
-    println!("{}", "building...".cyan());
-
-    let root_dir = utils::find_file("godot-rust-helper.toml".to_string());
-
-    // Get the config so that we can check the targets later.
-    let config_path = root_dir.join("godot-rust-helper.toml");
-    let config_string =
-        read_to_string(&config_path).expect("Unable to read godot-rust-helper.toml config file");
-    let config: Config = toml::from_str(&config_string).expect("Unable to parse config");
+/// The options of a module created with `create`.
+#[derive(Debug, Default)]
+pub struct CreateOptions {
+    /// Indicates whether the class should be registered as a tool class so that it also runs in the editor.
+    pub tool: bool,
+    /// The `user_data` wrapper that the module's struct is stored in.
+    pub user_data: UserData,
+    /// Indicates whether the module should be added to the Godot project's autoloads.
+    pub autoload: bool,
+    /// The path to a scene to create that uses the module, relative to the Godot project. If the path is empty then
+    /// the scene is named after the module and put in the root of the Godot project.
+    pub scene: Option<PathBuf>,
+    /// The path to a GDScript file to convert into the module.
+    pub from_gd: Option<PathBuf>,
+    /// Indicates whether the scenes and resources that use the GDScript file should use the module's gdns file
+    /// instead.
+    pub swap_references: bool,
+    /// Indicates whether the class should be a named script class so that it shows up in Godot's "Create New Node"
+    /// dialog.
+    pub named: bool,
+    /// The path to the icon that Godot shows for the named script class.
+    pub icon: Option<PathBuf>,
+    /// The version of Godot that the library is expected to be for, as it's passed to `--godot`.
+    pub godot: Option<GodotVersion>,
+    /// The member library of the workspace to create the module in when the command is used at the root of a
+    /// workspace.
+    pub lib: Option<String>,
+}
 
-    let status = Command::new("cargo")
-        .arg("build")
-        .status()
-        .expect("Unable to run cargo build");
+/// Creates a new module inside of the library.
+///
+/// # Arguments
+///
+/// `name` - The class name of the module to create; examples include 'Player', 'Princess', 'Mob', 'HUD', etc. When a
+/// GDScript file is converted the name defaults to its `class_name` or the name of the file.
+/// `options` - The options of the module.
+pub fn create_module(name: Option<&str>, mut options: CreateOptions) {
+    println!("{}", "creating module".white());
 
-    if !status.success() {
-        println!("{}", "build failed".red());
-        exit(1);
+    if let Some(scene_path) = &options.scene {
+        if !utils::is_contained_relative_path(scene_path) {
+            println!("The scene path has to be relative to the Godot project and inside of it");
+            exit(1);
+        }
     }
 
-    // Get the path to where the build files are stored.
-    let targets_dir = root_dir.join("target").join("debug");
+    // Files that are passed relative to the current directory have to keep pointing to the same files when the
+    // command moves into a workspace member.
+    if options.lib.is_some() {
+        let current_dir_path = current_dir().expect("Unable to get current directory");
+        for path in options.from_gd.iter_mut().chain(options.icon.iter_mut()) {
+            if path.is_relative() && current_dir_path.join(&path).exists() {
+                *path = current_dir_path.join(&path);
+            }
+        }
+    }
+    enter_workspace_member("create", options.lib.as_deref());
+
+    let config = read_config("create");
+    check_godot_version(&config, options.godot);
+
+    // Godot 4 classes are registered through GDExtension so the options that depend on NativeScript don't apply.
+    if config.general.godot_version == GodotVersion::V4 {
+        let unsupported_options = [
+            ("--user-data", options.user_data != UserData::default()),
+            ("--from-gd", options.from_gd.is_some()),
+            ("--named", options.named),
+            ("--autoload", options.autoload),
+        ];
+        if let Some((option, _)) = unsupported_options.iter().find(|(_, used)| *used) {
+            println!("{} can't be used in a library for Godot 4", option);
+            exit(1);
+        }
+    }
 
-    let ext = utils::get_dynamic_library_ext();
-    let extra = if cfg!(windows) { "" } else { "lib" };
+    // Parse the GDScript file that is being converted, if there is one, before anything is changed.
+    let gd_file = options.from_gd.as_ref().map(|gd_path| {
+        let gd_file_path = resolve_godot_file(&config, gd_path);
+        let gd_file_string = match read_to_string(&gd_file_path) {
+            Ok(v) => v,
+            Err(e) => {
+                println!("Unable to read {}: {}", gd_file_path.display(), e);
+                exit(1);
+            }
+        };
 
-    let file = format!("{}{}.{}", extra, config.general.name, ext);
-    let file_path = targets_dir.join(file.to_case(Case::Snake));
+        match gdscript::parse(&gd_file_string) {
+            Ok(v) => (gd_file_path, v),
+            Err(e) => {
+                println!(
+                    "{} can't be converted because {}",
+                    gd_file_path.display(),
+                    e
+                );
+                exit(1);
+            }
+        }
+    });
+
+    let name = match (name, &gd_file) {
+        (Some(name), _) => name.to_string(),
+        (None, Some((gd_file_path, script))) => match &script.class_name {
+            Some(class_name) => class_name.to_string(),
+            None => gd_file_path
+                .file_stem()
+                .expect("Unable to get the GDScript file name")
+                .to_string_lossy()
+                .to_case(Case::Pascal),
+        },
+        (None, None) => {
+            println!("A name is required unless the module is created from a GDScript file");
+            exit(1);
+        }
+    };
+    let (base, tool, mod_file) = match &gd_file {
+        Some((_, script)) => {
+            // Scripts without `extends` inherit from Reference and scripts that extend another script or a custom
+            // class fall back to the default class since only built-in classes can be inherited from.
+            let base = match &script.extends {
+                Some(extends) if godot_classes::inherits(extends, "Object") == Some(true) => {
+                    extends.to_string()
+                }
+                Some(extends) => {
+                    println!(
+                        "{} isn't a built-in Godot class so the module inherits from {} instead",
+                        extends,
+                        content::DEFAULT_BASE_CLASS
+                    );
+                    content::DEFAULT_BASE_CLASS.to_string()
+                }
+                None => "Reference".to_string(),
+            };
+
+            let (mod_file, notes) = content::create_mod_file_from_gd(
+                &name,
+                &base,
+                script,
+                options.user_data,
+                config.general.gdnative_version,
+            );
+            for note in notes {
+                println!("{}", note);
+            }
 
-    Command::new("cp")
-        .arg(file_path)
-        .arg(&config.paths.godot.join(&config.paths.output))
-        .output()
-        .expect("Unable to copy build files");
+            (base, options.tool || script.tool, mod_file)
+        }
+        None => (
+            content::DEFAULT_BASE_CLASS.to_string(),
+            options.tool,
+            match config.general.godot_version {
+                GodotVersion::V3 => content::create_mod_file(
+                    &name,
+                    options.user_data,
+                    config.general.gdnative_version,
+                ),
+                GodotVersion::V4 => content::create_gdext_mod_file(
+                    &name,
+                    &naming::godot_class_name(&name, class_prefix(&config)),
+                    content::DEFAULT_BASE_CLASS,
+                    content::is_tool_class(&name, &config.modules, config.general.plugin)
+                        || options.tool,
+                ),
+            },
+        ),
+    };
 
-    println!("{}", "build complete".green());
+    // Icons that are already in the Godot project are used where they are and the others are copied next to the
+    // module's gdns file.
+    let icon = options.icon.as_ref().map(|icon_path| {
+        let icon_file_path = resolve_godot_file(&config, icon_path);
+        if !icon_file_path.is_file() {
+            println!("The icon {} doesn't exist", icon_path.display());
+            exit(1);
+        }
+        let icon_file_path = utils::absolute_path(&icon_file_path).unwrap_or(icon_file_path);
 
-    // Delete this next iteration if not needed anymore.
-    // for target in &config.general.targets {
-    //     // Since the user can have multiple targets, we need to check what target
-    //     // they can actually build for and set that as the target to use.
-    //     match target.as_ref() {
-    //         "linux" => {
-    //         },
-    //         _ => exit(1)
-    //     }
+        if icon_file_path.starts_with(&config.paths.godot) {
+            (icon_file_path, None)
+        } else {
+            let mut icon_file_name = PathBuf::from(naming::snake_case(&name));
+            if let Some(ext) = icon_file_path.extension() {
+                icon_file_name.set_extension(ext);
+            }
+            let project_icon_file_path = config.paths.nativescript.join(icon_file_name);
+            (project_icon_file_path, Some(icon_file_path))
+        }
+    });
+
+    // The base of a converted script comes from its `extends` so it is saved like the ones of imported modules.
+    let module_config = ConfigModule {
+        base: gd_file.as_ref().map(|_| base.to_owned()),
+        tool,
+        named: options.named,
+        icon: icon
+            .as_ref()
+            .map(|(path, _)| utils::godot_resource_path(&config.paths.godot, path)),
+        ..Default::default()
+    };
+    let config = add_module("create", &name, module_config, &mod_file);
 
-    //     let ext = if target == "linux" {
-    //         "so"
-    //     } else if target == "osx" {
-    //         "dylib"
-    //     } else if target == "windows" {
-    //         "dll"
-    //     } else {
-    //         exit(1);
-    //     };
-    //     let extra = if cfg!(windows) { "" } else { "lib" };
+    if let Some((project_icon_file_path, Some(icon_file_path))) = icon {
+        if project_icon_file_path.exists() {
+            println!(
+                "The icon {} already exists so it was not copied",
+                project_icon_file_path.display()
+            );
+        } else {
+            match std::fs::copy(&icon_file_path, &project_icon_file_path) {
+                Ok(_v) => println!("copied the icon to {}", project_icon_file_path.display()),
+                Err(e) => {
+                    println!("There was a problem copying the icon: {}", e);
+                    exit(1);
+                }
+            }
+        }
+    }
+    let gdns_file_path = gdns_file_path(&config, &name);
+    let gdns_res_path = utils::godot_resource_path(&config.paths.godot, &gdns_file_path);
 
-    //     let file = format!("{}{}.{}", extra, config.general.name, ext);
-    //     let file_path = targets_dir.join(file);
+    if options.autoload {
+        let mut project_file = read_project_file(&config);
+        add_autoload(&config, &mut project_file, &name);
+        write_project_file(&config, &project_file);
+    }
 
-    //     Command::new("cp")
-    //         .arg(file_path)
-    //         .arg(&config.paths.godot.join(&config.paths.output))
-    //         .output()
-    //         .expect("Unable to copy build files");
+    // Create a scene whose root node uses the module's script.
+    if let Some(scene_path) = &options.scene {
+        let godot_class_name = content::godot_class_name(&name, &config.modules);
+        let scene_path = if scene_path.as_os_str().is_empty() {
+            PathBuf::from(naming::snake_case(&name))
+        } else {
+            scene_path.to_owned()
+        };
+        let scene_file_contents = match config.general.godot_version {
+            GodotVersion::V3 => content::create_tscn_file(&gdns_res_path, &godot_class_name, &base),
+            GodotVersion::V4 => {
+                content::create_gdext_tscn_file(&godot_class_name, &godot_class_name)
+            }
+        };
 
-    //     println!("{}", "build complete".green());
-    // }
+        write_new_godot_file(
+            &config.paths.godot.join(scene_path.with_extension("tscn")),
+            &scene_file_contents,
+            "scene",
+        );
+    }
+
+    // Point the scenes and resources that use the GDScript file to the module's gdns file instead.
+    if let Some((gd_file_path, _)) = gd_file.filter(|_| options.swap_references) {
+        let gd_file_path = utils::absolute_path(&gd_file_path).unwrap_or(gd_file_path);
+        if !gd_file_path.starts_with(&config.paths.godot) {
+            println!(
+                "The GDScript file isn't in the Godot project so there are no references to swap"
+            );
+        } else {
+            let reference = format!(
+                "path=\"{}\"",
+                utils::godot_resource_path(&config.paths.godot, &gd_file_path)
+            );
+            for file_path in
+                utils::find_files_containing(&config.paths.godot, &["tscn", "tres"], &reference)
+            {
+                let file = match read_to_string(&file_path) {
+                    Ok(v) => v,
+                    Err(_e) => continue,
+                };
+                let new_file = file.replace(&reference, &format!("path=\"{}\"", gdns_res_path));
+                match write(&file_path, new_file) {
+                    Ok(_v) => println!("updated reference in {}", file_path.display()),
+                    Err(e) => {
+                        println!(
+                            "There was a problem updating the reference in {}: {}",
+                            file_path.display(),
+                            e
+                        );
+                        exit(1);
+                    }
+                }
+            }
+        }
+    }
+
+    println!("{}", "module created".green());
 }
 
-/// Watches for changes in the src directory of the library and then automatically runs the build command.
-/// This is used when build --watch is called.
-pub fn watch_library() {
-    let lib_dir = utils::find_file("godot-rust-helper.toml".to_string());
-    let (tx, rx) = channel();
+/// Creates a new module that inherits from `Resource` and exports the given properties so that it can be used to
+/// store data in the Godot project. A sample .tres file that uses the module's script can be created as well.
+///
+/// # Arguments
+///
+/// `name` - The class name of the resource to create; examples include 'Item', 'EnemyStats', 'Dialogue', etc.
+/// `properties` - The properties that the resource exports.
+/// `tres` - The path to the sample .tres file to create, relative to the Godot project.
+pub fn create_resource(name: &str, properties: &[ResourceProperty], tres: Option<PathBuf>) {
+    println!("{}", "creating resource".white());
+
+    if let Some(tres_path) = &tres {
+        if !utils::is_contained_relative_path(tres_path) {
+            println!(
+                "The sample resource path has to be relative to the Godot project and inside of it"
+            );
+            exit(1);
+        }
+    }
 
-    build_with_time_log();
+    // Make sure that properties aren't defined more than once before anything is changed.
+    for (i, property) in properties.iter().enumerate() {
+        if properties[..i].iter().any(|p| p.name == property.name) {
+            println!("The property {} is defined more than once", property.name);
+            exit(1);
+        }
+    }
 
-    let mut last_checked = chrono::offset::Local::now();
+    let config = read_config("create-resource");
+    require_godot_3(&config, "create-resource");
+    let config = add_module(
+        "create-resource",
+        name,
+        ConfigModule::default(),
+        &content::create_resource_mod_file(name, properties, config.general.gdnative_version),
+    );
 
-    let mut watcher = raw_watcher(tx).expect("Unable to create watcher");
-    watcher
-        .watch(lib_dir.join("src"), RecursiveMode::Recursive)
-        .expect("Unable to watch src directory");
+    // Create the sample .tres file that uses the resource's script.
+    if let Some(tres_path) = tres {
+        let gdns_file_path = gdns_file_path(&config, name);
+        let tres_file_contents = content::create_tres_file(
+            &utils::godot_resource_path(&config.paths.godot, &gdns_file_path),
+            properties,
+        );
 
-    loop {
-        match rx.recv() {
-            Ok(RawEvent {
-                path: Some(_path),
-                op: Ok(op),
-                cookie: _,
-            }) => {
-                if op.contains(op::WRITE) {
-                    let now = chrono::offset::Local::now();
-                    if (now - last_checked).num_seconds() == 0 {
-                        build_with_time_log();
-                    }
-                    last_checked = chrono::offset::Local::now();
-                }
-            }
-            Ok(event) => println!("broken event: {:?}", event),
-            Err(e) => println!("watch error: {:?}", e),
-        }
+        write_new_godot_file(
+            &config.paths.godot.join(tres_path.with_extension("tres")),
+            &tres_file_contents,
+            "sample resource",
+        );
     }
+
+    println!("{}", "resource created".green());
 }
 
-/// Changes the path of the project and the godot project directory in the config if you cloned/downlaoded the project from elsewhere.
+/// Removes a created module from the config and filesystem.
+///
+/// Before anything is removed, the Godot project is checked for scenes, resources, and scripts that still reference
+/// the module's .gdns file. If any are found they are listed and the module is not destroyed unless `force` is set.
 ///
 /// # Arguments
 ///
-/// `godot_project_dir` - The directory that contains the Godot project that the modules are for.
-/// `targets` - The new build targets that should be set.
-pub fn rebase(godot_project_dir: PathBuf, targets: String) {
-    println!("{}", "rebasing library".white());
+/// `name` The name of the module to destroy. This should be the same name that was provided when it was created.
+/// `force` - Indicates whether the module should be destroyed even if it is still referenced in the Godot project.
+/// `keep_source` - Indicates whether the module's .rs file should be kept so that only the class is unregistered.
+pub fn destroy_module(name: &str, force: bool, keep_source: bool) {
+    println!("{}", "destroying module".white());
 
-    // Check to see if we are in the directory of a library created with the `new` command.
-    // This is done by checking to see if there is a godot-rust-helper.toml configuration file present in the current directory.
     let current_dir_path = std::env::current_dir().expect("Unable to get current directory");
     let config_path = Path::new(&current_dir_path).join("godot-rust-helper.toml");
-    if !config_path.exists() {
-        println!(
-            "The create command can only be used inside of a library created with the new command"
+    let mut config = read_config("destroy");
+
+    // Make sure that the lib file can be updated before anything is changed.
+    let lib_file_path = current_dir_path.join("src").join("lib.rs");
+    check_lib_file(&lib_file_path, &config);
+
+    // The module's autoload is removed along with the module so it doesn't count as a reference.
+    let gdns_res_path =
+        utils::godot_resource_path(&config.paths.godot, &gdns_file_path(&config, name));
+    let project_file_path = config.paths.godot.join("project.godot");
+    let mut project_file = read_project_file(&config);
+    let autoload_name = find_autoload(&project_file, &gdns_res_path);
+    if let Some(autoload_name) = &autoload_name {
+        project_file.remove("autoload", autoload_name);
+    }
+
+    // Check every Godot project that the library is deployed to for anything that still uses the module's .gdns file
+    // before removing anything.
+    let project_configs = godot_project_configs(&config);
+    let mut is_referenced = false;
+    for project_config in &project_configs {
+        let gdns_res_path = utils::godot_resource_path(
+            &project_config.paths.godot,
+            &gdns_file_path(project_config, name),
         );
+        let reference = format!("{}\"", gdns_res_path);
+        let references: Vec<PathBuf> = utils::find_files_containing(
+            &project_config.paths.godot,
+            &["tscn", "tres", "gd", "godot"],
+            &reference,
+        )
+        .into_iter()
+        .filter(|path| *path != project_file_path || project_file.to_string().contains(&reference))
+        .collect();
+
+        if references.is_empty() {
+            continue;
+        }
+        is_referenced = true;
+
+        if project_config.paths.godot == config.paths.godot {
+            println!("{} is still referenced by:", gdns_res_path);
+        } else {
+            println!(
+                "{} is still referenced in {} by:",
+                gdns_res_path,
+                project_config.paths.godot.display()
+            );
+        }
+        for reference in &references {
+            let reference_path = diff_paths(reference, &project_config.paths.godot)
+                .unwrap_or_else(|| reference.to_owned());
+            println!("  {}", reference_path.display());
+        }
+    }
+
+    if is_referenced && !force {
+        println!("Remove these references or use --force to destroy the module anyway");
         exit(1);
     }
 
-    // Get the config file so that we can update values.
-    let config_string =
-        read_to_string(&config_path).expect("Unable to read godot-rust-helper.toml config file");
-    let mut config: Config = toml::from_str(&config_string).expect("Unable to parse config");
+    // Remove the module from the config file and save it again.
+    config.general.modules.retain(|x| *x != name);
+    let module_config = config.modules.remove(name);
+    let new_config_string = toml::to_string(&config).expect("Unable to convert config to string");
 
-    // Get the new paths to use for the config.
-    let lib_path = current_dir().expect("Unable to get current directory");
-    let godot_path = utils::absolute_path(godot_project_dir)
-        .expect("Unable to create absolute path from destination path")
-        .as_path()
-        .to_owned();
+    match write(config_path, new_config_string) {
+        Ok(_v) => (),
+        Err(e) => {
+            println!("There was a problem destroying the module: {}", e);
+            exit(1);
+        }
+    }
 
-    // Update the paths in the config.
-    config.paths.lib = lib_path;
-    config.paths.godot = godot_path;
+    // Update the generated regions of the src/lib.rs file based on the modules that are left over.
+    update_lib_file(&lib_file_path, &config);
 
-    // If new targets were set then we update it in the config and the gdnlib file.
-    if !targets.is_empty() {
-        let valid_targets = &["windows", "linux", "osx"];
-        let targets_split: Vec<String> = targets.split(",").map(|s| s.to_string()).collect();
+    if let Some(autoload_name) = autoload_name {
+        write_project_file(&config, &project_file);
+        println!("removed the {} autoload", autoload_name);
+    }
 
-        for t in &targets_split {
-            if !valid_targets.iter().any(|&i| i == t) {
-                println!("An invalid target was specified: {}", t);
+    // Remove the module's .rs file unless it should be kept around. Modules that were imported from a file that
+    // isn't named after them are never removed since the file could contain other code.
+    let has_own_file = module_config.map(|m| m.path.is_none()).unwrap_or(true);
+    if !keep_source && has_own_file {
+        let mod_file_path = current_dir_path
+            .join("src")
+            .join(naming::mod_file_name(name));
+        match remove_file(mod_file_path) {
+            Ok(_v) => (),
+            Err(e) => {
+                println!("There was a problem removing the module file: {}", e);
                 exit(1);
             }
         }
+    }
 
-        config.general.targets = targets_split;
+    // Remove the corresponding .gdns file from every Godot project directory.
+    if config.general.godot_version == GodotVersion::V3 {
+        for project_config in &project_configs {
+            Command::new("rm")
+                .arg(gdns_file_path(project_config, name))
+                .status()
+                .expect("Unable to remove the corresponding gdns file from the Godot project.");
+        }
     }
 
-    let targets_vec: Vec<String> = targets.split(",").map(|s| s.to_string()).collect();
-    let targets_str: Vec<&str> = targets_vec.iter().map(AsRef::as_ref).collect();
+    println!("{}", "module destroyed".green());
+}
 
-    let gdnlib = content::create_gdnlib_file(
-        &config.general.name.to_owned(),
-        &config.paths.output,
-        &targets_str,
-    );
-    let gdnlib_file_name = format!("{}.gdnlib", &config.general.name.to_owned());
-    let gdnlib_path = config.paths.output.join(gdnlib_file_name);
+/// Renames a module by renaming its source file, its struct and impl, its entry in the config and lib file, and its
+/// .gdns file. Any scenes or resources in the Godot project that reference the old .gdns file are updated to use the
+/// new one.
+///
+/// # Arguments
+///
+/// `name` - The current class name of the module.
+/// `new_name` - The class name that the module should be renamed to.
+pub fn rename_module(name: &str, new_name: &str) {
+    println!("{}", "renaming module".white());
+
+    let current_dir_path = std::env::current_dir().expect("Unable to get current directory");
+    let config_path = Path::new(&current_dir_path).join("godot-rust-helper.toml");
+    let mut config = read_config("rename");
+    require_godot_3(&config, "rename");
+
+    // Make sure that the lib file can be updated before anything is changed.
+    let lib_file_path = current_dir_path.join("src").join("lib.rs");
+    check_lib_file(&lib_file_path, &config);
+
+    // Make sure that the module exists and that the new name isn't already taken.
+    let module_index = match config.general.modules.iter().position(|i| i == name) {
+        Some(v) => v,
+        None => {
+            println!("A module with the name {} does not exist", name);
+            exit(1);
+        }
+    };
+    let crate_name = Some(config.general.name.as_str()).filter(|_| !config.general.plugin);
+    if let Err(e) = naming::validate_class_name(new_name, crate_name, class_prefix(&config)) {
+        println!("{}", e);
+        exit(1);
+    }
+    if config.general.modules.iter().any(|i| i == new_name) {
+        println!("A module with the same name already exists");
+        exit(1);
+    }
+    if config
+        .modules
+        .get(name)
+        .map(|m| m.path.is_some())
+        .unwrap_or(false)
+    {
+        println!("Only modules that are in their own file, like the ones created with the create command, can be renamed");
+        exit(1);
+    }
+
+    // Rename the module's .rs file and replace the old struct name with the new one.
+    let mod_file_path = current_dir_path
+        .join("src")
+        .join(naming::mod_file_name(name));
+    let new_mod_file_path = current_dir_path
+        .join("src")
+        .join(naming::mod_file_name(new_name));
+    if new_mod_file_path != mod_file_path && new_mod_file_path.exists() {
+        println!(
+            "{} already exists, move it out of the way to rename the module",
+            new_mod_file_path.display()
+        );
+        exit(1);
+    }
+
+    let mod_file = read_to_string(&mod_file_path).expect("Unable to read module file");
+    let new_mod_file = match rust_source::rename_struct(&mod_file, name, new_name) {
+        Ok(v) => v,
+        Err(e) => {
+            println!("Unable to parse {}: {}", mod_file_path.display(), e);
+            exit(1);
+        }
+    };
 
-    match write(gdnlib_path, gdnlib.replace("\\", "")) {
+    match write(&new_mod_file_path, new_mod_file) {
         Ok(_v) => (),
         Err(e) => {
-            println!("There was a problem creating the gdnlib file: {}", e);
+            println!("There was a problem writing the renamed module file: {}", e);
             exit(1);
         }
     }
+    if new_mod_file_path != mod_file_path {
+        match remove_file(&mod_file_path) {
+            Ok(_v) => (),
+            Err(e) => {
+                println!("There was a problem removing the old module file: {}", e);
+                exit(1);
+            }
+        }
+    }
 
-    // Finally we write back the config file.
-    let config_string = toml::to_string(&config).expect("Unable to convert config to string");
-    match write("godot-rust-helper.toml", config_string) {
+    // Update the module in the config, keeping its position and its settings, and save it again. A .gdns file that
+    // was found by `init` is renamed in the directory that it's in and the new name gets the class prefix like the
+    // name of a new module.
+    let project_configs = godot_project_configs(&config);
+    let new_gdns_file_path =
+        gdns_file_path(&config, name).with_file_name(naming::gdns_file_name(new_name));
+    let new_class_name = naming::godot_class_name(new_name, class_prefix(&config));
+    config.general.modules[module_index] = new_name.to_string();
+    let mut module_config = config.modules.remove(name).unwrap_or_default();
+    if module_config.gdns.is_some() {
+        module_config.gdns = Some(new_gdns_file_path);
+    }
+    module_config.class_name = Some(new_class_name.to_owned()).filter(|c| c != new_name);
+    if module_config != ConfigModule::default() {
+        config.modules.insert(new_name.to_string(), module_config);
+    }
+    let new_config_string = toml::to_string(&config).expect("Unable to convert config to string");
+
+    match write(config_path, new_config_string) {
         Ok(_v) => (),
         Err(e) => {
-            println!("There was a problem creating the config file: {}", e);
+            println!("There was a problem renaming the module: {}", e);
             exit(1);
         }
     }
 
-    println!("{}", "library rebased".green());
+    // Update the generated regions of the src/lib.rs file with the renamed module.
+    update_lib_file(&lib_file_path, &config);
+
+    // Rename the module's files in every Godot project that the library is deployed to.
+    for project_config in &project_configs {
+        rename_module_files(project_config, name, new_name, &new_class_name);
+    }
+
+    println!("{}", "module renamed".green());
 }
 
-/// Updates a library from an older version of godot_rust_helper to the newest version.
+/// Moves a renamed module's .gdns file in a Godot project and updates the scenes, resources, autoload, and
+/// plugin.cfg file that reference it.
 ///
 /// # Arguments
 ///
-/// `output` - As of godot_rust_helper 2.x the 'rust-modules' directory no longer exists and is customizable. You can change this to a different directory at this time but you'll have to fix all references in Godot.
-/// `nativescript_path` - As of godot_rust_helper 3.x the nativescript files can be placed into a custom directory within the Godot project.
-pub fn update(output: PathBuf, nativescript_path: PathBuf) {
-    println!(
-        "{}",
-        "Updating project from an older version of godot_rust_helper...".white()
-    );
+/// `config` - The config of the library for the Godot project.
+/// `name` - The old name of the module.
+/// `new_name` - The new name of the module.
+/// `new_class_name` - The name that Godot uses for the renamed module's class.
+fn rename_module_files(config: &Config, name: &str, new_name: &str, new_class_name: &str) {
+    let gdns_file_path = gdns_file_path(config, name);
+    let new_gdns_file_path = gdns_file_path.with_file_name(naming::gdns_file_name(new_name));
+
+    // Move the .gdns file and update the names within it.
+    let godot_class_name = content::godot_class_name(name, &config.modules);
+    let gdns_file = match read_to_string(&gdns_file_path) {
+        Ok(v) => v,
+        Err(_e) => content::create_gdns_file(
+            &godot_class_name,
+            &gdnlib_res_path(config),
+            config.modules.get(name),
+        ),
+    };
+    let new_gdns_file = gdns_file
+        .replace(
+            &format!("resource_name = \"{}\"", godot_class_name),
+            &format!("resource_name = \"{}\"", new_class_name),
+        )
+        .replace(
+            &format!("class_name = \"{}\"", godot_class_name),
+            &format!("class_name = \"{}\"", new_class_name),
+        );
 
-    // Check to see if we are in a library created by the `new` command.
-    let current_dir_path = current_dir().expect("Unable to get current directory");
-    let config_path = current_dir_path.join("godot-rust-helper.toml");
-    if !config_path.exists() {
-        println!(
-            "The upgrade command can only be used inside of a library created with the new command"
-        );
-        exit(1);
+    match write(&new_gdns_file_path, new_gdns_file) {
+        Ok(_v) => (),
+        Err(e) => {
+            println!("There was a problem creating the gdns file: {}", e);
+            exit(1);
+        }
+    }
+    if new_gdns_file_path != gdns_file_path && gdns_file_path.exists() {
+        match remove_file(&gdns_file_path) {
+            Ok(_v) => (),
+            Err(e) => {
+                println!("There was a problem removing the old gdns file: {}", e);
+                exit(1);
+            }
+        }
     }
 
-    // Read and parse the old config file.
-    let mut config_string =
-        read_to_string(&config_path).expect("Unable to read godot-rust-helper.toml config file");
-
-    // Check to see what version the current project is using by looking for key differences in the config file.
-    // If the config file doesn't have the newer config format with a [paths] section, then it is v1.x. If the config file doesn't have a nativescript_path entry, then it is v2.x.
-    // If the config file has the [paths] section but doesn't have the nativescript entry under it, then the project is v2.x.
-    if !config_string.contains("[paths]") {
-        // Parse the current configuration of the project of the project as configuration v1.
-        let current_config: ConfigV1 = toml::from_str(&config_string).expect(
-            "Unable to parse godot-rust-helper config file of project using godot_rust_helper v1.x",
-        );
+    // Update any scenes or resources that reference the old .gdns file.
+    let gdns_res_path = utils::godot_resource_path(&config.paths.godot, &gdns_file_path);
+    let new_gdns_res_path = utils::godot_resource_path(&config.paths.godot, &new_gdns_file_path);
 
-        // Create an instance of the new configuration of the project using the values from the current config.
-        // Since absolute paths were not a thing until godot_rust_helper v2 we also have to create absolute paths from the old paths.
-        let new_config_paths = ConfigPathsV2 {
-            lib: utils::absolute_path(current_config.general.lib_path)
-                .expect("Unable to create absolute path from godot_rust_helper v1.x lib path"),
-            godot: utils::absolute_path(current_config.general.godot_path).expect(
-                "Unable to create absolute path from from godot_rust_helper v1.x godot path",
-            ),
-            output: PathBuf::new(),
-        };
-        let new_config_general = ConfigGeneral {
-            name: current_config.general.name,
-            targets: current_config.general.targets,
-            modules: current_config.general.modules,
-            plugin: false,
-        };
-        let mut new_config = ConfigV2 {
-            general: new_config_general,
-            paths: new_config_paths,
+    for file_path in utils::find_files_with_extensions(&config.paths.godot, &["tscn", "tres"]) {
+        let file = match read_to_string(&file_path) {
+            Ok(v) => v,
+            Err(_e) => continue,
         };
+        let reference = format!("path=\"{}\"", gdns_res_path);
+        if !file.contains(&reference) {
+            continue;
+        }
 
-        // Check to see if the user would like to get rid of the rust-modules folder and instead use another directory within the Godot project.
-        let output_as_path_buf = PathBuf::from(output);
-        let output_path = if output_as_path_buf == PathBuf::from("") {
-            PathBuf::from(&new_config.paths.godot.join("rust-modules"))
-        } else if !output_as_path_buf.is_absolute() {
-            utils::absolute_path(output_as_path_buf)
-                .expect("Unable to create absolute path from destination path")
-                .as_path()
-                .to_owned()
+        let new_file = file.replace(&reference, &format!("path=\"{}\"", new_gdns_res_path));
+        match write(&file_path, new_file) {
+            Ok(_v) => println!("updated reference in {}", file_path.display()),
+            Err(e) => {
+                println!(
+                    "There was a problem updating the reference in {}: {}",
+                    file_path.display(),
+                    e
+                );
+                exit(1);
+            }
+        }
+    }
+
+    // Point the module's autoload at the new .gdns file. An autoload that is named after the class is renamed too.
+    let mut project_file = read_project_file(config);
+    if let Some(autoload_name) = find_autoload(&project_file, &gdns_res_path) {
+        let new_autoload_name = if autoload_name == godot_class_name {
+            new_class_name.to_owned()
         } else {
-            Path::new(&output_as_path_buf).to_path_buf()
+            autoload_name.to_owned()
         };
-        new_config.paths.output = output_path;
+        let autoload_value = project_file
+            .get("autoload", &autoload_name)
+            .unwrap_or_default()
+            .replace(&gdns_res_path, &new_gdns_res_path);
 
-        // Update the 'rust-modules' folder to the new output folder.
-        Command::new("mv")
-            .arg(&new_config.paths.godot.join("rust-modules"))
-            .arg(&new_config.paths.godot.join(&new_config.paths.output))
-            .output()
-            .expect("Unable to move output to the new directory");
+        if new_autoload_name != autoload_name {
+            project_file.remove("autoload", &autoload_name);
+        }
+        project_file.set("autoload", &new_autoload_name, &autoload_value);
+        write_project_file(config, &project_file);
+        println!("updated the {} autoload", new_autoload_name);
+    }
 
-        // Change the path to the dynamic libraries in the gdnlib file.
-        let targets_str: Vec<&str> = new_config
-            .general
-            .targets
-            .iter()
-            .map(AsRef::as_ref)
-            .collect();
-        let gdnlib = content::create_gdnlib_file(
-            &new_config.general.name,
-            &diff_paths(&new_config.paths.output, &new_config.paths.godot)
-                .expect("Unable to get path diff"),
-            &targets_str,
+    // Point the plugin.cfg file at the new .gdns file when the module is the plugin's base script.
+    if config.general.plugin {
+        let plugin_cfg_path = config.paths.nativescript.join("plugin.cfg");
+        if let Ok(plugin_cfg_string) = read_to_string(&plugin_cfg_path) {
+            let mut plugin_cfg = ConfigFile::parse(&plugin_cfg_string);
+            let script_path = |path: &Path| {
+                let relative_path =
+                    diff_paths(path, &config.paths.nativescript).unwrap_or_else(|| path.to_owned());
+                format!("\"{}\"", relative_path.to_slash_lossy())
+            };
+
+            if plugin_cfg.get("plugin", "script") == Some(script_path(&gdns_file_path)) {
+                plugin_cfg.set("plugin", "script", &script_path(&new_gdns_file_path));
+                match write(&plugin_cfg_path, plugin_cfg.to_string()) {
+                    Ok(_v) => println!("updated the script in {}", plugin_cfg_path.display()),
+                    Err(e) => {
+                        println!("There was a problem updating the plugin.cfg file: {}", e);
+                        exit(1);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Adds structs that derive `NativeClass` but weren't created with `create` to the library. Each struct that isn't
+/// already a module is added to the config, registered in the lib file, and gets a .gdns file if it doesn't already
+/// have one.
+pub fn import_modules() {
+    println!("{}", "importing modules".white());
+
+    let current_dir_path = std::env::current_dir().expect("Unable to get current directory");
+    let config_path = Path::new(&current_dir_path).join("godot-rust-helper.toml");
+    let mut config = read_config("import");
+    require_godot_3(&config, "import");
+
+    // Make sure that the lib file can be updated before anything is changed.
+    let lib_file_path = current_dir_path.join("src").join("lib.rs");
+    check_lib_file(&lib_file_path, &config);
+
+    // Find all of the NativeClass structs that aren't modules yet and add them to the config.
+    let mut imported: Vec<String> = vec![];
+    for class in rust_source::find_native_classes(&current_dir_path.join("src")) {
+        if config.general.modules.contains(&class.name) {
+            continue;
+        }
+        if imported.contains(&class.name) {
+            println!(
+                "Skipping {} in {} since a class with the same name was already imported",
+                class.name,
+                class.file.display()
+            );
+            continue;
+        }
+
+        // Classes that aren't in a file named after them need to have their module path saved so that they can be
+        // registered correctly in the lib file. The base class is saved so that `list` and `attach` know it.
+        let mut module_config = ConfigModule {
+            base: class.base.to_owned(),
+            ..Default::default()
+        };
+        if class.mod_path != naming::mod_name(&class.name) {
+            module_config.path = Some(class.mod_path.to_owned());
+        }
+        if module_config != ConfigModule::default() {
+            config.modules.insert(class.name.to_owned(), module_config);
+        }
+
+        println!(
+            "imported {} ({}) from {}",
+            class.name,
+            class.base.as_deref().unwrap_or("Node"),
+            diff_paths(&class.file, &current_dir_path)
+                .unwrap_or_else(|| class.file.to_owned())
+                .display()
         );
+        config.general.modules.push(class.name.to_owned());
+        imported.push(class.name);
+    }
 
-        // Finally create the new gndlib file and write to it.
-        let gdnlib_file_name = format!("{}.gdnlib", &new_config.general.name);
+    if imported.is_empty() {
+        println!("{}", "no new NativeClass structs were found".green());
+        return;
+    }
 
-        let gdnlib_path = &new_config.paths.output;
-        std::fs::File::create(&gdnlib_path.join(&gdnlib_file_name))
-            .expect("Unable to create gdnlib file");
+    let new_config_string = toml::to_string(&config).expect("Unable to convert config to string");
+    match write(config_path, new_config_string) {
+        Ok(_v) => (),
+        Err(e) => {
+            println!("There was a problem importing the modules: {}", e);
+            exit(1);
+        }
+    }
 
-        match write(&new_config.paths.output.join(gdnlib_file_name), gdnlib) {
-            Ok(_v) => (),
-            Err(e) => {
-                println!("There was a problem creating the gdnlib file: {}", e);
-                exit(1);
-            }
+    // Register the imported modules in the generated regions of the src/lib.rs file.
+    update_lib_file(&lib_file_path, &config);
+
+    // Create the .gdns files for the imported modules that don't have one yet.
+    for name in &imported {
+        let gdns_file_path = gdns_file_path(&config, name);
+        if gdns_file_path.exists() {
+            continue;
         }
 
-        // We're done with the config for now so we can write it over the original.
-        let new_config_string =
-            toml::to_string(&new_config).expect("Unable to convert v2.x config to string");
-        match write(&config_path, new_config_string) {
+        let gdns_file_contents = content::create_gdns_file(
+            &content::godot_class_name(name, &config.modules),
+            &gdnlib_res_path(&config),
+            config.modules.get(name),
+        );
+
+        match write(gdns_file_path, gdns_file_contents) {
             Ok(_v) => (),
             Err(e) => {
-                println!(
-                    "{}: {}",
-                    "There was a problem writing to the v2.x config file".red(),
-                    e
-                );
+                println!("There was a problem creating the gdns file: {}", e);
                 exit(1);
             }
         }
+    }
 
-        // At some point godot_rust_helper_extensions was changed to godot_rust_helper_ext so we gotta change any reference to it in the Cargo.toml file.
-        let mut cargo_string = read_to_string("Cargo.toml").expect("Unable to read Cargo.toml");
-        if cargo_string.contains("godot_rust_helper_extensions") {
-            cargo_string =
-                cargo_string.replace("godot_rust_helper_extensions", "godot_rust_helper_ext");
+    println!("{}", "modules imported".green());
+}
 
-            match write("Cargo.toml", cargo_string) {
-                Ok(_v) => (),
-                Err(e) => {
-                    println!(
-                        "{}: {}",
-                        "There was a problem writing to the v2.x Cargo.toml file".red(),
-                        e
-                    );
-                    exit(1);
-                }
+/// Brings the library in line with its config. The generated regions of the lib file are regenerated, missing .gdns
+/// files are created, and .gdns files that point to a stale gdnlib path are rewritten. Files that aren't used by any
+/// module anymore are reported and removed if `prune` is set.
+///
+/// # Arguments
+///
+/// `prune` - Indicates whether the orphaned files should be removed.
+pub fn sync_library(prune: bool) {
+    println!("{}", "syncing library".white());
+
+    let current_dir_path = std::env::current_dir().expect("Unable to get current directory");
+    let config = read_config("sync");
+    require_godot_3(&config, "sync");
+
+    // Regenerate the generated regions of the src/lib.rs file from the modules in the config.
+    let lib_file_path = current_dir_path.join("src").join("lib.rs");
+    update_lib_file(&lib_file_path, &config);
+
+    // Create the .gdns files that are missing and point the existing ones at the current gdnlib file in every Godot
+    // project that the library is deployed to, and look for .gdns files that use this library but don't belong to a
+    // module.
+    let mut orphans: Vec<PathBuf> = vec![];
+    for project_config in godot_project_configs(&config) {
+        orphans.extend(sync_gdns_files(&project_config));
+    }
+
+    // Look for .rs files that aren't declared in the lib file.
+    let lib_file = read_to_string(&lib_file_path).expect("Unable to read the lib file");
+    let declared_mods = match rust_source::declared_mods(&lib_file) {
+        Ok(v) => v,
+        Err(e) => {
+            println!("Unable to parse {}: {}", lib_file_path.display(), e);
+            exit(1);
+        }
+    };
+    let src_dir_path = current_dir_path.join("src");
+    for file_path in utils::find_files_with_extensions(&src_dir_path, &["rs"]) {
+        if file_path.parent() != Some(src_dir_path.as_path()) || file_path == lib_file_path {
+            continue;
+        }
+
+        let mod_name = rust_source::file_mod_path(&src_dir_path, &file_path);
+        if !declared_mods.contains(&mod_name) {
+            orphans.push(file_path);
+        }
+    }
+
+    for orphan in &orphans {
+        if !prune {
+            println!("orphaned file {}", orphan.display());
+            continue;
+        }
+
+        match remove_file(orphan) {
+            Ok(_v) => println!("removed orphaned file {}", orphan.display()),
+            Err(e) => {
+                println!("There was a problem removing {}: {}", orphan.display(), e);
+                exit(1);
             }
         }
+    }
+    if !orphans.is_empty() && !prune {
+        println!("Use --prune to remove the orphaned files");
+    }
 
-        // Now we gotta go through each of the components created and check to see if they are still using `use godot_rust_helper_extensions` and change it to ext just like above.
-        for module in new_config.general.modules {
-            let module_filename = format!("{}.rs", module.to_case(Case::Snake));
-            let mut module_path = PathBuf::from("src");
-            module_path.push(module_filename);
+    println!("{}", "library synced".green());
+}
 
-            let mut module_string = read_to_string(&module_path).expect("Unable to read module");
-            // If the module has the old extensions, then update it to the new ones.
-            if module_string.contains("godot_rust_helper_extensions") {
-                module_string =
-                    module_string.replace("godot_rust_helper_extensions", "godot_rust_helper_ext");
+/// Creates the .gdns files of the modules that are missing from a Godot project and points the existing ones at the
+/// current gdnlib file. Returns the .gdns files that use the library but don't belong to any of its modules.
+///
+/// # Arguments
+///
+/// `config` - The config of the library for the Godot project.
+fn sync_gdns_files(config: &Config) -> Vec<PathBuf> {
+    let gdnlib_res_path = gdnlib_res_path(config);
+    let gdnlib_re =
+        regex::Regex::new(r#"path="[^"]*\.gdnlib""#).expect("Unable to create regex pattern");
+
+    let mut gdns_file_paths: Vec<PathBuf> = vec![];
+    for name in &config.general.modules {
+        let gdns_file_path = gdns_file_path(config, name);
+        gdns_file_paths.push(gdns_file_path.to_owned());
+
+        let gdns_file = match read_to_string(&gdns_file_path) {
+            Ok(v) => v,
+            Err(_e) => {
+                let gdns_file_contents = content::create_gdns_file(
+                    &content::godot_class_name(name, &config.modules),
+                    &gdnlib_res_path,
+                    config.modules.get(name),
+                );
 
-                match write(&module_path, module_string) {
-                    Ok(_v) => (),
+                match write(&gdns_file_path, gdns_file_contents) {
+                    Ok(_v) => println!("created {}", gdns_file_path.display()),
                     Err(e) => {
-                        println!(
-                            "{}: {}",
-                            "there was a problem writing to the module file".red(),
-                            e
-                        );
+                        println!("There was a problem creating the gdns file: {}", e);
                         exit(1);
                     }
                 }
+                continue;
+            }
+        };
+
+        let expected_path = format!("path=\"{}\"", gdnlib_res_path);
+        let new_gdns_file = gdnlib_re
+            .replace(&gdns_file, regex::NoExpand(&expected_path))
+            .to_string();
+        if new_gdns_file == gdns_file {
+            continue;
+        }
+
+        match write(&gdns_file_path, new_gdns_file) {
+            Ok(_v) => println!("updated the library path in {}", gdns_file_path.display()),
+            Err(e) => {
+                println!("There was a problem updating the gdns file: {}", e);
+                exit(1);
             }
         }
     }
-    if !config_string.contains("nativescript") {
-        config_string = read_to_string(&config_path)
-            .expect("Unable to read godot-rust-helper.toml config file");
-        // Parse the current configuration of the project as configuration v2.
-        let current_config: ConfigV2 = toml::from_str(&config_string).expect(
-            "Unable to parse godot-rust-helper config file of project using godot_rust_helper v2.x",
-        );
 
-        // Create an instance of the new configuration of the project using the values from the current config.
-        let new_config_paths = ConfigPaths {
-            lib: current_config.paths.lib,
-            godot: current_config.paths.godot.to_owned(),
-            output: utils::absolute_path(current_config.paths.output).expect(
-                "Unable to create absolute path from from godot_rust_helper v2.x output path",
-            ),
-            nativescript: current_config.paths.godot.to_owned(),
-        };
-        let new_config_general = ConfigGeneralV3 {
-            name: current_config.general.name,
-            targets: current_config.general.targets,
-            modules: current_config.general.modules,
-        };
-        let mut new_config = ConfigV3 {
-            general: new_config_general,
-            paths: new_config_paths,
-        };
+    // Look for .gdns files that use this library but don't belong to a module.
+    let mut orphans: Vec<PathBuf> = vec![];
+
+    let gdnlib_file_name = library_file_path(config)
+        .file_name()
+        .expect("Unable to get the gdnlib file name")
+        .to_string_lossy()
+        .to_string();
+    let gdnlib_reference = format!("/{}\"", gdnlib_file_name);
+    for file_path in
+        utils::find_files_containing(&config.paths.nativescript, &["gdns"], &gdnlib_reference)
+    {
+        if !gdns_file_paths.contains(&file_path) {
+            orphans.push(file_path);
+        }
+    }
 
-        // Check to see if the user would like to place the nativescript files in a specific directory.
-        // If no path is provided for the nativescript directory then the files will be placed in the root of the project.
-        let ns_path_as_path_buf = PathBuf::from(nativescript_path);
-        let ns_path = if ns_path_as_path_buf == PathBuf::from("") {
-            PathBuf::from(&new_config.paths.godot)
-        } else if !ns_path_as_path_buf.is_absolute() {
-            utils::absolute_path(ns_path_as_path_buf)
-                .expect("Unable to create absolute path from nativescript path")
-                .as_path()
-                .to_owned()
-        } else {
-            Path::new(&ns_path_as_path_buf).to_path_buf()
+    return orphans;
+}
+
+/// A module as it's shown by the `list` command.
+#[derive(Debug, Serialize)]
+struct ModuleListing {
+    /// The class name of the module.
+    name: String,
+    /// The Godot class that the module inherits from, if it is saved in the config or its struct could be found.
+    base: Option<String>,
+    /// Indicates whether the class is registered as a tool class or a runtime class.
+    registration: String,
+    /// The path to the module's Rust source file relative to the library.
+    source: String,
+    /// Indicates whether the Rust source file exists.
+    source_exists: bool,
+    /// The `res://` path to the module's .gdns file.
+    gdns: String,
+    /// Indicates whether the .gdns file exists.
+    gdns_exists: bool,
+}
+
+/// The library as it's shown by the `list` command.
+#[derive(Debug, Serialize)]
+struct LibraryListing {
+    /// The name of the library.
+    name: String,
+    /// The targets that the library is built for.
+    targets: Vec<String>,
+    /// The `res://` path to the dynamic library that `build` deploys to the Godot project.
+    artifact: String,
+    /// Indicates whether the dynamic library has been deployed to the Godot project.
+    artifact_deployed: bool,
+    /// Indicates whether the source or the build is newer than the deployed dynamic library.
+    artifact_out_of_date: bool,
+    /// The modules in the library.
+    modules: Vec<ModuleListing>,
+}
+
+/// Prints every module in the library along with its base class, its source and .gdns files, and how it's
+/// registered, followed by the library's targets and whether the deployed dynamic library is out of date.
+///
+/// # Arguments
+///
+/// `json` - Indicates whether the list should be printed as JSON instead of text.
+pub fn list_modules(json: bool) {
+    let current_dir_path = std::env::current_dir().expect("Unable to get current directory");
+    let config = read_config("list");
+
+    let src_dir_path = current_dir_path.join("src");
+    let classes = rust_source::find_native_classes(&src_dir_path);
+
+    let mut modules: Vec<ModuleListing> = vec![];
+    for name in &config.general.modules {
+        let class = classes.iter().find(|c| &c.name == name);
+
+        // Use the file that the struct was found in, otherwise fall back to where the module should be.
+        let source_path = match class {
+            Some(v) => v.file.to_owned(),
+            None => match config.modules.get(name).and_then(|m| m.path.to_owned()) {
+                Some(v) => src_dir_path.join(format!("{}.rs", v.replace("::", "/"))),
+                None => src_dir_path.join(naming::mod_file_name(name)),
+            },
+        };
+        let gdns_file_path = gdns_file_path(&config, name);
+
+        modules.push(ModuleListing {
+            name: name.to_owned(),
+            base: config
+                .modules
+                .get(name)
+                .and_then(|m| m.base.to_owned())
+                .or_else(|| class.and_then(|c| c.base.to_owned())),
+            registration: if content::is_tool_class(name, &config.modules, config.general.plugin) {
+                "tool".to_string()
+            } else {
+                "runtime".to_string()
+            },
+            source: diff_paths(&source_path, &current_dir_path)
+                .unwrap_or_else(|| source_path.to_owned())
+                .to_string_lossy()
+                .replace("\\", "/"),
+            source_exists: source_path.exists(),
+            gdns: utils::godot_resource_path(&config.paths.godot, &gdns_file_path),
+            gdns_exists: gdns_file_path.exists(),
+        });
+    }
+
+    // The deployed dynamic library is out of date if the build or any of the source files are newer than it.
+    let artifact_file_name = utils::dynamic_library_file_name(&config.general.name);
+    let built_artifact_path = target_dir(&current_dir_path)
+        .join("debug")
+        .join(&artifact_file_name);
+    let deployed_artifact_path = config.paths.output.join(&artifact_file_name);
+    let deployed_time = utils::modified_time(&deployed_artifact_path);
+
+    let mut newest_source_paths = utils::find_files_with_extensions(&src_dir_path, &["rs"]);
+    newest_source_paths.push(built_artifact_path);
+    newest_source_paths.push(current_dir_path.join("Cargo.toml"));
+    let newest_time = newest_source_paths
+        .iter()
+        .filter_map(|p| utils::modified_time(p))
+        .max();
+
+    let library = LibraryListing {
+        name: config.general.name.to_owned(),
+        targets: config.general.targets.to_owned(),
+        artifact: utils::godot_resource_path(&config.paths.godot, &deployed_artifact_path),
+        artifact_deployed: deployed_time.is_some(),
+        artifact_out_of_date: match (deployed_time, newest_time) {
+            (Some(deployed), Some(newest)) => newest > deployed,
+            (None, _) => true,
+            _ => false,
+        },
+        modules,
+    };
+
+    if json {
+        let library_json =
+            serde_json::to_string_pretty(&library).expect("Unable to convert list to JSON");
+        println!("{}", library_json);
+        return;
+    }
+
+    let exists_label = |exists: bool| {
+        if exists {
+            "".normal()
+        } else {
+            " (missing)".red()
+        }
+    };
+
+    println!(
+        "{} (targets: {})",
+        library.name.white().bold(),
+        library.targets.join(", ")
+    );
+    let artifact_status = if !library.artifact_deployed {
+        "not deployed".red()
+    } else if library.artifact_out_of_date {
+        "out of date".yellow()
+    } else {
+        "up to date".green()
+    };
+    println!("  artifact: {} ({})", library.artifact, artifact_status);
+
+    if library.modules.is_empty() {
+        println!();
+        println!("no modules have been created yet");
+        return;
+    }
+
+    for module in &library.modules {
+        println!();
+        println!(
+            "{} ({}, {})",
+            module.name.white().bold(),
+            module.base.as_deref().unwrap_or("unknown base"),
+            module.registration
+        );
+        println!(
+            "  source: {}{}",
+            module.source,
+            exists_label(module.source_exists)
+        );
+        println!(
+            "  gdns: {}{}",
+            module.gdns,
+            exists_label(module.gdns_exists)
+        );
+    }
+}
+
+/// Adds a module to the autoloads in the Godot project's project.godot file so that it's loaded as a singleton, or
+/// removes it from them.
+///
+/// # Arguments
+///
+/// `name` - The class name of the module.
+/// `remove` - Indicates whether the module should be removed from the autoloads instead of being added.
+pub fn autoload_module(name: &str, remove: bool) {
+    let config = read_config("autoload");
+    require_godot_3(&config, "autoload");
+
+    if !config.general.modules.iter().any(|i| i == name) {
+        println!("A module with the name {} does not exist", name);
+        exit(1);
+    }
+
+    let mut project_file = read_project_file(&config);
+
+    if remove {
+        let gdns_file_path = gdns_file_path(&config, name);
+        let gdns_res_path = utils::godot_resource_path(&config.paths.godot, &gdns_file_path);
+
+        match find_autoload(&project_file, &gdns_res_path) {
+            Some(autoload_name) => {
+                project_file.remove("autoload", &autoload_name);
+                write_project_file(&config, &project_file);
+                println!(
+                    "{}",
+                    format!("removed the {} autoload", autoload_name).green()
+                );
+            }
+            None => println!("{}", format!("{} is not an autoload", name).green()),
+        }
+    } else {
+        add_autoload(&config, &mut project_file, name);
+        write_project_file(&config, &project_file);
+        println!("{}", format!("added the {} autoload", name).green());
+    }
+}
+
+/// Attaches a module's script to a node in an existing scene. The module's .gdns file is added to the scene's
+/// external resources if it isn't there already and is set as the node's script. The node's type is checked against
+/// the class that the module inherits from.
+///
+/// # Arguments
+///
+/// `name` - The class name of the module.
+/// `scene` - The path to the scene, either relative to the Godot project, a `res://` path, or relative to the current directory.
+/// `node_path` - The path to the node relative to the scene's root node, `.` being the root node itself.
+/// `force` - Indicates whether the script should be attached even if the node's type doesn't match the module.
+pub fn attach_module(name: &str, scene: PathBuf, node_path: &str, force: bool) {
+    println!("{}", "attaching module".white());
+
+    let current_dir_path = std::env::current_dir().expect("Unable to get current directory");
+    let config = read_config("attach");
+    require_godot_3(&config, "attach");
+
+    if !config.general.modules.iter().any(|i| i == name) {
+        println!("A module with the name {} does not exist", name);
+        exit(1);
+    }
+
+    let gdns_file_path = gdns_file_path(&config, name);
+    if !gdns_file_path.exists() {
+        println!(
+            "The gdns file of {} doesn't exist, use the sync command to create it",
+            name
+        );
+        exit(1);
+    }
+
+    let scene_file_path = resolve_godot_file(&config, &scene);
+
+    let scene_file_string = match read_to_string(&scene_file_path) {
+        Ok(v) => v,
+        Err(e) => {
+            println!("Unable to read {}: {}", scene_file_path.display(), e);
+            exit(1);
+        }
+    };
+    let mut scene_file = match SceneFile::parse(&scene_file_string) {
+        Ok(v) => v,
+        Err(e) => {
+            println!("{} can't be used because {}", scene_file_path.display(), e);
+            exit(1);
+        }
+    };
+
+    let node = match scene_file.find_node(node_path) {
+        Some(v) => v,
+        None => {
+            println!(
+                "The scene doesn't have a node at {}",
+                if node_path.is_empty() { "." } else { node_path }
+            );
+            exit(1);
+        }
+    };
+
+    // Make sure that the node is the class that the module inherits from or one of its descendants. The base class of
+    // an imported module is saved in the config, otherwise it's taken from the module's struct.
+    let base = match config.modules.get(name).and_then(|m| m.base.to_owned()) {
+        Some(v) => Some(v),
+        None => rust_source::find_native_classes(&current_dir_path.join("src"))
+            .into_iter()
+            .find(|c| c.name == name)
+            .map(|c| c.base.unwrap_or_else(|| "Reference".to_string())),
+    };
+    match (&base, &node.node_type) {
+        (None, _) => println!(
+            "Unable to find the {} struct so the node's type can't be checked",
+            name
+        ),
+        (Some(_), None) => println!(
+            "The {} node is an instance of another scene so its type can't be checked",
+            node.name
+        ),
+        (Some(base), Some(node_type)) => match godot_classes::inherits(node_type, base) {
+            Some(true) => (),
+            Some(false) => {
+                println!(
+                    "{} inherits from {} but the {} node is a {}",
+                    name, base, node.name, node_type
+                );
+                if !force {
+                    println!("Use a node of the same type or use --force to attach it anyway");
+                    exit(1);
+                }
+            }
+            None => println!(
+                "{} isn't a built-in Godot class so it can't be checked against {}",
+                node_type, base
+            ),
+        },
+    }
+
+    let gdns_res_path = utils::godot_resource_path(&config.paths.godot, &gdns_file_path);
+    let resource_id = scene_file.ext_resource(&gdns_res_path, "Script");
+    let script = format!("ExtResource( {} )", resource_id);
+
+    // The node has to be found again since adding the resource could have moved it.
+    let node = scene_file
+        .find_node(node_path)
+        .expect("Unable to find the node after adding the script resource");
+    if let Some(previous_script) = scene_file.node_property(&node, "script") {
+        if previous_script != script {
+            println!("replaced the {} node's script", node.name);
+        }
+    }
+    scene_file.set_node_property(&node, "script", &script);
+
+    match write(&scene_file_path, scene_file.to_string()) {
+        Ok(_v) => (),
+        Err(e) => {
+            println!("There was a problem updating the scene: {}", e);
+            exit(1);
+        }
+    }
+
+    println!("{}", "module attached".green());
+}
+
+/// Runs the `cargo build` command and copies the target files into the Godot project directory. In a workspace every
+/// library is built and copied to its own output path.
+///
+/// # Arguments
+///
+/// `godot` - The version of Godot that the library is expected to be for, as it's passed to `--godot`.
+pub fn build_library(godot: Option<GodotVersion>) {
+    let version_notice = format!(
+        "{}{}",
+        "godot_rust_helper v".white().underline(),
+        VERSION.white().underline()
+    );
+    println!("{}", version_notice);
+    println!("{}", "building...".cyan());
+
+    let root_dir = utils::find_file("godot-rust-helper.toml".to_string());
+
+    // Get the config so that we can check the targets later. A workspace builds all of its libraries at once.
+    let libraries = match read_workspace_config(&root_dir) {
+        Some(workspace_config) => read_member_configs(&root_dir, &workspace_config),
+        None => {
+            let config_path = root_dir.join("godot-rust-helper.toml");
+            let config_string = read_to_string(&config_path)
+                .expect("Unable to read godot-rust-helper.toml config file");
+            vec![(
+                root_dir.to_owned(),
+                parse_config(&config_string, &config_path),
+            )]
+        }
+    };
+    for (_lib_dir, config) in &libraries {
+        check_godot_version(config, godot);
+    }
+
+    let status = Command::new("cargo")
+        .arg("build")
+        .current_dir(&root_dir)
+        .status()
+        .expect("Unable to run cargo build");
+
+    if !status.success() {
+        println!("{}", "build failed".red());
+        exit(1);
+    }
+
+    // Copy each library's build file from where the build files are stored to the output path of every Godot
+    // project that it's deployed to.
+    for (lib_dir, config) in &libraries {
+        let targets_dir = target_dir(lib_dir).join("debug");
+
+        let file_path = targets_dir.join(utils::dynamic_library_file_name(&config.general.name));
+
+        for project_config in godot_project_configs(config) {
+            Command::new("cp")
+                .arg(&file_path)
+                .arg(
+                    project_config
+                        .paths
+                        .godot
+                        .join(&project_config.paths.output),
+                )
+                .output()
+                .expect("Unable to copy build files");
+        }
+    }
+
+    println!("{}", "build complete".green());
+
+    // Delete this next iteration if not needed anymore.
+    // for target in &config.general.targets {
+    //     // Since the user can have multiple targets, we need to check what target
+    //     // they can actually build for and set that as the target to use.
+    //     match target.as_ref() {
+    //         "linux" => {
+    //         },
+    //         _ => exit(1)
+    //     }
+
+    //     let ext = if target == "linux" {
+    //         "so"
+    //     } else if target == "osx" {
+    //         "dylib"
+    //     } else if target == "windows" {
+    //         "dll"
+    //     } else {
+    //         exit(1);
+    //     };
+    //     let extra = if cfg!(windows) { "" } else { "lib" };
+
+    //     let file = format!("{}{}.{}", extra, config.general.name, ext);
+    //     let file_path = targets_dir.join(file);
+
+    //     Command::new("cp")
+    //         .arg(file_path)
+    //         .arg(&config.paths.godot.join(&config.paths.output))
+    //         .output()
+    //         .expect("Unable to copy build files");
+
+    //     println!("{}", "build complete".green());
+    // }
+}
+
+/// Watches for changes in the src directory of the library, or of every library in a workspace, and then automatically
+/// runs the build command.
+/// This is used when build --watch is called.
+///
+/// # Arguments
+///
+/// `godot` - The version of Godot that the library is expected to be for, as it's passed to `--godot`.
+pub fn watch_library(godot: Option<GodotVersion>) {
+    let root_dir = utils::find_file("godot-rust-helper.toml".to_string());
+    let lib_dirs = match read_workspace_config(&root_dir) {
+        Some(workspace_config) => read_member_configs(&root_dir, &workspace_config)
+            .into_iter()
+            .map(|(lib_dir, _config)| lib_dir)
+            .collect(),
+        None => vec![root_dir],
+    };
+    let (tx, rx) = channel();
+
+    build_with_time_log(godot);
+
+    let mut last_checked = chrono::offset::Local::now();
+
+    // The src directory of every library in a workspace is watched and a change to any of them rebuilds all of them.
+    let mut watcher = raw_watcher(tx).expect("Unable to create watcher");
+    for lib_dir in &lib_dirs {
+        watcher
+            .watch(lib_dir.join("src"), RecursiveMode::Recursive)
+            .expect("Unable to watch src directory");
+    }
+
+    loop {
+        match rx.recv() {
+            Ok(RawEvent {
+                path: Some(_path),
+                op: Ok(op),
+                cookie: _,
+            }) => {
+                if op.contains(op::WRITE) {
+                    let now = chrono::offset::Local::now();
+                    if (now - last_checked).num_seconds() == 0 {
+                        build_with_time_log(godot);
+                    }
+                    last_checked = chrono::offset::Local::now();
+                }
+            }
+            Ok(event) => println!("broken event: {:?}", event),
+            Err(e) => println!("watch error: {:?}", e),
+        }
+    }
+}
+
+/// Changes the path of the project and the godot project directory in the config if you cloned/downlaoded the project from elsewhere.
+///
+/// # Arguments
+///
+/// `godot_project_dir` - The directory that contains the Godot project that the modules are for.
+/// `targets` - The new build targets that should be set.
+pub fn rebase(godot_project_dir: PathBuf, targets: String) {
+    println!("{}", "rebasing library".white());
+
+    let mut config = read_config("rebase");
+
+    // Get the new paths to use for the config.
+    let lib_path = current_dir().expect("Unable to get current directory");
+    let godot_path = utils::absolute_path(godot_project_dir)
+        .expect("Unable to create absolute path from destination path")
+        .as_path()
+        .to_owned();
+
+    // Update the paths in the config.
+    config.paths.lib = lib_path;
+    config.paths.godot = godot_path;
+
+    // If new targets were set then we update it in the config and the gdnlib file.
+    if !targets.is_empty() {
+        let valid_targets = &["windows", "linux", "osx"];
+        let targets_split: Vec<String> = targets.split(",").map(|s| s.to_string()).collect();
+
+        for t in &targets_split {
+            if !valid_targets.iter().any(|&i| i == t) {
+                println!("An invalid target was specified: {}", t);
+                exit(1);
+            }
+        }
+
+        config.general.targets = targets_split;
+    }
+
+    // Use the config targets so that an empty targets argument keeps the existing targets.
+    let targets_str: Vec<&str> = config.general.targets.iter().map(AsRef::as_ref).collect();
+
+    let (_library_file_name, library_file) = create_library_file(
+        &config,
+        &diff_paths(&config.paths.output, &config.paths.godot)
+            .expect("Unable to get output path diff"),
+        &targets_str,
+    );
+
+    match write(library_file_path(&config), library_file.replace("\\", "")) {
+        Ok(_v) => (),
+        Err(e) => {
+            println!("There was a problem creating the library file: {}", e);
+            exit(1);
+        }
+    }
+
+    // Finally we write back the config file.
+    let config_string = toml::to_string(&config).expect("Unable to convert config to string");
+    match write("godot-rust-helper.toml", config_string) {
+        Ok(_v) => (),
+        Err(e) => {
+            println!("There was a problem creating the config file: {}", e);
+            exit(1);
+        }
+    }
+
+    println!("{}", "library rebased".green());
+}
+
+/// Adds another Godot project that the library is deployed to and creates the library's gdnlib or gdextension file
+/// and the .gdns files of its modules in it. The project can be removed again with `remove`, which leaves the files
+/// in it alone.
+///
+/// # Arguments
+///
+/// `godot_project_dir` - The directory that contains the project.godot file of the other Godot project.
+/// `output` - The path in the Godot project, relative to it, where the library file and the build files go.
+/// `nativescript_path` - The path in the Godot project, relative to it, where the .gdns files go.
+/// `remove` - Indicates whether the project should be removed from the config instead of being added.
+pub fn godot_project(
+    godot_project_dir: PathBuf,
+    output: PathBuf,
+    nativescript_path: PathBuf,
+    remove: bool,
+) {
+    let mut config = read_config("project");
+
+    if !godot_project_dir.join("project.godot").exists() {
+        println!("The godot project dir provided is not valid.");
+        exit(1);
+    }
+    let godot_path = utils::absolute_path(godot_project_dir)
+        .expect("Unable to create absolute path from godot project path");
+
+    if remove {
+        let projects_count = config.paths.projects.len();
+        config.paths.projects.retain(|p| p.godot != godot_path);
+        if config.paths.projects.len() == projects_count {
+            println!(
+                "{} isn't one of the other Godot projects of the library",
+                godot_path.display()
+            );
+            exit(1);
+        }
+    } else {
+        if godot_path == config.paths.godot
+            || config.paths.projects.iter().any(|p| p.godot == godot_path)
+        {
+            println!(
+                "The library is already deployed to {}",
+                godot_path.display()
+            );
+            exit(1);
+        }
+
+        // Joining an empty path would leave a trailing separator so the root of the project is used as it is.
+        let project_path = |path: &Path| {
+            if path.as_os_str().is_empty() {
+                godot_path.to_owned()
+            } else {
+                godot_path.join(path)
+            }
+        };
+        config.paths.projects.push(ConfigProject {
+            godot: godot_path.to_owned(),
+            output: project_path(&output),
+            nativescript: project_path(&nativescript_path),
+        });
+    }
+
+    let config_string = toml::to_string(&config).expect("Unable to convert config to string");
+    match write("godot-rust-helper.toml", config_string) {
+        Ok(_v) => (),
+        Err(e) => {
+            println!("There was a problem updating the config file: {}", e);
+            exit(1);
+        }
+    }
+
+    if remove {
+        println!(
+            "{}",
+            format!("removed {} from the Godot projects", godot_path.display()).green()
+        );
+        return;
+    }
+
+    // The project that was just added is the last one.
+    let project_config = godot_project_configs(&config)
+        .pop()
+        .expect("Unable to get the config of the Godot project");
+    create_dir_all(&project_config.paths.output).expect("Unable to create output directory");
+    create_dir_all(&project_config.paths.nativescript)
+        .expect("Unable to create nativescript directory");
+
+    let targets_str: Vec<&str> = config.general.targets.iter().map(AsRef::as_ref).collect();
+    let (_library_file_name, library_file) = create_library_file(
+        &project_config,
+        &diff_paths(&project_config.paths.output, &godot_path)
+            .expect("Unable to get output path diff"),
+        &targets_str,
+    );
+    match write(library_file_path(&project_config), library_file) {
+        Ok(_v) => (),
+        Err(e) => {
+            println!("There was a problem creating the library file: {}", e);
+            exit(1);
+        }
+    }
+
+    if config.general.godot_version == GodotVersion::V3 {
+        for name in &config.general.modules {
+            let gdns_file_contents = content::create_gdns_file(
+                &content::godot_class_name(name, &config.modules),
+                &gdnlib_res_path(&project_config),
+                project_config.modules.get(name),
+            );
+            match write(gdns_file_path(&project_config, name), gdns_file_contents) {
+                Ok(_v) => (),
+                Err(e) => {
+                    println!("There was a problem creating the gdns file: {}", e);
+                    exit(1);
+                }
+            }
+        }
+    }
+
+    println!(
+        "{}",
+        format!("added {} to the Godot projects", godot_path.display()).green()
+    );
+}
+
+/// Updates a library from an older version of godot_rust_helper to the newest version.
+///
+/// # Arguments
+///
+/// `output` - As of godot_rust_helper 2.x the 'rust-modules' directory no longer exists and is customizable. You can change this to a different directory at this time but you'll have to fix all references in Godot.
+/// `nativescript_path` - As of godot_rust_helper 3.x the nativescript files can be placed into a custom directory within the Godot project.
+/// `gdnative` - The version of gdnative to convert the library to, if any.
+pub fn update(output: PathBuf, nativescript_path: PathBuf, gdnative: Option<GdnativeVersion>) {
+    println!(
+        "{}",
+        "Updating project from an older version of godot_rust_helper...".white()
+    );
+
+    // Check to see if we are in a library created by the `new` command.
+    let current_dir_path = current_dir().expect("Unable to get current directory");
+    let config_path = current_dir_path.join("godot-rust-helper.toml");
+    if !config_path.exists() {
+        println!(
+            "The upgrade command can only be used inside of a library created with the new command"
+        );
+        exit(1);
+    }
+
+    // Read and parse the old config file.
+    let mut config_string =
+        read_to_string(&config_path).expect("Unable to read godot-rust-helper.toml config file");
+
+    // Check to see what version the current project is using by looking for key differences in the config file.
+    // If the config file doesn't have the newer config format with a [paths] section, then it is v1.x. If the config file doesn't have a nativescript_path entry, then it is v2.x.
+    // If the config file has the [paths] section but doesn't have the nativescript entry under it, then the project is v2.x.
+    if !config_string.contains("[paths]") {
+        // Parse the current configuration of the project of the project as configuration v1.
+        let current_config: ConfigV1 = toml::from_str(&config_string).expect(
+            "Unable to parse godot-rust-helper config file of project using godot_rust_helper v1.x",
+        );
+
+        // Create an instance of the new configuration of the project using the values from the current config.
+        // Since absolute paths were not a thing until godot_rust_helper v2 we also have to create absolute paths from the old paths.
+        let new_config_paths = ConfigPathsV2 {
+            lib: utils::absolute_path(current_config.general.lib_path)
+                .expect("Unable to create absolute path from godot_rust_helper v1.x lib path"),
+            godot: utils::absolute_path(current_config.general.godot_path).expect(
+                "Unable to create absolute path from from godot_rust_helper v1.x godot path",
+            ),
+            output: PathBuf::new(),
+        };
+        let new_config_general = ConfigGeneral {
+            name: current_config.general.name,
+            targets: current_config.general.targets,
+            modules: current_config.general.modules,
+            plugin: false,
+            class_prefix: None,
+            gdnative_version: GdnativeVersion::default(),
+            godot_version: GodotVersion::default(),
+        };
+        let mut new_config = ConfigV2 {
+            general: new_config_general,
+            paths: new_config_paths,
+        };
+
+        // Check to see if the user would like to get rid of the rust-modules folder and instead use another directory within the Godot project.
+        let output_as_path_buf = output;
+        let output_path = if output_as_path_buf.as_os_str().is_empty() {
+            PathBuf::from(&new_config.paths.godot.join("rust-modules"))
+        } else if !output_as_path_buf.is_absolute() {
+            utils::absolute_path(output_as_path_buf)
+                .expect("Unable to create absolute path from destination path")
+                .as_path()
+                .to_owned()
+        } else {
+            Path::new(&output_as_path_buf).to_path_buf()
+        };
+        new_config.paths.output = output_path;
+
+        // Update the 'rust-modules' folder to the new output folder.
+        Command::new("mv")
+            .arg(new_config.paths.godot.join("rust-modules"))
+            .arg(new_config.paths.godot.join(&new_config.paths.output))
+            .output()
+            .expect("Unable to move output to the new directory");
+
+        // Change the path to the dynamic libraries in the gdnlib file.
+        let targets_str: Vec<&str> = new_config
+            .general
+            .targets
+            .iter()
+            .map(AsRef::as_ref)
+            .collect();
+        let gdnlib = match content::create_gdnlib_file(
+            &new_config.general.name,
+            &diff_paths(&new_config.paths.output, &new_config.paths.godot)
+                .expect("Unable to get path diff"),
+            &targets_str,
+        ) {
+            Ok(v) => v,
+            Err(e) => {
+                println!("{}", e);
+                exit(1);
+            }
+        };
+
+        // Finally create the new gndlib file and write to it.
+        let gdnlib_file_name = format!("{}.gdnlib", &new_config.general.name);
+
+        let gdnlib_path = &new_config.paths.output;
+        std::fs::File::create(gdnlib_path.join(&gdnlib_file_name))
+            .expect("Unable to create gdnlib file");
+
+        match write(new_config.paths.output.join(gdnlib_file_name), gdnlib) {
+            Ok(_v) => (),
+            Err(e) => {
+                println!("There was a problem creating the gdnlib file: {}", e);
+                exit(1);
+            }
+        }
+
+        // We're done with the config for now so we can write it over the original.
+        let new_config_string =
+            toml::to_string(&new_config).expect("Unable to convert v2.x config to string");
+        match write(&config_path, new_config_string) {
+            Ok(_v) => (),
+            Err(e) => {
+                println!(
+                    "{}: {}",
+                    "There was a problem writing to the v2.x config file".red(),
+                    e
+                );
+                exit(1);
+            }
+        }
+
+        // At some point godot_rust_helper_extensions was changed to godot_rust_helper_ext so we gotta change any reference to it in the Cargo.toml file.
+        let mut cargo_string = read_to_string("Cargo.toml").expect("Unable to read Cargo.toml");
+        if cargo_string.contains("godot_rust_helper_extensions") {
+            cargo_string =
+                cargo_string.replace("godot_rust_helper_extensions", "godot_rust_helper_ext");
+
+            match write("Cargo.toml", cargo_string) {
+                Ok(_v) => (),
+                Err(e) => {
+                    println!(
+                        "{}: {}",
+                        "There was a problem writing to the v2.x Cargo.toml file".red(),
+                        e
+                    );
+                    exit(1);
+                }
+            }
+        }
+
+        // Now we gotta go through each of the components created and check to see if they are still using `use godot_rust_helper_extensions` and change it to ext just like above.
+        for module in new_config.general.modules {
+            let mut module_path = PathBuf::from("src");
+            module_path.push(naming::mod_file_name(&module));
+
+            let mut module_string = read_to_string(&module_path).expect("Unable to read module");
+            // If the module has the old extensions, then update it to the new ones.
+            if module_string.contains("godot_rust_helper_extensions") {
+                module_string =
+                    module_string.replace("godot_rust_helper_extensions", "godot_rust_helper_ext");
+
+                match write(&module_path, module_string) {
+                    Ok(_v) => (),
+                    Err(e) => {
+                        println!(
+                            "{}: {}",
+                            "there was a problem writing to the module file".red(),
+                            e
+                        );
+                        exit(1);
+                    }
+                }
+            }
+        }
+    }
+    if !config_string.contains("nativescript") {
+        config_string = read_to_string(&config_path)
+            .expect("Unable to read godot-rust-helper.toml config file");
+        // Parse the current configuration of the project as configuration v2.
+        let current_config: ConfigV2 = toml::from_str(&config_string).expect(
+            "Unable to parse godot-rust-helper config file of project using godot_rust_helper v2.x",
+        );
+
+        // Create an instance of the new configuration of the project using the values from the current config.
+        let new_config_paths = ConfigPaths {
+            lib: current_config.paths.lib,
+            godot: current_config.paths.godot.to_owned(),
+            output: utils::absolute_path(current_config.paths.output).expect(
+                "Unable to create absolute path from from godot_rust_helper v2.x output path",
+            ),
+            nativescript: current_config.paths.godot.to_owned(),
+            library_file: None,
+            projects: Vec::new(),
+        };
+        let new_config_general = ConfigGeneralV3 {
+            name: current_config.general.name,
+            targets: current_config.general.targets,
+            modules: current_config.general.modules,
+        };
+        let mut new_config = ConfigV3 {
+            general: new_config_general,
+            paths: new_config_paths,
+        };
+
+        // Check to see if the user would like to place the nativescript files in a specific directory.
+        // If no path is provided for the nativescript directory then the files will be placed in the root of the project.
+        let ns_path_as_path_buf = nativescript_path;
+        let ns_path = if ns_path_as_path_buf.as_os_str().is_empty() {
+            PathBuf::from(&new_config.paths.godot)
+        } else if !ns_path_as_path_buf.is_absolute() {
+            utils::absolute_path(ns_path_as_path_buf)
+                .expect("Unable to create absolute path from nativescript path")
+                .as_path()
+                .to_owned()
+        } else {
+            Path::new(&ns_path_as_path_buf).to_path_buf()
+        };
+        new_config.paths.nativescript = ns_path;
+
+        // We're done making changes to the config so we can write over the original now.
+        // We're done with the config for now so we can write it over the original.
+        let new_config_string =
+            toml::to_string(&new_config).expect("Unable to convert v3.x config to string");
+        match write(&config_path, new_config_string) {
+            Ok(_v) => (),
+            Err(e) => {
+                println!(
+                    "{}: {}",
+                    "There was a problem writing to the v3.x config file".red(),
+                    e
+                );
+                exit(1);
+            }
+        }
+    }
+    if !config_string.contains("plugin") {
+        config_string = read_to_string(&config_path)
+            .expect("Unable to read godot-rust-helper.toml config file");
+        // Parse the current configuration of the project as configuration v3.
+        let current_config: ConfigV3 = toml::from_str(&config_string).expect(
+            "Unable to parse godot-rust-helper config file of project using godot_rust_helper v3.x",
+        );
+
+        // Create an instance of the new configuration of the project using the values from the current config.
+        let new_config_paths = ConfigPaths {
+            lib: current_config.paths.lib,
+            godot: current_config.paths.godot.to_owned(),
+            output: utils::absolute_path(current_config.paths.output).expect(
+                "Unable to create absolute path from from godot_rust_helper v3.x output path",
+            ),
+            nativescript: current_config.paths.godot.to_owned(),
+            library_file: None,
+            projects: Vec::new(),
+        };
+        let new_config_general = ConfigGeneral {
+            name: current_config.general.name,
+            targets: current_config.general.targets,
+            modules: current_config.general.modules,
+            plugin: false,
+            class_prefix: None,
+            gdnative_version: GdnativeVersion::default(),
+            godot_version: GodotVersion::default(),
+        };
+        let new_config = Config {
+            general: new_config_general,
+            paths: new_config_paths,
+            dependencies: ConfigDependencies::default(),
+            modules: BTreeMap::new(),
         };
-        new_config.paths.nativescript = ns_path;
 
         // We're done making changes to the config so we can write over the original now.
         // We're done with the config for now so we can write it over the original.
         let new_config_string =
-            toml::to_string(&new_config).expect("Unable to convert v3.x config to string");
+            toml::to_string(&new_config).expect("Unable to convert v4.x config to string");
         match write(&config_path, new_config_string) {
             Ok(_v) => (),
             Err(e) => {
                 println!(
-                    "{}: {}",
-                    "There was a problem writing to the v3.x config file".red(),
-                    e
+                    "{}: {}",
+                    "There was a problem writing to the v4.x config file".red(),
+                    e
+                );
+                exit(1);
+            }
+        }
+    }
+
+    // Libraries created before godot_rust_helper started using generated regions have a lib file without the region
+    // markers so they are added around the existing `mod` declarations and class registrations. Everything else in
+    // the lib file is kept as it is.
+    let lib_file_path = current_dir_path.join("src").join("lib.rs");
+    let lib_file = read_to_string(&lib_file_path).expect("Unable to read the lib file");
+    config_string =
+        read_to_string(&config_path).expect("Unable to read godot-rust-helper.toml config file");
+    let config = parse_config(&config_string, &config_path);
+    if config.general.godot_version == GodotVersion::V3
+        && content::check_lib_file(&lib_file, config.general.godot_version).is_err()
+    {
+        let new_lib_file = match content::add_lib_file_markers(
+            &lib_file,
+            &config.general.modules,
+            &config.modules,
+            config.general.plugin,
+        ) {
+            Ok(v) => v,
+            Err(e) => exit_with_lib_file_error(&e),
+        };
+
+        match write(&lib_file_path, new_lib_file) {
+            Ok(_v) => println!(
+                "added the generated region markers to {}",
+                lib_file_path.display()
+            ),
+            Err(e) => {
+                println!(
+                    "{}: {}",
+                    "There was a problem writing to the lib file".red(),
+                    e
+                );
+                exit(1);
+            }
+        }
+    }
+
+    if let Some(version) = gdnative {
+        update_gdnative_version(&current_dir_path, &config_path, version);
+    }
+
+    println!("{}", "Update finished".green());
+}
+
+/// Converts a library to a newer version of gdnative by bumping the dependency in Cargo.toml and rewriting the API
+/// changes in its source files. Anything that couldn't be converted is reported so that it can be changed by hand.
+///
+/// # Arguments
+///
+/// `library_path` - The path to the library.
+/// `config_path` - The path to the library's config file.
+/// `version` - The version of gdnative to convert the library to.
+fn update_gdnative_version(library_path: &Path, config_path: &Path, version: GdnativeVersion) {
+    let config_string =
+        read_to_string(config_path).expect("Unable to read godot-rust-helper.toml config file");
+    let mut config = parse_config(&config_string, config_path);
+    if config.general.godot_version == GodotVersion::V4 {
+        println!("Libraries for Godot 4 don't use gdnative so --gdnative can't be used");
+        exit(1);
+    }
+
+    let current_version = config.general.gdnative_version;
+    if current_version == version {
+        println!("The library already uses gdnative {}", version);
+        return;
+    }
+    if version < current_version {
+        println!(
+            "The library uses gdnative {} and can't be converted to the older gdnative {}",
+            current_version, version
+        );
+        exit(1);
+    }
+
+    let mut notes = vec![];
+
+    let cargo_toml_path = library_path.join("Cargo.toml");
+    let cargo_toml_string = read_to_string(&cargo_toml_path).expect("Unable to read Cargo.toml");
+    let mut cargo_toml = match CargoToml::parse(&cargo_toml_string) {
+        Ok(cargo_toml) => cargo_toml,
+        Err(e) => {
+            println!("{}", e);
+            exit(1);
+        }
+    };
+    if cargo_toml.set_dependency_version("gdnative", version.dependency()) {
+        if let Err(e) = write(&cargo_toml_path, cargo_toml.to_string()) {
+            println!(
+                "{}: {}",
+                "There was a problem writing to the Cargo.toml file".red(),
+                e
+            );
+            exit(1);
+        }
+    } else {
+        notes.push(
+            "Cargo.toml: the gdnative dependency doesn't have a version that can be changed"
+                .to_string(),
+        );
+    }
+    if cargo_toml.has_dependency("godot_rust_helper_ext") {
+        notes.push(
+            "Cargo.toml: godot_rust_helper_ext is built for gdnative 0.9 and has to be removed or replaced"
+                .to_string(),
+        );
+    }
+
+    let mut updated_files = 0;
+    for file in utils::find_files_with_extensions(&library_path.join("src"), &["rs"]) {
+        let file_name = diff_paths(&file, library_path)
+            .unwrap_or_else(|| file.clone())
+            .to_slash_lossy();
+        let source = read_to_string(&file).expect("Unable to read source file");
+        match migrate::migrate_source(&file_name, &source, current_version, version) {
+            Ok((new_source, file_notes)) => {
+                notes.extend(file_notes);
+                if new_source == source {
+                    continue;
+                }
+
+                if let Err(e) = write(&file, new_source) {
+                    println!(
+                        "{} {}: {}",
+                        "There was a problem writing to".red(),
+                        file_name,
+                        e
+                    );
+                    exit(1);
+                }
+                println!("updated {}", file_name);
+                updated_files += 1;
+            }
+            Err(note) => notes.push(note),
+        }
+    }
+
+    config.general.gdnative_version = version;
+    if let Some(dependency) = &mut config.dependencies.gdnative {
+        dependency.set_version(version.dependency());
+    }
+    let new_config_string = toml::to_string(&config).expect("Unable to convert config to string");
+    if let Err(e) = write(config_path, new_config_string) {
+        println!(
+            "{}: {}",
+            "There was a problem writing to the config file".red(),
+            e
+        );
+        exit(1);
+    }
+
+    // The class registrations in the lib file and the class names in the .gdns files are regenerated from the config
+    // so that they match the names that the classes are registered under with the new version.
+    update_lib_file(&library_path.join("src").join("lib.rs"), &config);
+    update_gdns_class_names(&config);
+
+    println!("converted {} files to gdnative {}", updated_files, version);
+    if !notes.is_empty() {
+        println!(
+            "{}",
+            "These couldn't be converted and have to be changed by hand:".yellow()
+        );
+        for note in notes {
+            println!("  {}", note);
+        }
+    }
+}
+
+/// Sets the class names in the .gdns files of the modules in every Godot project to the names that Godot uses for
+/// the modules' classes.
+///
+/// # Arguments
+///
+/// `config` - The library's config.
+fn update_gdns_class_names(config: &Config) {
+    let class_name_re =
+        regex::Regex::new(r#"(?m)^(resource_name|class_name|script_class_name) = "[^"]*""#)
+            .expect("Unable to create regex pattern");
+
+    for project_config in godot_project_configs(config) {
+        for name in &config.general.modules {
+            let gdns_file_path = gdns_file_path(&project_config, name);
+            let gdns_file = match read_to_string(&gdns_file_path) {
+                Ok(v) => v,
+                Err(_e) => continue,
+            };
+
+            let class_name = content::godot_class_name(name, &config.modules);
+            let new_gdns_file = class_name_re
+                .replace_all(&gdns_file, |captures: &regex::Captures| {
+                    format!("{} = \"{}\"", &captures[1], class_name)
+                });
+            if new_gdns_file == gdns_file {
+                continue;
+            }
+
+            match write(&gdns_file_path, new_gdns_file.as_ref()) {
+                Ok(_v) => println!("updated {}", gdns_file_path.display()),
+                Err(e) => {
+                    println!("There was a problem updating the gdns file: {}", e);
+                    exit(1);
+                }
+            }
+        }
+    }
+}
+
+/// The options of a plugin created with `plugin`.
+#[derive(Debug, Default)]
+pub struct PluginOptions {
+    /// A short description of the plugin.
+    pub description: String,
+    /// The author of the plugin.
+    pub author: String,
+    /// The initial version of the plugin.
+    pub version: String,
+    /// The version of Godot that the plugin is for. If it's not set then it's detected from the Godot project.
+    pub godot: Option<GodotVersion>,
+    /// The sources of the plugin's dependencies.
+    pub dependencies: ConfigDependencies,
+    /// The package fields and version control of the plugin's crate.
+    pub crate_options: CrateOptions,
+}
+
+/// Creates a plugin similar to using `godot_rust_helper new` by creating the base file structure and the plugin.cfg file that allows us to create
+/// a Nativescript plugin.
+///
+/// # Arguments
+///
+/// `name` - The name of the plugin.
+/// `destination` - The destination directory for the library.
+/// `godot_project_dir` - The directory that contains the Godot project that the plugin is for.
+/// `targets` - The build targets that should be set. As of writing this, the available targets are windows, linux, and osx with the default being just windows.
+/// `options` - The details of the plugin that go in its plugin.cfg file and the version of Godot that it's for.
+pub fn create_plugin(
+    name: String,
+    destination: PathBuf,
+    godot_project_dir: PathBuf,
+    targets: String,
+    options: PluginOptions,
+) {
+    println!("{}", "creating plugin".white());
+
+    let PluginOptions {
+        description,
+        author,
+        version,
+        godot,
+        dependencies,
+        crate_options,
+    } = options;
+
+    // Make the destination directory is an absolute path if it is not already one.
+    let dest_path = if !destination.is_absolute() {
+        utils::absolute_path(destination)
+            .expect("Unable to create absolute path from destination path")
+            .as_path()
+            .to_owned()
+    } else {
+        Path::new(&destination).to_path_buf()
+    };
+
+    // Make the godot directory is an absolute path if it is not already one.
+    let godot_path = if !godot_project_dir.is_absolute() {
+        utils::absolute_path(godot_project_dir)
+            .expect("Unable to create absolute path from destination path")
+            .as_path()
+            .to_owned()
+    } else {
+        Path::new(&godot_project_dir).to_path_buf()
+    };
+
+    // Check to see if the destination directory already exists, we don't want to overwrite an existing project.
+    if dest_path.exists() {
+        println!("A library with the specified destination already exists, please choose another destination for the library.");
+        exit(1);
+    }
+
+    // Check to make sure that the provided path to the Godot project is valid, i.e. it has a project.godot file at its root.
+    let godot_project_path = godot_path.join("project.godot");
+    if !godot_project_path.exists() {
+        println!("The godot project dir provided is not valid.");
+        exit(1);
+    }
+    let godot_version = godot.unwrap_or_else(|| detect_godot_version(&godot_project_path));
+
+    // Create the crate of the library in the destination directory.
+    let dest_basename_string = dest_path
+        .file_stem()
+        .and_then(|basename| basename.to_str())
+        .expect("Unable to get destination basename");
+    if let Err(e) = naming::validate_crate_name(dest_basename_string) {
+        println!("{}", e);
+        exit(1);
+    }
+    create_crate(
+        &dest_path,
+        dest_basename_string,
+        &crate_options,
+        godot_version,
+        GdnativeVersion::default(),
+        &dependencies,
+    );
+    set_current_dir(&dest_path).expect("Unable to change to library directory");
+
+    // Make sure the targets provided are in the list of accepted targets.
+    let valid_targets = &["windows", "linux", "osx"];
+    let targets_split: Vec<String> = targets.split(",").map(|s| s.to_string()).collect();
+    for t in &targets_split {
+        if !valid_targets.iter().any(|&i| i == t) {
+            println!("An invalid target was specified: {}", t);
+            exit(1);
+        }
+    }
+
+    // Create all of the variations of the plugin names we'll need.
+    let plugin_name = &name;
+    let plugin_name_normalized = plugin_name.to_case(Case::Snake);
+    let plugin_class_name = plugin_name.to_case(Case::Pascal);
+    // Create all of the paths we'll need for the plugin files.
+    let plugin_path = godot_path.join("addons").join(&plugin_name_normalized);
+    let plugin_cfg_path = plugin_path.join("plugin.cfg");
+
+    // Create the folder structure for the plugin if it doesn't already exist.
+    match std::fs::create_dir_all(&plugin_path) {
+        Ok(_v) => (),
+        Err(e) => {
+            println!(
+                "There was a problem creating the the plugin directory structure: {}",
+                e
+            );
+            exit(1);
+        }
+    }
+
+    // Create the config and write it to the godot-rust-helper.toml file.
+    let config_paths = ConfigPaths {
+        lib: dest_path.to_owned(),
+        godot: godot_path.to_owned(),
+        output: plugin_path.to_owned(),
+        nativescript: plugin_path.to_owned(),
+        library_file: None,
+        projects: Vec::new(),
+    };
+    let config_general = ConfigGeneral {
+        name: dest_basename_string.to_string(),
+        modules: vec![],
+        targets: targets_split.to_owned(),
+        plugin: true,
+        class_prefix: None,
+        gdnative_version: GdnativeVersion::default(),
+        godot_version,
+    };
+    let config = Config {
+        general: config_general,
+        paths: config_paths,
+        dependencies,
+        modules: BTreeMap::new(),
+    };
+    let config_string = toml::to_string(&config).expect("Unable to convert config to string");
+    match write("godot-rust-helper.toml", config_string) {
+        Ok(_v) => (),
+        Err(e) => {
+            println!("There was a problem creating the config file: {}", e);
+            exit(1);
+        }
+    }
+
+    // Create the gdnlib or gdextension file and write it to the plugin folder.
+    let targets_str: Vec<&str> = targets_split.iter().map(AsRef::as_ref).collect();
+    let (library_file_name, library_file) = create_library_file(
+        &config,
+        &diff_paths(&config.paths.output, &config.paths.godot)
+            .expect("Unable to get path diff for the plugin"),
+        &targets_str,
+    );
+
+    match write(config.paths.output.join(library_file_name), library_file) {
+        Ok(_v) => (),
+        Err(e) => {
+            println!(
+                "There was a problem creating the library file for the plugin: {}",
+                e
+            );
+            exit(1);
+        }
+    }
+
+    // Create the plugin.cfg file and write it to the plugin folder. Godot 4 adds the editor plugins of GDExtension
+    // libraries by itself so they don't need one.
+    let plugin_cfg_fields = PluginConfigFields {
+        name: name.to_owned(),
+        description,
+        author,
+        version,
+        script: naming::gdns_file_name(&plugin_class_name),
+    };
+    let plugin_cfg = PluginConfig {
+        plugin: plugin_cfg_fields,
+    };
+    let plugin_cfg_string =
+        toml::to_string(&plugin_cfg).expect("Unable to convert plugin config to string");
+    if godot_version == GodotVersion::V3 {
+        match write(plugin_cfg_path, plugin_cfg_string) {
+            Ok(_v) => (),
+            Err(e) => {
+                println!("There was a problem creating the plugin config file: {}", e);
+                exit(1);
+            }
+        }
+    }
+
+    // Create the initial src/lib.rs file for the plugin that will contain all of the created modules.
+    match write(
+        "src/lib.rs",
+        content::create_initial_lib_file(
+            config.general.godot_version,
+            config.general.gdnative_version,
+        ),
+    ) {
+        Ok(_v) => (),
+        Err(e) => {
+            println!("There was a problem writing to the lib file: {}", e);
+            exit(1);
+        }
+    }
+
+    // Create the module's base script file that the configuration expects.
+    create_module(Some(&plugin_class_name), CreateOptions::default());
+
+    // Since this base script is a bit different, all instances of Node need to be swapped with EditorPlugin and then we write it back.
+    let base_plugin_script_path = format!("src/{}", naming::mod_file_name(&plugin_class_name));
+    let base_plugin_script =
+        read_to_string(&base_plugin_script_path).expect("Unable to read plugin's base script");
+    let updated_base_plugin_script = base_plugin_script
+        .replace("Node", "EditorPlugin")
+        .replace("#[class(tool, ", "#[class(tool, editor_plugin, ");
+    match write(base_plugin_script_path, updated_base_plugin_script) {
+        Ok(_v) => (),
+        Err(e) => {
+            println!(
+                "There was a problem modifying the plugin's base script: {}",
+                e
+            );
+            exit(1);
+        }
+    }
+    println!("{}", "plugin created".white());
+}
+
+/// Adds a module to the config and lib file and creates its .rs file and .gdns file. This is used by the commands
+/// that create modules. The updated config is returned.
+///
+/// # Arguments
+///
+/// `command` - The name of the command that is creating the module.
+/// `name` - The class name of the module to create.
+/// `module_config` - The settings of the module that differ from the defaults.
+/// `mod_file` - The contents of the module's .rs file.
+fn add_module(
+    command: &str,
+    name: &str,
+    mut module_config: ConfigModule,
+    mod_file: &str,
+) -> Config {
+    let current_dir_path = std::env::current_dir().expect("Unable to get current directory");
+    let config_path = Path::new(&current_dir_path).join("godot-rust-helper.toml");
+    let mut config = read_config(command);
+
+    // Make sure that the lib file can be updated before anything is changed.
+    let lib_file_path = current_dir_path.join("src").join("lib.rs");
+    check_lib_file(&lib_file_path, &config);
+
+    // The main module of a plugin is named after the plugin's crate on purpose.
+    let crate_name = Some(config.general.name.as_str()).filter(|_| !config.general.plugin);
+    if let Err(e) = naming::validate_class_name(name, crate_name, class_prefix(&config)) {
+        println!("{}", e);
+        exit(1);
+    }
+    if config.general.modules.iter().any(|i| i == name) {
+        println!("A module with the same name already exists");
+        exit(1);
+    }
+
+    // Save the module name to the config file so that it can be worked with later. New modules get the class prefix
+    // in the name that Godot uses for them.
+    let class_name = naming::godot_class_name(name, class_prefix(&config));
+    if class_name != name {
+        module_config.class_name = Some(class_name);
+    }
+    config.general.modules.push(name.to_string());
+    if module_config != ConfigModule::default() {
+        config.modules.insert(name.to_string(), module_config);
+    }
+    let new_config_string = toml::to_string(&config).expect("Unable to convert config to string");
+
+    match write(config_path, new_config_string) {
+        Ok(_v) => (),
+        Err(e) => {
+            println!("There was a problem creating the module: {}", e);
+            exit(1);
+        }
+    }
+
+    // Add the new module to the generated regions of the src/lib.rs file.
+    update_lib_file(&lib_file_path, &config);
+
+    // Create the module file for the module.
+    let mod_file_path = current_dir_path
+        .join("src")
+        .join(naming::mod_file_name(name));
+
+    match write(mod_file_path, mod_file) {
+        Ok(_v) => (),
+        Err(e) => {
+            println!("There was a problem creating the mod file: {}", e);
+            exit(1);
+        }
+    }
+
+    // Create the gdns file. Godot 4 libraries don't have any since GDExtension registers the classes directly.
+    if config.general.godot_version == GodotVersion::V4 {
+        return config;
+    }
+    for project_config in godot_project_configs(&config) {
+        let gdns_file_contents = content::create_gdns_file(
+            &content::godot_class_name(name, &config.modules),
+            &gdnlib_res_path(&project_config),
+            project_config.modules.get(name),
+        );
+        let gdns_file_path = gdns_file_path(&project_config, name);
+
+        match write(gdns_file_path, gdns_file_contents) {
+            Ok(_v) => (),
+            Err(e) => {
+                println!("There was a problem creating the gdns file: {}", e);
+                exit(1);
+            }
+        }
+    }
+
+    return config;
+}
+
+/// Returns the version of Godot that a project was saved with.
+///
+/// # Arguments
+///
+/// `project_file_path` - The path to the project's project.godot file.
+fn detect_godot_version(project_file_path: &Path) -> GodotVersion {
+    let project_file = read_to_string(project_file_path).unwrap_or_default();
+
+    return GodotVersion::detect(&project_file);
+}
+
+/// Creates the directory of a new library with its Cargo.toml, .gitignore, and src directory, and initializes a git
+/// repository in it if that was asked for. The src/lib.rs file is written afterwards.
+///
+/// # Arguments
+///
+/// `dest_path` - The directory to create the library in.
+/// `name` - The name of the crate.
+/// `options` - The package fields and version control of the crate.
+/// `godot_version` - The version of Godot that the library is for.
+/// `gdnative_version` - The version of gdnative that the library uses when the config doesn't set a source for it.
+/// `dependencies` - The sources of the dependencies from the config.
+fn create_crate(
+    dest_path: &Path,
+    name: &str,
+    options: &CrateOptions,
+    godot_version: GodotVersion,
+    gdnative_version: GdnativeVersion,
+    dependencies: &ConfigDependencies,
+) {
+    if let Err(e) = create_dir_all(dest_path.join("src")) {
+        println!("There was a problem creating the library: {}", e);
+        exit(1);
+    }
+
+    let mut cargo_toml = CargoToml::parse(&content::create_cargo_toml_file(
+        name,
+        &options.edition,
+        &options.authors,
+        options.license.as_deref(),
+    ))
+    .expect("Unable to parse the new Cargo.toml");
+    cargo_toml.add_cdylib_crate_type();
+    set_dependencies(
+        &mut cargo_toml,
+        godot_version,
+        gdnative_version,
+        dependencies,
+    );
+
+    let files = [
+        ("Cargo.toml", cargo_toml.to_string()),
+        (".gitignore", content::create_gitignore_file()),
+    ];
+    for (file_name, contents) in files.iter() {
+        if let Err(e) = write(dest_path.join(file_name), contents) {
+            println!("There was a problem creating the library: {}", e);
+            exit(1);
+        }
+    }
+
+    if options.git {
+        let initialized = Command::new("git")
+            .arg("init")
+            .arg("--quiet")
+            .current_dir(dest_path)
+            .status()
+            .map(|status| status.success())
+            .unwrap_or(false);
+        if !initialized {
+            println!(
+                "{}",
+                "git init failed so the library was created without a git repository".yellow()
+            );
+        }
+    }
+}
+
+/// Sets the dependencies of a new library. Libraries for Godot 3 get gdnative and, unless it was turned off,
+/// godot_rust_helper_ext from the sources in the config. Libraries for Godot 4 get gdext instead.
+///
+/// # Arguments
+///
+/// `cargo_toml` - The library's Cargo.toml file.
+/// `godot_version` - The version of Godot that the library is for.
+/// `gdnative_version` - The version of gdnative that the library uses when the config doesn't set a source for it.
+/// `dependencies` - The sources of the dependencies from the config.
+fn set_dependencies(
+    cargo_toml: &mut CargoToml,
+    godot_version: GodotVersion,
+    gdnative_version: GdnativeVersion,
+    dependencies: &ConfigDependencies,
+) {
+    match godot_version {
+        GodotVersion::V3 => {
+            let gdnative = dependencies
+                .gdnative
+                .clone()
+                .unwrap_or_else(|| Dependency::Version(gdnative_version.dependency().to_string()));
+            cargo_toml.set_dependency("gdnative", Some(&gdnative));
+
+            let ext = dependencies
+                .godot_rust_helper_ext
+                .clone()
+                .unwrap_or_else(configs::add_extensions_dep);
+            cargo_toml.set_dependency(
+                "godot_rust_helper_ext",
+                if dependencies.ext { Some(&ext) } else { None },
+            );
+        }
+        GodotVersion::V4 => {
+            cargo_toml.set_dependency("gdnative", None);
+            cargo_toml.set_dependency("godot_rust_helper_ext", None);
+            cargo_toml.set_dependency("godot", Some(&configs::add_gdext_dep()));
+        }
+    }
+}
+
+/// Returns the name and the contents of the file that Godot loads the library with, which is a .gdnlib file for
+/// Godot 3 and a .gdextension file for Godot 4.
+///
+/// # Arguments
+///
+/// `config` - The library's config.
+/// `output_path` - The path where the compiled files are being output relative to the Godot project.
+/// `targets` - The build targets of the library.
+fn create_library_file(
+    config: &Config,
+    output_path: &PathBuf,
+    targets: &[&str],
+) -> (String, String) {
+    return match config.general.godot_version {
+        GodotVersion::V3 => {
+            match content::create_gdnlib_file(&config.general.name, output_path, targets) {
+                Ok(v) => (library_file_name(config), v),
+                Err(e) => {
+                    println!("{}", e);
+                    exit(1);
+                }
+            }
+        }
+        GodotVersion::V4 => {
+            match content::create_gdextension_file(&config.general.name, output_path, targets) {
+                Ok(v) => (library_file_name(config), v),
+                Err(e) => {
+                    println!("{}", e);
+                    exit(1);
+                }
+            }
+        }
+    };
+}
+
+/// Returns the name of the gdnlib or gdextension file that's created for the library.
+///
+/// # Arguments
+///
+/// `config` - The library's config.
+fn library_file_name(config: &Config) -> String {
+    return match config.general.godot_version {
+        GodotVersion::V3 => format!("{}.gdnlib", config.general.name),
+        GodotVersion::V4 => format!("{}.gdextension", config.general.name),
+    };
+}
+
+/// Returns the path to the library's gdnlib or gdextension file, which is the one found by `init` if there is one.
+///
+/// # Arguments
+///
+/// `config` - The library's config.
+fn library_file_path(config: &Config) -> PathBuf {
+    return match &config.paths.library_file {
+        Some(path) => path.to_owned(),
+        None => config.paths.output.join(library_file_name(config)),
+    };
+}
+
+/// Returns the `res://` path of the library's gdnlib file that the .gdns files point to.
+///
+/// # Arguments
+///
+/// `config` - The library's config.
+fn gdnlib_res_path(config: &Config) -> String {
+    return utils::godot_resource_path(&config.paths.godot, &library_file_path(config));
+}
+
+/// Returns the path to a module's .gdns file, which is the one found by `init` if there is one.
+///
+/// # Arguments
+///
+/// `config` - The library's config.
+/// `name` - The name of the module.
+fn gdns_file_path(config: &Config, name: &str) -> PathBuf {
+    return match config.modules.get(name).and_then(|m| m.gdns.as_ref()) {
+        Some(path) => path.to_owned(),
+        None => config.paths.nativescript.join(naming::gdns_file_name(name)),
+    };
+}
+
+/// Returns the config of the library for each Godot project that it's deployed to, starting with the one in
+/// `paths`. The configs of the other projects use their paths instead and the default file names, since the files
+/// found by `init` are only in the first project. They also leave out the module icons, which are only in the first
+/// project too.
+///
+/// # Arguments
+///
+/// `config` - The library's config.
+fn godot_project_configs(config: &Config) -> Vec<Config> {
+    let mut configs = vec![config.clone()];
+
+    for project in &config.paths.projects {
+        let mut project_config = config.clone();
+        project_config.paths.godot = project.godot.to_owned();
+        project_config.paths.output = project.output.to_owned();
+        project_config.paths.nativescript = project.nativescript.to_owned();
+        project_config.paths.library_file = None;
+        for module_config in project_config.modules.values_mut() {
+            module_config.gdns = None;
+            module_config.icon = None;
+        }
+        configs.push(project_config);
+    }
+
+    return configs;
+}
+
+/// Exits if the version of Godot that was passed to `--godot` isn't the one that the library was created for.
+///
+/// # Arguments
+///
+/// `config` - The library's config.
+/// `godot` - The version of Godot that was passed to `--godot`, if any.
+fn check_godot_version(config: &Config, godot: Option<GodotVersion>) {
+    if let Some(godot) = godot.filter(|v| *v != config.general.godot_version) {
+        println!(
+            "--godot {} was used but the library is for Godot {}",
+            godot, config.general.godot_version
+        );
+        exit(1);
+    }
+}
+
+/// Exits if the library is for Godot 4, which is used by the commands that work with .gdns files since Godot 4
+/// libraries don't have any.
+///
+/// # Arguments
+///
+/// `config` - The library's config.
+/// `command` - The name of the command that only works with Godot 3 libraries.
+fn require_godot_3(config: &Config, command: &str) {
+    if config.general.godot_version == GodotVersion::V4 {
+        println!(
+            "The {} command only works with libraries for Godot 3 since it uses .gdns files",
+            command
+        );
+        exit(1);
+    }
+}
+
+/// Reads the config of the library in the current directory, exiting if the current directory isn't a library created
+/// with the `new` command.
+///
+/// # Arguments
+///
+/// `command` - The name of the command that needs the config.
+fn read_config(command: &str) -> Config {
+    // Check to see if we are in the directory of a library created with the `new` command by checking for the presence of a godot-rust-helper.toml file.
+    let current_dir_path = std::env::current_dir().expect("Unable to get current directory");
+    let config_path = Path::new(&current_dir_path).join("godot-rust-helper.toml");
+    if !config_path.exists() {
+        println!(
+            "The {} command can only be used inside of a library created with the new command",
+            command
+        );
+        exit(1);
+    }
+
+    let config_string =
+        read_to_string(&config_path).expect("Unable to read godot-rust-helper.toml config file");
+    if let Some(workspace_config) = parse_workspace_config(&config_string) {
+        println!(
+            "The {} command has to be used inside of one of the workspace's libraries: {}",
+            command,
+            workspace_config.workspace.members.join(", ")
+        );
+        exit(1);
+    }
+
+    return parse_config(&config_string, &config_path);
+}
+
+/// Parses the contents of a library's godot-rust-helper.toml file, exiting with the problem and the path of the file if
+/// it isn't a valid config.
+///
+/// # Arguments
+///
+/// `config_string` - The contents of the config file.
+/// `config_path` - The path to the config file.
+fn parse_config(config_string: &str, config_path: &Path) -> Config {
+    return match toml::from_str(config_string) {
+        Ok(v) => v,
+        Err(e) => {
+            println!("Unable to parse {}: {}", config_path.display(), e);
+            exit(1);
+        }
+    };
+}
+
+/// Returns the config of the workspace in a directory, or `None` if the directory isn't the root of a workspace.
+///
+/// # Arguments
+///
+/// `dir` - The directory that might be the root of a workspace.
+fn read_workspace_config(dir: &Path) -> Option<WorkspaceConfig> {
+    let config_string = read_to_string(dir.join("godot-rust-helper.toml")).ok()?;
+
+    return parse_workspace_config(&config_string);
+}
+
+/// Parses the contents of a godot-rust-helper.toml file as a workspace config, exiting if it's a workspace config that
+/// isn't valid.
+///
+/// # Arguments
+///
+/// `config_string` - The contents of the config file.
+fn parse_workspace_config(config_string: &str) -> Option<WorkspaceConfig> {
+    return match WorkspaceConfig::parse(config_string) {
+        Ok(v) => v,
+        Err(e) => {
+            println!("{}", e);
+            exit(1);
+        }
+    };
+}
+
+/// Returns the directory and the config of each of the workspace's member libraries.
+///
+/// # Arguments
+///
+/// `workspace_dir` - The root of the workspace.
+/// `workspace_config` - The workspace's config.
+fn read_member_configs(
+    workspace_dir: &Path,
+    workspace_config: &WorkspaceConfig,
+) -> Vec<(PathBuf, Config)> {
+    let mut member_configs = vec![];
+    for member in &workspace_config.workspace.members {
+        let member_dir = workspace_dir.join(member);
+        let config_path = member_dir.join("godot-rust-helper.toml");
+        let config_string = match read_to_string(&config_path) {
+            Ok(v) => v,
+            Err(_e) => {
+                println!(
+                    "The workspace member {} doesn't have a godot-rust-helper.toml config file",
+                    member
                 );
                 exit(1);
             }
+        };
+        let config = parse_config(&config_string, &config_path);
+        member_configs.push((member_dir, config));
+    }
+
+    return member_configs;
+}
+
+/// Changes to the directory of one of the workspace's libraries when a command is used at the root of a workspace
+/// so that the command works on that library.
+///
+/// # Arguments
+///
+/// `command` - The name of the command.
+/// `lib` - The member library that was passed to `--lib`, either its path in the workspace or its directory name.
+fn enter_workspace_member(command: &str, lib: Option<&str>) {
+    let current_dir_path = current_dir().expect("Unable to get current directory");
+
+    match (read_workspace_config(&current_dir_path), lib) {
+        (Some(workspace_config), Some(lib)) => {
+            let members = &workspace_config.workspace.members;
+            let member = members.iter().find(|member| {
+                member.as_str() == lib
+                    || Path::new(member).file_name().and_then(|n| n.to_str()) == Some(lib)
+            });
+            match member {
+                Some(member) => set_current_dir(current_dir_path.join(member))
+                    .expect("Unable to change to the member's directory"),
+                None => {
+                    println!(
+                        "{} isn't a library in the workspace, use one of: {}",
+                        lib,
+                        members.join(", ")
+                    );
+                    exit(1);
+                }
+            }
+        }
+        (Some(workspace_config), None) => {
+            println!(
+                "Use --lib to pick the library to use the {} command in: {}",
+                command,
+                workspace_config.workspace.members.join(", ")
+            );
+            exit(1);
+        }
+        (None, Some(_lib)) => {
+            println!("--lib can only be used at the root of a workspace");
+            exit(1);
         }
+        (None, None) => (),
     }
-    if !config_string.contains("plugin") {
-        config_string = read_to_string(&config_path)
-            .expect("Unable to read godot-rust-helper.toml config file");
-        // Parse the current configuration of the project as configuration v3.
-        let current_config: ConfigV3 = toml::from_str(&config_string).expect(
-            "Unable to parse godot-rust-helper config file of project using godot_rust_helper v3.x",
-        );
+}
 
-        // Create an instance of the new configuration of the project using the values from the current config.
-        let new_config_paths = ConfigPaths {
-            lib: current_config.paths.lib,
-            godot: current_config.paths.godot.to_owned(),
-            output: utils::absolute_path(current_config.paths.output).expect(
-                "Unable to create absolute path from from godot_rust_helper v3.x output path",
-            ),
-            nativescript: current_config.paths.godot.to_owned(),
-        };
-        let new_config_general = ConfigGeneral {
-            name: current_config.general.name,
-            targets: current_config.general.targets,
-            modules: current_config.general.modules,
-            plugin: false,
-        };
-        let new_config = Config {
-            general: new_config_general,
-            paths: new_config_paths,
-        };
+/// Adds a library to the members in the workspace's config and in the workspace's Cargo.toml file if it has one.
+///
+/// # Arguments
+///
+/// `workspace_dir` - The root of the workspace.
+/// `workspace_config` - The workspace's config.
+/// `lib_dir` - The directory of the library.
+fn add_workspace_member(
+    workspace_dir: &Path,
+    mut workspace_config: WorkspaceConfig,
+    lib_dir: &Path,
+) {
+    let member = diff_paths(lib_dir, workspace_dir)
+        .expect("Unable to get member path diff")
+        .to_slash_lossy()
+        .to_string();
 
-        // We're done making changes to the config so we can write over the original now.
-        // We're done with the config for now so we can write it over the original.
-        let new_config_string =
-            toml::to_string(&new_config).expect("Unable to convert v4.x config to string");
-        match write(config_path, new_config_string) {
-            Ok(_v) => (),
+    if !workspace_config.workspace.members.contains(&member) {
+        workspace_config.workspace.members.push(member.to_owned());
+    }
+    let config_string =
+        toml::to_string(&workspace_config).expect("Unable to convert config to string");
+    match write(workspace_dir.join("godot-rust-helper.toml"), config_string) {
+        Ok(_v) => (),
+        Err(e) => {
+            println!("There was a problem updating the workspace config: {}", e);
+            exit(1);
+        }
+    }
+
+    let cargo_toml_path = workspace_dir.join("Cargo.toml");
+    if let Ok(cargo_toml_string) = read_to_string(&cargo_toml_path) {
+        let mut cargo_toml = match CargoToml::parse(&cargo_toml_string) {
+            Ok(v) => v,
             Err(e) => {
+                println!("{}", e);
+                exit(1);
+            }
+        };
+        if cargo_toml.is_workspace() {
+            cargo_toml.add_workspace_member(&member);
+            if let Err(e) = write(&cargo_toml_path, cargo_toml.to_string()) {
                 println!(
-                    "{}: {}",
-                    "There was a problem writing to the v4.x config file".red(),
+                    "There was a problem updating the workspace's Cargo.toml: {}",
                     e
                 );
                 exit(1);
@@ -874,251 +3666,248 @@ pub fn update(output: PathBuf, nativescript_path: PathBuf) {
         }
     }
 
-    println!("{}", "Update finished".green());
+    println!("added {} to the workspace", member);
 }
 
-/// Creates a plugin similar to using `godot_rust_helper new` by creating the base file structure and the plugin.cfg file that allows us to create
-/// a Nativescript plugin.
+/// Returns the directory that cargo builds a library into, which is the target directory of the Cargo workspace
+/// that the library belongs to if it's in one.
 ///
 /// # Arguments
 ///
-/// `name` - The name of the plugin.
-/// `destination` - The destination directory for the library.
-/// `godot_project_dir` - The directory that contains the Godot project that the plugin is for.
-/// `description` - A short description of the plugin.
-/// `author` - The author of the plugin. If no author is provided then the author field from the Cargo.toml is used.
-/// `version` - The initial version of the plugin.
-/// `targets` - The build targets that should be set. As of writing this, the available targets are windows, linux, and osx with the default being just windows.
-pub fn create_plugin(
-    name: String,
-    destination: PathBuf,
-    godot_project_dir: PathBuf,
-    description: String,
-    author: String,
-    version: String,
-    targets: String,
-) {
-    println!("{}", "creating plugin".white());
+/// `lib_dir` - The directory of the library.
+fn target_dir(lib_dir: &Path) -> PathBuf {
+    let workspace_dir = lib_dir.ancestors().find(|dir| {
+        read_to_string(dir.join("Cargo.toml"))
+            .ok()
+            .and_then(|c| CargoToml::parse(&c).ok())
+            .map(|c| c.is_workspace())
+            .unwrap_or(false)
+    });
+
+    return workspace_dir.unwrap_or(lib_dir).join("target");
+}
 
-    // Make the destination directory is an absolute path if it is not already one.
-    let dest_path = if !destination.is_absolute() {
-        utils::absolute_path(destination)
-            .expect("Unable to create absolute path from destination path")
-            .as_path()
-            .to_owned()
-    } else {
-        Path::new(&destination).to_path_buf()
-    };
+/// Returns the library's class name prefix, which is added to the names that Godot uses for the classes of new modules.
+/// gdnative 0.9 can only register a class under the name of its struct so the prefix can't be used with it.
+///
+/// # Arguments
+///
+/// `config` - The config of the library.
+fn class_prefix(config: &Config) -> Option<&str> {
+    let prefix = config.general.class_prefix.as_deref();
+    if prefix.is_some()
+        && config.general.godot_version == GodotVersion::V3
+        && config.general.gdnative_version == GdnativeVersion::V0_9
+    {
+        println!("The class_prefix in godot-rust-helper.toml can only be used with gdnative 0.10 since gdnative 0.9 registers classes under the names of their structs. Use `update --gdnative 0.10` to convert the library or remove the class_prefix.");
+        exit(1);
+    }
 
-    // Make the godot directory is an absolute path if it is not already one.
-    let godot_path = if !godot_project_dir.is_absolute() {
-        utils::absolute_path(godot_project_dir)
-            .expect("Unable to create absolute path from destination path")
-            .as_path()
-            .to_owned()
-    } else {
-        Path::new(&godot_project_dir).to_path_buf()
-    };
+    return prefix;
+}
 
-    // Check to see if the destination directory already exists, we don't want to overwrite an existing project.
-    if dest_path.exists() {
-        println!("A library with the specified destination already exists, please choose another destination for the library.");
-        exit(1);
+/// Returns the path to a file in the Godot project that was passed to a command. Files can be given as a `res://`
+/// path, relative to the Godot project, or relative to the current directory.
+///
+/// # Arguments
+///
+/// `config` - The config of the library.
+/// `path` - The path that was passed to the command.
+fn resolve_godot_file(config: &Config, path: &Path) -> PathBuf {
+    let path_string = path.to_string_lossy().to_string();
+    if let Some(res_path) = path_string.strip_prefix("res://") {
+        return config.paths.godot.join(res_path);
+    }
+    if path.is_relative() && config.paths.godot.join(path).exists() {
+        return config.paths.godot.join(path);
     }
 
-    // Check to make sure that the provided path to the Godot project is valid, i.e. it has a project.godot file at its root.
-    let godot_project_path = godot_path.join("project.godot");
-    if !godot_project_path.exists() {
-        println!("The godot project dir provided is not valid.");
-        exit(1);
+    return std::env::current_dir()
+        .expect("Unable to get current directory")
+        .join(path);
+}
+
+/// Writes a new file to the Godot project, creating the directories that it's in if needed. Existing files are never
+/// overwritten.
+///
+/// # Arguments
+///
+/// `file_path` - The path to the file.
+/// `contents` - The contents of the file.
+/// `description` - What the file is, used in the messages, e.g. 'scene'.
+fn write_new_godot_file(file_path: &Path, contents: &str, description: &str) {
+    if file_path.exists() {
+        println!(
+            "Skipping the {} since {} already exists",
+            description,
+            file_path.display()
+        );
+        return;
     }
 
-    // Run the `cargo new --lib` command in the destination directory to create the library.
-    let dest_parent = dest_path
-        .parent()
-        .expect("Unable to get destination parent");
-    let dest_basename = dest_path
-        .file_stem()
-        .expect("Unable to get destination basename");
-    set_current_dir(&dest_parent).expect("Unable to change to destination parent directory");
-
-    let dest_basename_string = dest_basename
-        .to_str()
-        .expect("Unable to convert destination basename to string");
-    match Command::new("cargo")
-        .arg("new")
-        .arg(dest_basename_string)
-        .arg("--lib")
-        .output()
-    {
+    if let Some(parent) = file_path.parent() {
+        create_dir_all(parent).expect("Unable to create the directory in the Godot project");
+    }
+    match write(file_path, contents) {
         Ok(_v) => (),
         Err(e) => {
-            println!("{}", e);
+            println!("There was a problem creating the {}: {}", description, e);
             exit(1);
         }
     }
+}
+
+/// Returns the Godot project's project.godot file.
+///
+/// # Arguments
+///
+/// `config` - The library's config.
+fn read_project_file(config: &Config) -> ConfigFile {
+    let project_file_path = config.paths.godot.join("project.godot");
+    let project_file_string = match read_to_string(&project_file_path) {
+        Ok(v) => v,
+        Err(e) => {
+            println!("There was a problem reading the project.godot file: {}", e);
+            exit(1);
+        }
+    };
 
-    // Set up the Cargo.toml file of the library to have the required tags and dependencies.
-    set_current_dir(&dest_basename).expect("Unable to change to library directory");
-    let cargo_toml_string = read_to_string("Cargo.toml").expect("Unable to read Cargo.toml");
-    let cargo_toml: Cargo = toml::from_str(&cargo_toml_string).expect("Unable to parse Cargo.toml");
-    let cargo_toml_str = toml::to_string(&cargo_toml).expect("Unable to convert to toml to string");
+    return ConfigFile::parse(&project_file_string);
+}
 
+/// Saves the Godot project's project.godot file.
+///
+/// # Arguments
+///
+/// `config` - The library's config.
+/// `project_file` - The updated project.godot file.
+fn write_project_file(config: &Config, project_file: &ConfigFile) {
     match write(
-        "Cargo.toml",
-        cargo_toml_str
-            .replace("\\", "")
-            .replace("\"{", "{")
-            .replace("}\"", "}"),
+        config.paths.godot.join("project.godot"),
+        project_file.to_string(),
     ) {
         Ok(_v) => (),
         Err(e) => {
-            println!("There was a problem creating the library: {}", e);
-            exit(1);
-        }
-    }
-
-    // Make sure the targets provided are in the list of accepted targets.
-    let valid_targets = &["windows", "linux", "osx"];
-    let targets_split: Vec<String> = targets.split(",").map(|s| s.to_string()).collect();
-    for t in &targets_split {
-        if !valid_targets.iter().any(|&i| i == t) {
-            println!("An invalid target was specified: {}", t);
+            println!("There was a problem updating the project.godot file: {}", e);
             exit(1);
         }
     }
+}
 
-    // Create all of the variations of the plugin names we'll need.
-    let plugin_name = &name;
-    let plugin_name_normalized = plugin_name.to_case(Case::Snake);
-    // Create all of the paths we'll need for the plugin files.
-    let plugin_path = godot_path.join("addons").join(&plugin_name_normalized);
-    let plugin_cfg_path = plugin_path.join("plugin.cfg");
+/// Returns the name of the autoload that loads a .gdns file, if there is one.
+///
+/// # Arguments
+///
+/// `project_file` - The Godot project's project.godot file.
+/// `gdns_res_path` - The `res://` path of the .gdns file.
+fn find_autoload(project_file: &ConfigFile, gdns_res_path: &str) -> Option<String> {
+    return project_file
+        .section("autoload")
+        .into_iter()
+        .find(|(_, value)| value.trim_matches('"').trim_start_matches('*') == gdns_res_path)
+        .map(|(key, _)| key);
+}
 
-    // Create the folder structure for the plugin if it doesn't already exist.
-    match std::fs::create_dir_all(&plugin_path) {
-        Ok(_v) => (),
-        Err(e) => {
-            println!(
-                "There was a problem creating the the plugin directory structure: {}",
-                e
-            );
-            exit(1);
-        }
+/// Adds a module's .gdns file to the autoloads of the project.godot file as an enabled singleton that is named after
+/// the module's class. Nothing is changed if the module is already an autoload.
+///
+/// # Arguments
+///
+/// `config` - The library's config.
+/// `project_file` - The Godot project's project.godot file.
+/// `name` - The class name of the module.
+fn add_autoload(config: &Config, project_file: &mut ConfigFile, name: &str) {
+    let gdns_file_path = gdns_file_path(config, name);
+    let gdns_res_path = utils::godot_resource_path(&config.paths.godot, &gdns_file_path);
+
+    if let Some(autoload_name) = find_autoload(project_file, &gdns_res_path) {
+        println!("{} is already the {} autoload", name, autoload_name);
+        return;
     }
 
-    // Create the config and write it to the godot-rust-helper.toml file.
-    let config_paths = ConfigPaths {
-        lib: dest_path.to_owned(),
-        godot: godot_path.to_owned(),
-        output: plugin_path.to_owned(),
-        nativescript: plugin_path.to_owned(),
-    };
-    let config_general = ConfigGeneral {
-        name: dest_basename_string.to_string(),
-        modules: vec![],
-        targets: targets_split.to_owned(),
-        plugin: true,
-    };
-    let config = Config {
-        general: config_general,
-        paths: config_paths,
-    };
-    let config_string = toml::to_string(&config).expect("Unable to convert config to string");
-    match write("godot-rust-helper.toml", config_string) {
-        Ok(_v) => (),
-        Err(e) => {
-            println!("There was a problem creating the config file: {}", e);
-            exit(1);
-        }
+    let autoload_name = content::godot_class_name(name, &config.modules);
+    if project_file.get("autoload", &autoload_name).is_some() {
+        println!(
+            "The Godot project already has an autoload named {}",
+            autoload_name
+        );
+        exit(1);
     }
 
-    // Create the gdnlib file and write it to the plugin folder.
-    let targets_str: Vec<&str> = targets_split.iter().map(AsRef::as_ref).collect();
-    let gdnlib = content::create_gdnlib_file(
-        &config.general.name,
-        &diff_paths(&config.paths.output, &config.paths.godot)
-            .expect("Unable to get path diff for the plugin"),
-        &targets_str,
+    project_file.set(
+        "autoload",
+        &autoload_name,
+        &format!("\"*{}\"", gdns_res_path),
     );
+}
 
-    // Finally create the new gndlib file and write to it.
-    let gdnlib_file_name = format!("{}.gdnlib", &config.general.name);
-
-    let gdnlib_path = &config.paths.output;
-    std::fs::File::create(&gdnlib_path.join(&gdnlib_file_name))
-        .expect("Unable to create gdnlib file for the plugin");
+/// Exits with an error if the lib file is missing the markers of the regions that godot_rust_helper generates.
+///
+/// # Arguments
+///
+/// `lib_file_path` - The path to the src/lib.rs file.
+/// `config` - The library's config.
+fn check_lib_file(lib_file_path: &Path, config: &Config) {
+    let lib_file = read_to_string(lib_file_path).expect("Unable to read the lib file");
 
-    match write(&config.paths.output.join(gdnlib_file_name), gdnlib) {
-        Ok(_v) => (),
-        Err(e) => {
-            println!(
-                "There was a problem creating the gdnlib file for the plugin: {}",
-                e
-            );
-            exit(1);
-        }
+    if let Err(e) = content::check_lib_file(&lib_file, config.general.godot_version) {
+        exit_with_lib_file_error(&e);
     }
+}
 
-    // Create the plugin.cfg file and write it to the plugin folder.
-    let plugin_cfg_fields = PluginConfigFields {
-        name: name.to_owned(),
-        description: description,
-        author: author,
-        version: version,
-        script: format!("{}.gdns", plugin_name_normalized),
-    };
-    let plugin_cfg = PluginConfig {
-        plugin: plugin_cfg_fields,
-    };
-    let plugin_cfg_string =
-        toml::to_string(&plugin_cfg).expect("Unable to convert plugin config to string");
-    match write(plugin_cfg_path, plugin_cfg_string) {
-        Ok(_v) => (),
-        Err(e) => {
-            println!("There was a problem creating the plugin config file: {}", e);
-            exit(1);
-        }
-    }
+/// Prints why the lib file can't be updated along with how to fix it and exits.
+///
+/// # Arguments
+///
+/// `error` - The problem with the lib file.
+fn exit_with_lib_file_error(error: &str) -> ! {
+    println!(
+        "The lib file can't be updated because {}. godot_rust_helper only manages the code between its begin and end markers in src/lib.rs, so add the markers back around the `mod` declarations and the class registrations in the `init` function.",
+        error
+    );
+    exit(1);
+}
 
-    // Run `cargo create` to create the module's base script file that the configuration expects.
-    match Command::new("godot_rust_helper")
-        .arg("create")
-        .arg(plugin_name.to_case(Case::Pascal))
-        .output()
-    {
-        Ok(_v) => (),
-        Err(e) => {
-            println!("{}", e);
-            exit(1);
-        }
-    }
-    // Since this base script is a bit different, all instances of Node need to be swapped with EditorPlugin and then we write it back.
-    let base_plugin_script_path = format!("src/{}.rs", name.to_case(Case::Snake));
-    let base_plugin_script =
-        read_to_string(&base_plugin_script_path).expect("Unable to read plugin's base script");
-    let updated_base_plugin_script = base_plugin_script.replace("Node", "EditorPlugin");
-    match write(base_plugin_script_path, updated_base_plugin_script) {
+/// Updates the generated regions of the lib file to match the modules in the config.
+///
+/// # Arguments
+///
+/// `lib_file_path` - The path to the src/lib.rs file.
+/// `config` - The library's config.
+fn update_lib_file(lib_file_path: &Path, config: &Config) {
+    check_lib_file(lib_file_path, config);
+
+    let lib_file = read_to_string(lib_file_path).expect("Unable to read the lib file");
+    let new_lib_file = content::update_lib_file(
+        &lib_file,
+        &config.general.modules,
+        &config.modules,
+        config.general.plugin,
+        config.general.godot_version,
+    )
+    .expect("Unable to update the lib file");
+
+    match write(lib_file_path, new_lib_file) {
         Ok(_v) => (),
         Err(e) => {
-            println!(
-                "There was a problem modifying the plugin's base script: {}",
-                e
-            );
+            println!("There was a problem updating the lib file: {}", e);
             exit(1);
         }
     }
-    println!("{}", "plugin created".white());
 }
 
 /// Runs the build command and logs some info used by `watch_library` to show the version of godot_rust_helper and the timestamp of when the last build was run.
-fn build_with_time_log() {
+///
+/// # Arguments
+///
+/// `godot` - The version of Godot that the library is expected to be for, as it's passed to `--godot`.
+fn build_with_time_log(godot: Option<GodotVersion>) {
     let dt: DateTime<Local> = Local::now();
     let dt_formatted = dt.format("%Y-%m-%d %H:%M:%S").to_string();
 
-    build_library();
+    build_library(godot);
 
-    println!("");
+    println!();
     println!(
         "[{}] {}",
         dt_formatted.white(),
@@ -0,0 +1,324 @@
+#![allow(clippy::needless_return)]
+
+/// A file in Godot's ConfigFile format, such as project.godot, that can be edited without changing the parts of the
+/// file that aren't touched. Comments, blank lines, the order of the sections and keys, and values that span
+/// multiple lines are all kept as they are.
+#[derive(Debug)]
+pub struct ConfigFile {
+    /// The lines of the file.
+    lines: Vec<String>,
+}
+
+/// A key and its value in a ConfigFile along with the lines that they are on.
+#[derive(Debug)]
+struct ConfigFileEntry {
+    /// The section that the key is in. Keys before the first section have an empty section.
+    section: String,
+    /// The name of the key.
+    key: String,
+    /// The index of the line that the key is on.
+    start: usize,
+    /// The index of the last line of the key's value.
+    end: usize,
+}
+
+impl ConfigFile {
+    /// Parses the contents of a ConfigFile.
+    ///
+    /// # Arguments
+    ///
+    /// `contents` - The contents of the file.
+    pub fn parse(contents: &str) -> ConfigFile {
+        return ConfigFile {
+            lines: contents.split('\n').map(|l| l.to_string()).collect(),
+        };
+    }
+
+    /// Returns the value of a key in a section as it's written in the file, e.g. `"*res://game.gdns"` with the
+    /// quotes included.
+    ///
+    /// # Arguments
+    ///
+    /// `section` - The name of the section without the brackets.
+    /// `key` - The name of the key.
+    pub fn get(&self, section: &str, key: &str) -> Option<String> {
+        return self
+            .entries()
+            .into_iter()
+            .find(|e| e.section == section && e.key == key)
+            .map(|e| self.entry_value(&e));
+    }
+
+    /// Returns the keys and values in a section in the order that they appear in the file.
+    ///
+    /// # Arguments
+    ///
+    /// `section` - The name of the section without the brackets.
+    pub fn section(&self, section: &str) -> Vec<(String, String)> {
+        return self
+            .entries()
+            .into_iter()
+            .filter(|e| e.section == section)
+            .map(|e| (e.key.to_owned(), self.entry_value(&e)))
+            .collect();
+    }
+
+    /// Sets the value of a key in a section. An existing key is replaced in place, a new key is added after the last
+    /// key of the section, and a new section is added in alphabetical order like Godot does.
+    ///
+    /// # Arguments
+    ///
+    /// `section` - The name of the section without the brackets.
+    /// `key` - The name of the key.
+    /// `value` - The value as it should be written in the file, e.g. `"*res://game.gdns"` with the quotes included.
+    pub fn set(&mut self, section: &str, key: &str, value: &str) {
+        let line = format!("{}={}", key, value);
+        let entries = self.entries();
+
+        if let Some(entry) = entries
+            .iter()
+            .find(|e| e.section == section && e.key == key)
+        {
+            self.lines.splice(entry.start..=entry.end, vec![line]);
+            return;
+        }
+
+        if let Some(entry) = entries.iter().rfind(|e| e.section == section) {
+            self.lines.insert(entry.end + 1, line);
+            return;
+        }
+
+        let headers = self.section_headers();
+        if let Some((_, header_index)) = headers.iter().find(|(name, _)| name == section) {
+            // Godot leaves a blank line between the header and the first key.
+            let mut index = header_index + 1;
+            if self.lines.get(index).map(|l| l.trim().is_empty()) == Some(true)
+                && index + 1 < self.lines.len()
+            {
+                index += 1;
+            }
+            self.lines.insert(index, line);
+            return;
+        }
+
+        let new_section = vec![format!("[{}]", section), String::new(), line, String::new()];
+        match headers.iter().find(|(name, _)| name.as_str() > section) {
+            Some((_, header_index)) => {
+                self.lines.splice(*header_index..*header_index, new_section);
+            }
+            None => {
+                // Put the new section at the end with one blank line before it, keeping the final newline.
+                while self.lines.len() > 1
+                    && self.lines[self.lines.len() - 1].trim().is_empty()
+                    && self.lines[self.lines.len() - 2].trim().is_empty()
+                {
+                    self.lines.pop();
+                }
+                let has_content = self.lines.iter().any(|l| !l.trim().is_empty());
+                let ends_with_newline = self.lines.last().map(|l| l.is_empty()) == Some(true);
+
+                let mut insert = vec![];
+                if has_content {
+                    insert.push(String::new());
+                }
+                insert.extend(new_section);
+
+                if ends_with_newline {
+                    // The file's final newline takes the place of the blank line after the new section.
+                    insert.pop();
+                    let index = self.lines.len() - 1;
+                    self.lines.splice(index..index, insert);
+                } else {
+                    self.lines.extend(insert);
+                }
+            }
+        }
+    }
+
+    /// Removes a key from a section. If the section doesn't have any keys left then the section is removed too.
+    /// Returns whether the key was found.
+    ///
+    /// # Arguments
+    ///
+    /// `section` - The name of the section without the brackets.
+    /// `key` - The name of the key.
+    pub fn remove(&mut self, section: &str, key: &str) -> bool {
+        let entry = match self
+            .entries()
+            .into_iter()
+            .find(|e| e.section == section && e.key == key)
+        {
+            Some(v) => v,
+            None => return false,
+        };
+        self.lines.drain(entry.start..=entry.end);
+
+        if self.entries().iter().any(|e| e.section == section) {
+            return true;
+        }
+
+        // Remove the empty section along with the blank lines after it.
+        if let Some((_, header_index)) = self
+            .section_headers()
+            .into_iter()
+            .find(|(name, _)| name == section)
+        {
+            let mut end = header_index + 1;
+            while end < self.lines.len() - 1 && self.lines[end].trim().is_empty() {
+                end += 1;
+            }
+            self.lines.drain(header_index..end);
+
+            // A section at the end of the file leaves the blank line that was before it behind.
+            while header_index >= self.lines.len() - 1
+                && self.lines.len() > 1
+                && self.lines[self.lines.len() - 1].is_empty()
+                && self.lines[self.lines.len() - 2].trim().is_empty()
+            {
+                self.lines.pop();
+            }
+        }
+
+        return true;
+    }
+
+    /// Returns the names of the sections along with the index of the line that their header is on.
+    fn section_headers(&self) -> Vec<(String, usize)> {
+        let mut headers = vec![];
+        let mut value_end = None;
+
+        for (index, line) in self.lines.iter().enumerate() {
+            if value_end.map(|end| index <= end).unwrap_or(false) {
+                continue;
+            }
+
+            if let Some(name) = section_name(line) {
+                headers.push((name, index));
+            } else if line.contains('=') && !is_comment(line) {
+                value_end = Some(self.value_end(index));
+            }
+        }
+
+        return headers;
+    }
+
+    /// Returns all of the keys in the file along with the lines that they are on.
+    fn entries(&self) -> Vec<ConfigFileEntry> {
+        let mut entries = vec![];
+        let mut section = String::new();
+        let mut index = 0;
+
+        while index < self.lines.len() {
+            let line = &self.lines[index];
+
+            if let Some(name) = section_name(line) {
+                section = name;
+            } else if let Some(equals) = line.find('=') {
+                if !is_comment(line) {
+                    let end = self.value_end(index);
+                    entries.push(ConfigFileEntry {
+                        section: section.to_owned(),
+                        key: line[..equals].trim().to_string(),
+                        start: index,
+                        end,
+                    });
+                    index = end;
+                }
+            }
+
+            index += 1;
+        }
+
+        return entries;
+    }
+
+    /// Returns the index of the last line of the value that starts on a line. Values that open a string, array,
+    /// dictionary, or constructor continue until it's closed.
+    ///
+    /// # Arguments
+    ///
+    /// `start` - The index of the line that the key is on.
+    fn value_end(&self, start: usize) -> usize {
+        let mut depth = 0;
+        let mut in_string = false;
+        let mut escaped = false;
+
+        for (index, line) in self.lines.iter().enumerate().skip(start) {
+            let text = if index == start {
+                &line[line.find('=').map(|i| i + 1).unwrap_or(0)..]
+            } else {
+                line.as_str()
+            };
+
+            for c in text.chars() {
+                if in_string {
+                    if escaped {
+                        escaped = false;
+                    } else if c == '\\' {
+                        escaped = true;
+                    } else if c == '"' {
+                        in_string = false;
+                    }
+                    continue;
+                }
+
+                match c {
+                    '"' => in_string = true,
+                    '[' | '{' | '(' => depth += 1,
+                    ']' | '}' | ')' => depth -= 1,
+                    _ => (),
+                }
+            }
+
+            if depth <= 0 && !in_string {
+                return index;
+            }
+        }
+
+        return self.lines.len() - 1;
+    }
+
+    /// Returns the value of an entry as it's written in the file.
+    ///
+    /// # Arguments
+    ///
+    /// `entry` - The entry to get the value of.
+    fn entry_value(&self, entry: &ConfigFileEntry) -> String {
+        let first_line = &self.lines[entry.start];
+        let mut value =
+            vec![first_line[first_line.find('=').map(|i| i + 1).unwrap_or(0)..].to_string()];
+        value.extend(self.lines[entry.start + 1..=entry.end].iter().cloned());
+
+        return value.join("\n").trim().to_string();
+    }
+}
+
+impl std::fmt::Display for ConfigFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        return write!(f, "{}", self.lines.join("\n"));
+    }
+}
+
+/// Returns the name of the section if the line is a section header, e.g. `autoload` for `[autoload]`.
+///
+/// # Arguments
+///
+/// `line` - The line to check.
+fn section_name(line: &str) -> Option<String> {
+    let trimmed = line.trim();
+    if trimmed.starts_with('[') && trimmed.ends_with(']') && !trimmed.contains('=') {
+        return Some(trimmed[1..trimmed.len() - 1].trim().to_string());
+    }
+
+    return None;
+}
+
+/// Returns whether the line is a comment.
+///
+/// # Arguments
+///
+/// `line` - The line to check.
+fn is_comment(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    return trimmed.starts_with(';') || trimmed.starts_with('#');
+}
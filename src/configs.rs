@@ -1,68 +1,129 @@
+#![allow(clippy::needless_return)]
+
+use serde::ser::SerializeStruct;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
 use std::path::PathBuf;
+use std::str::FromStr;
 
-/// The structure of the Cargo.toml file created by the `godot-rust-helper new` command.
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Cargo {
-	/// A reference to the CargoPackage struct.
-	pub package: CargoPackage,
-	/// A reference to the CargoLib struct.
-	/// If it doesn't exist (which it most likely won't) then we use the `create_cargo_lib` function to set the default value.
-	#[serde(default = "create_cargo_lib")]
-	pub lib: CargoLib,
-	/// A reference to the CargoDependencies struct.
-	pub dependencies: CargoDependencies,
-}
-
-/// The fields of the Cargo.toml that are under the [package] tag.
-#[derive(Debug, Serialize, Deserialize)]
-pub struct CargoPackage {
-	/// The name of the package.
-	pub name: String,
-	/// The version of the package.
-	pub version: String,
-	/// The authors of the package.
-	pub authors: Vec<String>,
-	/// The Rust edition used in the package.
-	pub edition: String,
+/// A dependency in the Cargo.toml file, which is either a version requirement or a table that says where the
+/// dependency comes from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Dependency {
+	/// A version requirement from crates.io, e.g. `"0.9.1"`.
+	Version(String),
+	/// A dependency with a path, git repository, or features.
+	Detailed(DetailedDependency),
 }
 
-/// The fields of the Cargo.toml that are under the [lib] tag.
-#[derive(Debug, Serialize, Deserialize)]
-pub struct CargoLib {
-	/// The type of crate it is.
-	#[serde(rename = "crate-type")]
-	pub crate_type: Vec<String>,
+/// The fields of a dependency that is written as a table.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DetailedDependency {
+	/// The version requirement of the dependency.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub version: Option<String>,
+	/// The path to a local copy of the dependency.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub path: Option<String>,
+	/// The git repository to get the dependency from.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub git: Option<String>,
+	/// The features of the dependency to turn on.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub features: Vec<String>,
 }
 
-/// The fields of the Cargo.toml that are under the [dependencies] tag.
-#[derive(Debug, Serialize, Deserialize)]
-pub struct CargoDependencies {
-	/// The gdnative dependency that is required to create Rust modules.
-	#[serde(default = "add_gdnative_dep")]
-	pub gdnative: String,
-	/// Optional helper methods.
-	#[serde(default = "add_extensions_dep")]
-	pub godot_rust_helper_ext: String,
+impl Dependency {
+	/// Changes the version requirement of the dependency. Dependencies that come from a path or git repository
+	/// without a version are left as they are.
+	pub fn set_version(&mut self, version: &str) {
+		match self {
+			Dependency::Version(current) => *current = version.to_string(),
+			Dependency::Detailed(detailed) => {
+				if detailed.version.is_some() {
+					detailed.version = Some(version.to_string());
+				}
+			}
+		}
+	}
+}
+
+/// Dependencies are written the way they appear in the Cargo.toml file, e.g. `"0.9.1"` or
+/// `{ git = "https://github.com/godot-rust/godot-rust", features = ["serde"] }`.
+impl fmt::Display for Dependency {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Dependency::Version(version) => write!(f, "{}", toml::Value::from(version.as_str())),
+			Dependency::Detailed(detailed) => {
+				let mut fields = vec![];
+				let sources = [
+					("version", &detailed.version),
+					("path", &detailed.path),
+					("git", &detailed.git),
+				];
+				for (key, value) in sources.iter() {
+					if let Some(value) = value {
+						fields.push(format!("{} = {}", key, toml::Value::from(value.as_str())));
+					}
+				}
+				if !detailed.features.is_empty() {
+					fields.push(format!(
+						"features = {}",
+						toml::Value::from(detailed.features.clone())
+					));
+				}
+
+				write!(f, "{{ {} }}", fields.join(", "))
+			}
+		}
+	}
 }
 
-/// Returns the contents of what should appear under the [lib] tag.
-/// This is used by the Cargo struct to create the default value for the [lib] tag if no value is present.
-fn create_cargo_lib() -> CargoLib {
-	return CargoLib {
-		crate_type: vec!["cdylib".to_string()],
-	};
+/// Dependencies are parsed from the command line as either a version requirement or an inline table, e.g.
+/// `0.9.3` or `{ path = "../godot-rust/gdnative" }`.
+impl FromStr for Dependency {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let s = s.trim();
+		if !s.starts_with('{') {
+			if s.is_empty() {
+				return Err("The dependency can't be empty".to_string());
+			}
+			return Ok(Dependency::Version(s.trim_matches('"').to_string()));
+		}
+
+		#[derive(Deserialize)]
+		struct Wrapper {
+			dependency: DetailedDependency,
+		}
+		let wrapper: Wrapper = toml::from_str(&format!("dependency = {}", s))
+			.map_err(|e| format!("{} is not a valid dependency: {}", s, e))?;
+		let dependency = wrapper.dependency;
+		if dependency.version.is_none() && dependency.path.is_none() && dependency.git.is_none() {
+			return Err(format!("{} needs a version, path, or git repository", s));
+		}
+
+		return Ok(Dependency::Detailed(dependency));
+	}
 }
 
-/// Returns the gdnative dependency to add to the Cargo.toml dependencies.
-/// This is used by the CargoDependencies struct to add the gdnative dependency that is necessary.
-fn add_gdnative_dep() -> String {
-	return String::from("0.9.1");
+/// Returns the godot_rust_helper_ext dependency that's added to the Cargo.toml dependencies when the config doesn't
+/// have one.
+pub fn add_extensions_dep() -> Dependency {
+	return Dependency::Detailed(DetailedDependency {
+		git: Some(String::from(
+			"https://github.com/robertcorponoi/godot_rust_helper_ext",
+		)),
+		..DetailedDependency::default()
+	});
 }
 
-/// Returns the godot_rust_helper_extensions dependency to add to the Cargo.toml dependencies.
-pub fn add_extensions_dep() -> String {
-	return String::from("{ git = \"https://github.com/robertcorponoi/godot_rust_helper_ext\" }");
+/// Returns the gdext dependency to add to the Cargo.toml dependencies of libraries for Godot 4.
+pub fn add_gdext_dep() -> Dependency {
+	return Dependency::Version(String::from("0.2"));
 }
 
 /// The structure of the plugin.cfg file used by `godot_rust_helper plugin`.
@@ -89,16 +150,130 @@ pub struct PluginConfigFields {
 
 /// The structure of the godot-rust-helper.toml config file created by the `godot-rust-helper new`
 /// command and used throughout the rest of the commands.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
 	/// General configuration options that can't be grouped in other ways (for now).
 	pub general: ConfigGeneral,
 	/// The locations of various important parts of the project.
 	pub paths: ConfigPaths,
+	/// The sources of the dependencies that are written to the Cargo.toml file. This is left out of the config when
+	/// the defaults are used.
+	#[serde(default, skip_serializing_if = "ConfigDependencies::is_default")]
+	pub dependencies: ConfigDependencies,
+	/// Settings for individual modules, keyed by the module's class name. Modules that use the defaults don't have
+	/// an entry here.
+	#[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+	pub modules: BTreeMap<String, ConfigModule>,
 }
 
-/// General configuration options that can't be grouped in other ways (for now).
+/// The structure of the godot-rust-helper.toml config file at the root of a Cargo workspace whose members are
+/// libraries with their own configs, output paths, and gdnlib files.
 #[derive(Debug, Serialize, Deserialize)]
+pub struct WorkspaceConfig {
+	pub workspace: ConfigWorkspace,
+}
+
+/// The libraries in the workspace.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ConfigWorkspace {
+	/// The directories of the member libraries relative to the root of the workspace.
+	pub members: Vec<String>,
+}
+
+impl WorkspaceConfig {
+	/// Parses the contents of a godot-rust-helper.toml file as a workspace config, returning `None` if it's the
+	/// config of a library instead and an error if it has a `[workspace]` table that isn't valid.
+	///
+	/// # Arguments
+	///
+	/// `config_string` - The contents of the config file.
+	pub fn parse(config_string: &str) -> Result<Option<WorkspaceConfig>, String> {
+		let value: toml::Value = match config_string.parse() {
+			Ok(v) => v,
+			Err(_e) => return Ok(None),
+		};
+		if value.get("workspace").is_none() {
+			return Ok(None);
+		}
+
+		return match toml::from_str(config_string) {
+			Ok(v) => Ok(Some(v)),
+			Err(e) => Err(format!("Unable to parse the workspace config: {}", e)),
+		};
+	}
+}
+
+/// The dependencies of the library that can be changed from their defaults, e.g. to use a local copy of gdnative.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ConfigDependencies {
+	/// The gdnative dependency. If this isn't set then the version that matches `gdnative_version` is used.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub gdnative: Option<Dependency>,
+	/// Whether the godot_rust_helper_ext dependency is added, which is turned off with `new --no-ext`.
+	#[serde(default = "default_ext", skip_serializing_if = "is_default_ext")]
+	pub ext: bool,
+	/// The godot_rust_helper_ext dependency. If this isn't set then its git repository is used.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub godot_rust_helper_ext: Option<Dependency>,
+}
+
+impl Default for ConfigDependencies {
+	fn default() -> Self {
+		return ConfigDependencies {
+			gdnative: None,
+			ext: default_ext(),
+			godot_rust_helper_ext: None,
+		};
+	}
+}
+
+impl ConfigDependencies {
+	/// Used to leave the dependencies out of the config when they're the defaults.
+	fn is_default(&self) -> bool {
+		return *self == ConfigDependencies::default();
+	}
+}
+
+/// The toml serializer needs plain values to come before tables so the dependencies that are written as tables are
+/// serialized last.
+impl Serialize for ConfigDependencies {
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let mut state = serializer.serialize_struct("ConfigDependencies", 3)?;
+		if !is_default_ext(&self.ext) {
+			state.serialize_field("ext", &self.ext)?;
+		}
+
+		let dependencies = [
+			("gdnative", &self.gdnative),
+			("godot_rust_helper_ext", &self.godot_rust_helper_ext),
+		];
+		for (name, dependency) in dependencies.iter() {
+			if let Some(Dependency::Version(_)) = dependency {
+				state.serialize_field(name, dependency)?;
+			}
+		}
+		for (name, dependency) in dependencies.iter() {
+			if let Some(Dependency::Detailed(_)) = dependency {
+				state.serialize_field(name, dependency)?;
+			}
+		}
+
+		return state.end();
+	}
+}
+
+/// The godot_rust_helper_ext dependency is added unless it's turned off.
+fn default_ext() -> bool {
+	return true;
+}
+
+/// Used to leave `ext` out of the config when it's turned on.
+fn is_default_ext(ext: &bool) -> bool {
+	return *ext == default_ext();
+}
+
+/// General configuration options that can't be grouped in other ways (for now).
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConfigGeneral {
 	/// The name of the library.
 	pub name: String,
@@ -108,11 +283,179 @@ pub struct ConfigGeneral {
 	pub modules: Vec<String>,
 	/// Indicates whether this is a plugin or not.
 	pub plugin: bool,
+	/// A prefix that is added to the class names of new modules so that they don't collide with Godot's classes or
+	/// the classes of other libraries, e.g. `Rs` to create `RsTimer` from `Timer`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub class_prefix: Option<String>,
+	/// The version of gdnative that the generated code is written for. Libraries that don't have this use 0.9.
+	#[serde(default, skip_serializing_if = "GdnativeVersion::is_default")]
+	pub gdnative_version: GdnativeVersion,
+	/// The version of Godot that the library is for. Libraries that don't have this are for Godot 3.
+	#[serde(default, skip_serializing_if = "GodotVersion::is_default")]
+	pub godot_version: GodotVersion,
+}
+
+/// The versions of Godot, as they are passed to `--godot`, that libraries can be created for.
+pub const GODOT_VERSIONS: [&str; 2] = ["3", "4"];
+
+/// A version of Godot that a library can be created for.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum GodotVersion {
+	/// Godot 3, which loads the library through GDNative with a .gdnlib file and a .gdns file for each class.
+	#[default]
+	#[serde(rename = "3")]
+	V3,
+	/// Godot 4, which loads the library through GDExtension with a .gdextension file and registers the classes itself.
+	#[serde(rename = "4")]
+	V4,
+}
+
+impl GodotVersion {
+	/// Used to leave the default version out of the config.
+	fn is_default(&self) -> bool {
+		return *self == GodotVersion::default();
+	}
+
+	/// Returns the version of Godot that a project.godot file was saved with. Godot 4 saves projects with
+	/// `config_version=5` while Godot 3 uses `config_version=4`.
+	///
+	/// # Arguments
+	///
+	/// `project_file` - The contents of the project.godot file.
+	pub fn detect(project_file: &str) -> GodotVersion {
+		if project_file
+			.lines()
+			.any(|l| l.replace(' ', "") == "config_version=5")
+		{
+			return GodotVersion::V4;
+		}
+
+		return GodotVersion::V3;
+	}
+}
+
+impl std::str::FromStr for GodotVersion {
+	type Err = String;
+
+	fn from_str(value: &str) -> Result<Self, Self::Err> {
+		return match value {
+			"3" => Ok(GodotVersion::V3),
+			"4" => Ok(GodotVersion::V4),
+			_ => Err(format!(
+				"{} is not a supported Godot version, use one of {}",
+				value,
+				GODOT_VERSIONS.join(", ")
+			)),
+		};
+	}
+}
+
+impl std::fmt::Display for GodotVersion {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		return match self {
+			GodotVersion::V3 => write!(f, "3"),
+			GodotVersion::V4 => write!(f, "4"),
+		};
+	}
+}
+
+/// The versions of gdnative, as they are passed to `new --gdnative`, that code can be generated for.
+pub const GDNATIVE_VERSIONS: [&str; 2] = ["0.9", "0.10"];
+
+/// A version of gdnative that code can be generated for. The versions differ in where the API is exported from and
+/// in how methods and their owners are declared.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default, Serialize, Deserialize)]
+pub enum GdnativeVersion {
+	/// gdnative 0.9, where methods get the owner as a `&Node`.
+	#[default]
+	#[serde(rename = "0.9")]
+	V0_9,
+	/// gdnative 0.10, where methods get the owner as a `TRef<Node>` marked with `#[base]`.
+	#[serde(rename = "0.10")]
+	V0_10,
+}
+
+impl GdnativeVersion {
+	/// Used to leave the default version out of the config.
+	fn is_default(&self) -> bool {
+		return *self == GdnativeVersion::default();
+	}
+
+	/// Returns the version requirement of the gdnative dependency in the Cargo.toml file.
+	pub fn dependency(self) -> &'static str {
+		return match self {
+			GdnativeVersion::V0_9 => "0.9.1",
+			GdnativeVersion::V0_10 => "0.10",
+		};
+	}
+}
+
+impl std::str::FromStr for GdnativeVersion {
+	type Err = String;
+
+	fn from_str(value: &str) -> Result<Self, Self::Err> {
+		return match value {
+			"0.9" => Ok(GdnativeVersion::V0_9),
+			"0.10" => Ok(GdnativeVersion::V0_10),
+			_ => Err(format!(
+				"{} is not a supported gdnative version, use one of {}",
+				value,
+				GDNATIVE_VERSIONS.join(", ")
+			)),
+		};
+	}
+}
+
+impl std::fmt::Display for GdnativeVersion {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		return match self {
+			GdnativeVersion::V0_9 => write!(f, "0.9"),
+			GdnativeVersion::V0_10 => write!(f, "0.10"),
+		};
+	}
+}
+
+/// Settings for an individual module that differ from the defaults used by modules created with `create`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConfigModule {
+	/// The path of the Rust module that contains the class, relative to the crate root, e.g. `enemies::goblin`.
+	/// If not set then the module is expected to be in its own file named after the class.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub path: Option<String>,
+	/// The Godot class that the module's struct inherits from. This is saved for the structs that were adopted by
+	/// `import` or `init` and for the modules converted with `create --from-gd` so that `list` and `attach` don't
+	/// have to find the struct in the source files.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub base: Option<String>,
+	/// The name that Godot uses for the class when it isn't the name of the module, which is the case for modules that
+	/// were created or renamed while the library had a `class_prefix`. Modules that existed before the prefix was set
+	/// keep the names that the Godot project already uses for them.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub class_name: Option<String>,
+	/// Indicates whether the class should be registered as a tool class so that it also runs in the editor.
+	/// Every class in a plugin is registered as a tool class regardless of this setting.
+	#[serde(default, skip_serializing_if = "is_false")]
+	pub tool: bool,
+	/// Indicates whether the class is a named script class so that it shows up in Godot's "Create New Node" dialog.
+	#[serde(default, skip_serializing_if = "is_false")]
+	pub named: bool,
+	/// The `res://` path of the icon that Godot shows for the named script class.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub icon: Option<String>,
+	/// The path to the module's .gdns file when it isn't the one named after the module in the nativescript
+	/// directory, which is the case for existing .gdns files that were found by `init`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub gdns: Option<PathBuf>,
+}
+
+/// Used to leave boolean module settings out of the config when they are turned off.
+fn is_false(value: &bool) -> bool {
+	return !*value;
 }
 
 /// Contains the location of the scripts, the godot project, and the folder in the godot project that contains the
 /// gdnlib file and the build files.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConfigPaths {
 	/// The path to the library that contains the Rust scripts.
 	pub lib: PathBuf,
@@ -122,6 +465,25 @@ pub struct ConfigPaths {
 	pub output: PathBuf,
 	/// The relative path to where the nativescript files in Godot.
 	pub nativescript: PathBuf,
+	/// The path to the gdnlib or gdextension file when it isn't the one named after the library in the output
+	/// directory, which is the case for an existing file that was found by `init`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub library_file: Option<PathBuf>,
+	/// The other Godot projects that the library is deployed to along with the one in `godot`, which get their own
+	/// gdnlib and gdns files and a copy of the build files.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub projects: Vec<ConfigProject>,
+}
+
+/// The paths of another Godot project that the library is deployed to, added with `godot_rust_helper project`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigProject {
+	/// The path to the Godot project.
+	pub godot: PathBuf,
+	/// The path to the gdnlib file and build files in the Godot project.
+	pub output: PathBuf,
+	/// The path to where the nativescript files go in the Godot project.
+	pub nativescript: PathBuf,
 }
 
 /// The v3.x version of the godot-rust-helper.toml config file.
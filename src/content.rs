@@ -1,122 +1,1388 @@
-use crate::utils;
+#![allow(clippy::needless_return)]
+
+use crate::configs::{ConfigModule, GdnativeVersion, GodotVersion};
+use crate::gdscript::GdScript;
+use crate::naming;
 
 use path_slash::PathBufExt;
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
-/// Returns the initial contents of the src/lib.rs file.
-pub fn create_initial_lib_file() -> String {
-    return r#"#[macro_use]
-extern crate gdnative;
+/// The Rust editions that a new library can use.
+pub const EDITIONS: [&str; 4] = ["2015", "2018", "2021", "2024"];
+
+/// The name of the generated region of the lib file that contains the `mod` declarations of the modules.
+const LIB_MODULES_REGION: &str = "modules";
+
+/// The name of the generated region of the lib file that contains the calls that register the modules' classes.
+const LIB_CLASSES_REGION: &str = "classes";
 
-fn init(handle: gdnative::nativescript::InitHandle) {
+/// Returns the generated regions that the lib file of a library for the given version of Godot has. gdext registers
+/// the classes by itself so the lib files of Godot 4 libraries only declare the modules.
+///
+/// # Arguments
+///
+/// `godot_version` - The version of Godot that the library is for.
+fn lib_regions(godot_version: GodotVersion) -> &'static [&'static str] {
+    return match godot_version {
+        GodotVersion::V3 => &[LIB_MODULES_REGION, LIB_CLASSES_REGION],
+        GodotVersion::V4 => &[LIB_MODULES_REGION],
+    };
 }
 
-godot_init!(init);"#
-        .to_string();
+/// Returns the comment that marks the beginning of a generated region in the lib file.
+///
+/// # Arguments
+///
+/// `region` - The name of the region.
+pub fn region_begin_marker(region: &str) -> String {
+    return format!("// godot_rust_helper: begin {}", region);
 }
 
-/// Creates the contents for the src/lib.rs file depending on what modules are present.
+/// Returns the comment that marks the end of a generated region in the lib file.
+///
+/// # Arguments
+///
+/// `region` - The name of the region.
+pub fn region_end_marker(region: &str) -> String {
+    return format!("// godot_rust_helper: end {}", region);
+}
+
+/// The parts of the generated code that differ between the versions of gdnative.
+impl GdnativeVersion {
+    /// Returns the lines at the top of the lib file that bring gdnative's macros into scope.
+    fn lib_header(self) -> &'static str {
+        return match self {
+            GdnativeVersion::V0_9 => "#[macro_use]\nextern crate gdnative;",
+            GdnativeVersion::V0_10 => "use gdnative::prelude::*;",
+        };
+    }
+
+    /// Returns the type of the handle that the classes are registered with.
+    fn init_handle(self) -> &'static str {
+        return match self {
+            GdnativeVersion::V0_9 => "gdnative::nativescript::InitHandle",
+            GdnativeVersion::V0_10 => "InitHandle",
+        };
+    }
+
+    /// Returns the path of the module that contains the `user_data` wrappers.
+    fn user_data_path(self) -> &'static str {
+        return match self {
+            GdnativeVersion::V0_9 => "gdnative::nativescript::user_data",
+            GdnativeVersion::V0_10 => "gdnative::export::user_data",
+        };
+    }
+
+    /// Returns the `use` declaration that module files need for gdnative's macros. In 0.9 the lib file's
+    /// `#[macro_use]` already covers them.
+    fn macros_import(self) -> &'static str {
+        return match self {
+            GdnativeVersion::V0_9 => "",
+            GdnativeVersion::V0_10 => "\nuse gdnative::prelude::*;",
+        };
+    }
+
+    /// Returns the attribute that goes on the `impl` block of a class.
+    fn methods_attribute(self) -> &'static str {
+        return match self {
+            GdnativeVersion::V0_9 => "#[gdnative::methods]",
+            GdnativeVersion::V0_10 => "#[methods]",
+        };
+    }
+
+    /// Returns the attribute that exports a method to Godot.
+    fn method_attribute(self) -> &'static str {
+        return match self {
+            GdnativeVersion::V0_9 => "#[export]",
+            GdnativeVersion::V0_10 => "#[method]",
+        };
+    }
+
+    /// Returns the argument of the constructor that the owner of the class is passed in.
+    ///
+    /// # Arguments
+    ///
+    /// `base` - The Godot class that the class inherits from.
+    fn constructor_owner(self, base: &str) -> String {
+        return match self {
+            GdnativeVersion::V0_9 => format!("_owner: &{}", base),
+            GdnativeVersion::V0_10 => format!("_base: &{}", base),
+        };
+    }
+
+    /// Returns the argument of an exported method that the owner of the class is passed in.
+    ///
+    /// # Arguments
+    ///
+    /// `base` - The Godot class that the class inherits from.
+    fn method_owner(self, base: &str) -> String {
+        return match self {
+            GdnativeVersion::V0_9 => format!("_owner: &{}", base),
+            GdnativeVersion::V0_10 => format!("#[base] _base: TRef<{}>", base),
+        };
+    }
+
+    /// Returns a default value from `RESOURCE_PROPERTY_TYPES` or `GDSCRIPT_TYPES`, which are written for 0.9, as it's
+    /// written for this version.
+    ///
+    /// # Arguments
+    ///
+    /// `value` - The Rust expression for the default value.
+    fn default_value(self, value: &str) -> String {
+        return match self {
+            GdnativeVersion::V0_9 => value.to_string(),
+            GdnativeVersion::V0_10 => value
+                .replace("::zero()", "::ZERO")
+                .replace("Variant::new()", "Variant::nil()"),
+        };
+    }
+
+    /// Returns the statement that registers a signal with the class builder.
+    ///
+    /// # Arguments
+    ///
+    /// `name` - The name of the signal.
+    /// `args` - The names of the signal's arguments.
+    fn signal_registration(self, name: &str, args: &[String]) -> String {
+        return match self {
+            GdnativeVersion::V0_9 => {
+                let args = if args.is_empty() {
+                    "&[]".to_string()
+                } else {
+                    let args: Vec<String> = args
+                        .iter()
+                        .map(|a| {
+                            format!(
+                                "\t\t\t\tSignalArgument {{\n\t\t\t\t\tname: \"{}\",\n\t\t\t\t\tdefault: Variant::new(),\n\t\t\t\t\texport_info: ExportInfo::new(VariantType::Nil),\n\t\t\t\t\tusage: PropertyUsage::DEFAULT,\n\t\t\t\t}},",
+                                a
+                            )
+                        })
+                        .collect();
+                    format!("&[\n{}\n\t\t\t]", args.join("\n"))
+                };
+                format!(
+                    "\t\tbuilder.add_signal(Signal {{\n\t\t\tname: \"{}\",\n\t\t\targs: {},\n\t\t}});",
+                    name, args
+                )
+            }
+            GdnativeVersion::V0_10 => {
+                let params: String = args
+                    .iter()
+                    .map(|a| format!("\n\t\t\t.with_param_untyped(\"{}\")", a))
+                    .collect();
+                format!("\t\tbuilder.signal(\"{}\"){}\n\t\t\t.done();", name, params)
+            }
+        };
+    }
+}
+
+/// Returns the contents of the Cargo.toml file of a new library. The crate type and the dependencies are added to it
+/// afterwards.
+///
+/// # Arguments
+///
+/// `name` - The name of the crate.
+/// `edition` - The Rust edition that the crate uses.
+/// `authors` - The authors of the crate, which are left out if there aren't any.
+/// `license` - The SPDX license expression of the crate, if it has one.
+pub fn create_cargo_toml_file(
+    name: &str,
+    edition: &str,
+    authors: &[String],
+    license: Option<&str>,
+) -> String {
+    let mut package = vec![
+        format!("name = {}", toml::Value::from(name)),
+        "version = \"0.1.0\"".to_string(),
+    ];
+    if !authors.is_empty() {
+        package.push(format!("authors = {}", toml::Value::from(authors.to_vec())));
+    }
+    package.push(format!("edition = {}", toml::Value::from(edition)));
+    if let Some(license) = license {
+        package.push(format!("license = {}", toml::Value::from(license)));
+    }
+
+    return format!("[package]\n{}\n\n[dependencies]\n", package.join("\n"));
+}
+
+/// Returns the contents of the .gitignore file of a new library, which leaves out the build output.
+pub fn create_gitignore_file() -> String {
+    return "/target\n".to_string();
+}
+
+/// Returns the initial contents of the src/lib.rs file.
+///
+/// # Arguments
+///
+/// `godot_version` - The version of Godot that the library is for.
+/// `version` - The version of gdnative that the library uses if it's for Godot 3.
+pub fn create_initial_lib_file(godot_version: GodotVersion, version: GdnativeVersion) -> String {
+    return match godot_version {
+        GodotVersion::V3 => create_lib_file(&vec![], &BTreeMap::new(), false, version),
+        GodotVersion::V4 => format!(
+            r#"use godot::prelude::*;
+
+{}
+{}
+
+struct Extension;
+
+#[gdextension]
+unsafe impl ExtensionLibrary for Extension {{}}
+"#,
+            region_begin_marker(LIB_MODULES_REGION),
+            region_end_marker(LIB_MODULES_REGION)
+        ),
+    };
+}
+
+/// Returns the lines that go in the generated regions of the lib file. The first list contains the `mod` declarations
+/// and the second list contains the calls that register the classes.
 ///
 /// # Arguments
 ///
 /// `modules` - The modules that have been created.
+/// `module_configs` - The settings of modules that don't use the defaults.
 /// `is_plugin` - Indicates whether the modules are for a plugin or not.
-pub fn create_lib_file(modules: &Vec<String>, is_plugin: bool) -> String {
-    let mut mods = String::new();
-    let mut classes = String::new();
+fn create_lib_regions(
+    modules: &Vec<String>,
+    module_configs: &BTreeMap<String, ConfigModule>,
+    is_plugin: bool,
+) -> (Vec<String>, Vec<String>) {
+    let mut mods: Vec<String> = vec![];
+    let mut declared_mods: Vec<String> = vec![];
+    let mut classes: Vec<String> = vec![];
 
     for module in modules {
-        let mod_normalized = utils::format_str(module.to_string());
-        let mod_formatted = format!("\nmod {};", mod_normalized);
-        let class_formatted = if is_plugin {
-            format!(
-                "\thandle.add_tool_class::<{}::{}>();",
-                mod_normalized, module
-            )
+        let mod_path = match module_configs.get(module).and_then(|m| m.path.to_owned()) {
+            Some(v) => v,
+            None => naming::mod_name(module),
+        };
+        let class_path = if mod_path.is_empty() {
+            module.to_string()
         } else {
-            format!("\thandle.add_class::<{}::{}>();", mod_normalized, module)
+            format!("{}::{}", mod_path, module)
         };
 
-        mods.push_str(&mod_formatted.to_owned());
-        classes.push_str(&class_formatted.to_owned());
+        // Only the top level module needs to be declared and it only needs to be declared once.
+        let top_level_mod = mod_path.split("::").next().unwrap_or("").to_string();
+        if !top_level_mod.is_empty() && !declared_mods.contains(&top_level_mod) {
+            mods.push(format!("mod {};", top_level_mod));
+            declared_mods.push(top_level_mod);
+        }
 
-        let mod_index = modules
-            .iter()
-            .position(|i| i == module)
-            .expect("Unable to get position of module");
+        let add_class = if is_tool_class(module, module_configs, is_plugin) {
+            "add_tool_class"
+        } else {
+            "add_class"
+        };
 
-        if mod_index != modules.iter().count() - 1 {
-            classes.push_str("\n")
+        // Classes that Godot knows under another name, like the ones with the class prefix, are registered under it.
+        let godot_name = godot_class_name(module, module_configs);
+        if &godot_name == module {
+            classes.push(format!("handle.{}::<{}>();", add_class, class_path));
+        } else {
+            classes.push(format!(
+                "handle.{}_as::<{}>(\"{}\".to_string());",
+                add_class, class_path, godot_name
+            ));
         }
     }
-    mods.push_str("\n");
+
+    return (mods, classes);
+}
+
+/// Returns whether a module's class should be registered as a tool class. Every class in a plugin is a tool class,
+/// otherwise it depends on the module's settings.
+///
+/// # Arguments
+///
+/// `module` - The class name of the module.
+/// `module_configs` - The settings of modules that don't use the defaults.
+/// `is_plugin` - Indicates whether the modules are for a plugin or not.
+pub fn is_tool_class(
+    module: &str,
+    module_configs: &BTreeMap<String, ConfigModule>,
+    is_plugin: bool,
+) -> bool {
+    return is_plugin || module_configs.get(module).map(|m| m.tool).unwrap_or(false);
+}
+
+/// Returns the name that Godot uses for a module's class, which is the module's name unless its settings have
+/// another one.
+///
+/// # Arguments
+///
+/// `module` - The class name of the module.
+/// `module_configs` - The settings of modules that don't use the defaults.
+pub fn godot_class_name(module: &str, module_configs: &BTreeMap<String, ConfigModule>) -> String {
+    return module_configs
+        .get(module)
+        .and_then(|m| m.class_name.to_owned())
+        .unwrap_or_else(|| module.to_string());
+}
+
+/// Creates the contents for the src/lib.rs file depending on what modules are present.
+///
+/// # Arguments
+///
+/// `modules` - The modules that have been created.
+/// `module_configs` - The settings of modules that don't use the defaults.
+/// `is_plugin` - Indicates whether the modules are for a plugin or not.
+/// `version` - The version of gdnative that the library uses.
+pub fn create_lib_file(
+    modules: &Vec<String>,
+    module_configs: &BTreeMap<String, ConfigModule>,
+    is_plugin: bool,
+    version: GdnativeVersion,
+) -> String {
+    let (mods, classes) = create_lib_regions(modules, module_configs, is_plugin);
+
+    let mut mods_region = vec![region_begin_marker(LIB_MODULES_REGION)];
+    mods_region.extend(mods);
+    mods_region.push(region_end_marker(LIB_MODULES_REGION));
+
+    let mut classes_region = vec![region_begin_marker(LIB_CLASSES_REGION)];
+    classes_region.extend(classes);
+    classes_region.push(region_end_marker(LIB_CLASSES_REGION));
 
     let lib_file = format!(
-        r#"#[macro_use]
-extern crate gdnative;
+        r#"{}
+
 {}
-fn init(handle: gdnative::nativescript::InitHandle) {{
+
+fn init(handle: {}) {{
 {}
 }}
 
 godot_init!(init);"#,
-        mods, classes
+        version.lib_header(),
+        mods_region.join("\n"),
+        version.init_handle(),
+        classes_region
+            .iter()
+            .map(|line| format!("\t{}", line))
+            .collect::<Vec<String>>()
+            .join("\n")
     );
 
     return lib_file;
 }
 
+/// Updates the generated regions of an existing src/lib.rs file to match the modules that are present. Everything
+/// outside of the generated regions is left as it is.
+///
+/// Returns an error describing the problem if the lib file is missing the markers of one of the regions.
+///
+/// # Arguments
+///
+/// `lib_file` - The current contents of the lib file.
+/// `modules` - The modules that have been created.
+/// `module_configs` - The settings of modules that don't use the defaults.
+/// `is_plugin` - Indicates whether the modules are for a plugin or not.
+/// `godot_version` - The version of Godot that the library is for.
+pub fn update_lib_file(
+    lib_file: &str,
+    modules: &Vec<String>,
+    module_configs: &BTreeMap<String, ConfigModule>,
+    is_plugin: bool,
+    godot_version: GodotVersion,
+) -> Result<String, String> {
+    let (mods, classes) = create_lib_regions(modules, module_configs, is_plugin);
+
+    let mut lib_file = replace_region(lib_file, LIB_MODULES_REGION, &mods)?;
+    if lib_regions(godot_version).contains(&LIB_CLASSES_REGION) {
+        lib_file = replace_region(&lib_file, LIB_CLASSES_REGION, &classes)?;
+    }
+
+    return Ok(lib_file);
+}
+
+/// Checks that an existing src/lib.rs file has the markers of all of its generated regions.
+///
+/// Returns an error describing the problem if the lib file is missing the markers of one of the regions.
+///
+/// # Arguments
+///
+/// `lib_file` - The current contents of the lib file.
+/// `godot_version` - The version of Godot that the library is for.
+pub fn check_lib_file(lib_file: &str, godot_version: GodotVersion) -> Result<(), String> {
+    let file_lines: Vec<&str> = lib_file.split('\n').collect();
+
+    for region in lib_regions(godot_version) {
+        find_region(&file_lines, region)?;
+    }
+
+    return Ok(());
+}
+
+/// Adds the begin and end markers of the generated regions to a src/lib.rs file that doesn't have them, like the lib
+/// files of libraries created before godot_rust_helper used generated regions. The markers are put around the `mod`
+/// declarations and the class registrations of the modules and everything else in the file is left as it is. A region
+/// without any lines yet is put before the `init` function or at the start of it.
+///
+/// Returns an error describing the problem if the lines of a region aren't next to each other, if the `init` function
+/// can't be found, or if the file only has some of a region's markers.
+///
+/// # Arguments
+///
+/// `lib_file` - The current contents of the lib file.
+/// `modules` - The modules that have been created.
+/// `module_configs` - The settings of modules that don't use the defaults.
+/// `is_plugin` - Indicates whether the modules are for a plugin or not.
+pub fn add_lib_file_markers(
+    lib_file: &str,
+    modules: &Vec<String>,
+    module_configs: &BTreeMap<String, ConfigModule>,
+    is_plugin: bool,
+) -> Result<String, String> {
+    let (mods, classes) = create_lib_regions(modules, module_configs, is_plugin);
+
+    // A class can be registered as a tool class or a runtime class by hand, either way the line belongs to the region.
+    let class_lines: Vec<String> = classes
+        .iter()
+        .flat_map(|c| {
+            vec![
+                c.replace("add_tool_class", "add_class"),
+                c.replace("add_class", "add_tool_class"),
+            ]
+        })
+        .collect();
+
+    let mut file_lines: Vec<String> = lib_file.split('\n').map(|l| l.to_string()).collect();
+    let init_index = file_lines
+        .iter()
+        .position(|l| l.trim_start().starts_with("fn init("));
+
+    // The classes region is done first since it comes after the modules region so adding it doesn't move the lines
+    // of the modules region.
+    if find_region(&to_strs(&file_lines), LIB_CLASSES_REGION).is_err() {
+        let init_index =
+            init_index.ok_or("the `init` function that registers the classes can't be found")?;
+        add_region_markers(
+            &mut file_lines,
+            LIB_CLASSES_REGION,
+            &class_lines,
+            init_index + 1,
+            "\t",
+        )?;
+    }
+    if find_region(&to_strs(&file_lines), LIB_MODULES_REGION).is_err() {
+        let init_index =
+            init_index.ok_or("the `init` function that registers the classes can't be found")?;
+        add_region_markers(&mut file_lines, LIB_MODULES_REGION, &mods, init_index, "")?;
+    }
+
+    return Ok(file_lines.join("\n"));
+}
+
+/// Returns the lines of a file as string slices.
+///
+/// # Arguments
+///
+/// `file_lines` - The lines of the file.
+fn to_strs(file_lines: &[String]) -> Vec<&str> {
+    return file_lines.iter().map(|l| l.as_str()).collect();
+}
+
+/// Puts the begin and end markers of a generated region around the lines of the file that belong to it. If none of
+/// the lines are in the file then the markers are inserted at `empty_index`.
+///
+/// # Arguments
+///
+/// `file_lines` - The lines of the file.
+/// `region` - The name of the region.
+/// `region_lines` - The lines, without indentation, that belong to the region.
+/// `empty_index` - The index of the line to put the markers before when the region doesn't have any lines.
+/// `empty_indent` - The indentation of the markers when the region doesn't have any lines.
+fn add_region_markers(
+    file_lines: &mut Vec<String>,
+    region: &str,
+    region_lines: &[String],
+    empty_index: usize,
+    empty_indent: &str,
+) -> Result<(), String> {
+    let begin_marker = region_begin_marker(region);
+    let end_marker = region_end_marker(region);
+    if file_lines
+        .iter()
+        .any(|l| l.trim() == begin_marker || l.trim() == end_marker)
+    {
+        return Err(format!(
+            "the `{}` and `{}` markers aren't both in the file in that order",
+            begin_marker, end_marker
+        ));
+    }
+
+    let indexes: Vec<usize> = file_lines
+        .iter()
+        .enumerate()
+        .filter(|(_i, l)| region_lines.iter().any(|r| r == l.trim()))
+        .map(|(i, _l)| i)
+        .collect();
+
+    let (first, last) = match (indexes.first(), indexes.last()) {
+        (Some(first), Some(last)) => (*first, *last),
+        _ => {
+            // Markers at the top level of the file are kept apart from the item that follows them.
+            if empty_indent.is_empty() {
+                file_lines.insert(empty_index, String::new());
+            }
+            file_lines.insert(empty_index, format!("{}{}", empty_indent, end_marker));
+            file_lines.insert(empty_index, format!("{}{}", empty_indent, begin_marker));
+            return Ok(());
+        }
+    };
+    if last - first + 1 != indexes.len() {
+        return Err(format!(
+            "the `{}` marker is missing and the lines that belong in it aren't next to each other",
+            begin_marker
+        ));
+    }
+
+    let first_line = &file_lines[first];
+    let indent = first_line[..first_line.len() - first_line.trim_start().len()].to_string();
+    file_lines.insert(last + 1, format!("{}{}", indent, end_marker));
+    file_lines.insert(first, format!("{}{}", indent, begin_marker));
+
+    return Ok(());
+}
+
+/// Returns the indexes of the lines that contain the begin and end markers of a generated region.
+///
+/// # Arguments
+///
+/// `file_lines` - The lines of the file that contains the region.
+/// `region` - The name of the region.
+fn find_region(file_lines: &[&str], region: &str) -> Result<(usize, usize), String> {
+    let begin_marker = region_begin_marker(region);
+    let end_marker = region_end_marker(region);
+
+    let begin_index = match file_lines.iter().position(|l| l.trim() == begin_marker) {
+        Some(v) => v,
+        None => return Err(format!("the `{}` marker is missing", begin_marker)),
+    };
+    let end_index = match file_lines
+        .iter()
+        .skip(begin_index)
+        .position(|l| l.trim() == end_marker)
+    {
+        Some(v) => begin_index + v,
+        None => {
+            return Err(format!(
+                "the `{}` marker is missing after `{}`",
+                end_marker, begin_marker
+            ))
+        }
+    };
+
+    return Ok((begin_index, end_index));
+}
+
+/// Replaces the lines between the begin and end markers of a generated region. The new lines are indented to match
+/// the begin marker.
+///
+/// # Arguments
+///
+/// `file` - The contents of the file that contains the region.
+/// `region` - The name of the region.
+/// `lines` - The lines to put in the region.
+pub fn replace_region(file: &str, region: &str, lines: &[String]) -> Result<String, String> {
+    let file_lines: Vec<&str> = file.split('\n').collect();
+    let (begin_index, end_index) = find_region(&file_lines, region)?;
+
+    let begin_line = file_lines[begin_index];
+    let indent = &begin_line[..begin_line.len() - begin_line.trim_start().len()];
+
+    let mut new_lines: Vec<String> = file_lines[..=begin_index]
+        .iter()
+        .map(|l| l.to_string())
+        .collect();
+    new_lines.extend(lines.iter().map(|l| format!("{}{}", indent, l)));
+    new_lines.extend(file_lines[end_index..].iter().map(|l| l.to_string()));
+
+    return Ok(new_lines.join("\n"));
+}
+
+/// The Godot class that modules created with `create` inherit from.
+pub const DEFAULT_BASE_CLASS: &str = "Node";
+
+/// The `user_data` wrappers that a module's struct can be stored in, as they are passed to `create --user-data`.
+pub const USER_DATA_WRAPPERS: [&str; 4] = ["local-cell", "mutex", "rw-lock", "arc"];
+
+/// The `user_data` wrapper that gdnative stores an instance of a module's struct in.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum UserData {
+    /// Only accessible from the thread that created it. This is the default.
+    #[default]
+    LocalCell,
+    /// Accessible from any thread with one caller at a time.
+    Mutex,
+    /// Accessible from any thread with any number of readers or one writer at a time.
+    RwLock,
+    /// Accessible from any thread at the same time but only ever through a shared reference.
+    Arc,
+}
+
+impl UserData {
+    /// Returns the type from `gdnative::nativescript::user_data` that wraps the struct.
+    fn wrapper_type(self) -> &'static str {
+        return match self {
+            UserData::LocalCell => "LocalCellData",
+            UserData::Mutex => "MutexData",
+            UserData::RwLock => "RwLockData",
+            UserData::Arc => "ArcData",
+        };
+    }
+
+    /// Returns the receiver that the generated methods should use. A `MutexData` lock is exclusive anyway so its
+    /// methods can mutate the struct directly while the other wrappers keep to shared references so that `ArcData`
+    /// compiles and `RwLockData` only takes a read lock.
+    fn method_receiver(self) -> &'static str {
+        return match self {
+            UserData::Mutex => "&mut self",
+            _ => "&self",
+        };
+    }
+}
+
+impl std::str::FromStr for UserData {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        return match value {
+            "local-cell" => Ok(UserData::LocalCell),
+            "mutex" => Ok(UserData::Mutex),
+            "rw-lock" => Ok(UserData::RwLock),
+            "arc" => Ok(UserData::Arc),
+            _ => Err(format!(
+                "{} is not a user_data wrapper, use one of {}",
+                value,
+                USER_DATA_WRAPPERS.join(", ")
+            )),
+        };
+    }
+}
+
 /// Creates the default contents of the module file for any module created.
 ///
 /// # Arguments
 ///
 /// `name` - The name of the module.
-pub fn create_mod_file(name: &str) -> String {
+/// `user_data` - The `user_data` wrapper that the module's struct is stored in.
+/// `version` - The version of gdnative that the library uses.
+pub fn create_mod_file(name: &str, user_data: UserData, version: GdnativeVersion) -> String {
     let init_string = format!(
-        r#"fn new(_owner: &Node) -> Self {{
-{}
+        r#"fn new({}) -> Self {{
+		{}
 {}"#,
-        format!("\t\t{}", name),
+        version.constructor_owner(DEFAULT_BASE_CLASS),
+        name,
         "\t}"
     );
 
     let ready_string = format!(
-        r#"#[export]
-{}
-{}
-{}"#,
-        "\tfn _ready(&self, _owner: &Node) {",
-        format!("\t\t{}", "godot_print!(\"hello, world.\");"),
-        "\t}"
+        r#"{}
+	fn _ready({}, {}) {{
+		godot_print!("hello, world.");
+	}}"#,
+        version.method_attribute(),
+        user_data.method_receiver(),
+        version.method_owner(DEFAULT_BASE_CLASS)
     );
 
+    // ArcData never hands out a mutable reference so fields that change have to handle that themselves.
+    let struct_note = if user_data == UserData::Arc {
+        "// ArcData only gives out shared references, so use a Mutex, RwLock, or atomic for fields that change.\n"
+    } else {
+        ""
+    };
+
     let mod_file = format!(
         r#"use gdnative::api::Node;
-use gdnative::nativescript::user_data;
+use {};{}
 
 #[derive(NativeClass)]
 #[inherit(Node)]
-#[user_data(user_data::LocalCellData<{}>)]
-pub struct {};
+#[user_data(user_data::{}<{}>)]
+{}pub struct {};
 
-#[gdnative::methods]
-impl {} {{
 {}
+impl {} {{
+	{}
+
+	{}
+}}
+"#,
+        version.user_data_path(),
+        version.macros_import(),
+        user_data.wrapper_type(),
+        name,
+        struct_note,
+        name,
+        version.methods_attribute(),
+        name,
+        init_string,
+        ready_string
+    );
+
+    return mod_file;
+}
+
+/// Creates the contents of the module file for a module of a Godot 4 library, which uses gdext instead of gdnative.
+///
+/// # Arguments
+///
+/// `name` - The name of the module.
+/// `godot_name` - The name that Godot uses for the class, which differs from the name when it has the class prefix.
+/// `base` - The Godot class that the module inherits from.
+/// `tool` - Indicates whether the class should also run in the editor.
+pub fn create_gdext_mod_file(name: &str, godot_name: &str, base: &str, tool: bool) -> String {
+    let mut class_options = if tool {
+        format!("tool, base={}", base)
+    } else {
+        format!("base={}", base)
+    };
+    if godot_name != name {
+        class_options.push_str(&format!(", rename={}", godot_name));
+    }
+
+    return format!(
+        r#"use godot::classes::{{I{}, {}}};
+use godot::prelude::*;
+
+#[derive(GodotClass)]
+#[class({})]
+pub struct {} {{
+	base: Base<{}>,
+}}
+
+#[godot_api]
+impl I{} for {} {{
+	fn init(base: Base<{}>) -> Self {{
+		Self {{ base }}
+	}}
+
+	fn ready(&mut self) {{
+		godot_print!("hello, world.");
+	}}
+}}
+"#,
+        base, base, class_options, name, base, base, name, base
+    );
+}
+
+/// The property types that resources created with `create-resource` can export. Each entry contains the name of the
+/// type as it's passed to `--property`, the Rust type, the Rust expression for its default value, and the value that
+/// is written to the sample .tres file.
+const RESOURCE_PROPERTY_TYPES: [(&str, &str, &str, &str); 7] = [
+    ("int", "i64", "0", "0"),
+    ("float", "f64", "0.0", "0.0"),
+    ("bool", "bool", "false", "false"),
+    ("String", "String", "String::new()", "\"\""),
+    ("Vector2", "Vector2", "Vector2::zero()", "Vector2( 0, 0 )"),
+    (
+        "Vector3",
+        "Vector3",
+        "Vector3::zero()",
+        "Vector3( 0, 0, 0 )",
+    ),
+    (
+        "Color",
+        "Color",
+        "Color::rgba(0.0, 0.0, 0.0, 1.0)",
+        "Color( 0, 0, 0, 1 )",
+    ),
+];
+
+/// A property that is exported by a resource created with `create-resource`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourceProperty {
+    /// The name of the property.
+    pub name: String,
+    /// The name of the property's type as it's passed to `--property`, e.g. `int` or `Vector2`.
+    pub type_name: String,
+}
+
+impl ResourceProperty {
+    /// Returns the Rust type, the Rust default value, and the .tres default value of the property's type.
+    fn type_info(&self) -> (&'static str, &'static str, &'static str) {
+        let (_, rust_type, rust_default, tres_default) = RESOURCE_PROPERTY_TYPES
+            .iter()
+            .find(|t| t.0 == self.type_name)
+            .expect("Unknown resource property type");
+
+        return (rust_type, rust_default, tres_default);
+    }
+}
+
+impl std::str::FromStr for ResourceProperty {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (name, type_name) = match value.find(':') {
+            Some(i) => (value[..i].trim(), value[i + 1..].trim()),
+            None => return Err(format!("{} should be in the form of name:type", value)),
+        };
+
+        let is_valid_name = name
+            .chars()
+            .next()
+            .map(|c| c.is_ascii_lowercase() || c == '_')
+            .unwrap_or(false)
+            && name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+        if !is_valid_name || naming::is_rust_keyword(name) {
+            return Err(format!(
+                "{} is not a valid property name, use a snake_case name",
+                name
+            ));
+        }
+
+        // The Rust name of a type can be used as well, e.g. `i64` instead of `int`.
+        let property_type = RESOURCE_PROPERTY_TYPES
+            .iter()
+            .find(|t| t.0 == type_name || t.1 == type_name);
+        return match property_type {
+            Some(t) => Ok(ResourceProperty {
+                name: name.to_string(),
+                type_name: t.0.to_string(),
+            }),
+            None => Err(format!(
+                "{} is not a supported property type, use one of {}",
+                type_name,
+                RESOURCE_PROPERTY_TYPES
+                    .iter()
+                    .map(|t| t.0)
+                    .collect::<Vec<&str>>()
+                    .join(", ")
+            )),
+        };
+    }
+}
+
+/// Creates the contents of the module file for a module that inherits from `Resource` and exports properties.
+///
+/// # Arguments
+///
+/// `name` - The name of the module.
+/// `properties` - The properties that the resource exports.
+/// `version` - The version of gdnative that the library uses.
+pub fn create_resource_mod_file(
+    name: &str,
+    properties: &[ResourceProperty],
+    version: GdnativeVersion,
+) -> String {
+    let mut core_types: Vec<&str> = vec![];
+    for property in properties {
+        let (rust_type, _, _) = property.type_info();
+        if ["Vector2", "Vector3", "Color"].contains(&rust_type) && !core_types.contains(&rust_type)
+        {
+            core_types.push(rust_type);
+        }
+    }
+    core_types.sort_unstable();
+
+    let mut imports = vec!["use gdnative::api::Resource;".to_string()];
+    if core_types.len() == 1 {
+        imports.push(format!("use gdnative::core_types::{};", core_types[0]));
+    } else if !core_types.is_empty() {
+        imports.push(format!(
+            "use gdnative::core_types::{{{}}};",
+            core_types.join(", ")
+        ));
+    }
+    imports.push(format!(
+        "use {};{}",
+        version.user_data_path(),
+        version.macros_import()
+    ));
+
+    let (struct_body, init_body) = if properties.is_empty() {
+        (";".to_string(), name.to_string())
+    } else {
+        let fields: Vec<String> = properties
+            .iter()
+            .map(|p| format!("\t#[property]\n\tpub {}: {},", p.name, p.type_info().0))
+            .collect();
+        let defaults: Vec<String> = properties
+            .iter()
+            .map(|p| {
+                format!(
+                    "\t\t\t{}: {},",
+                    p.name,
+                    version.default_value(p.type_info().1)
+                )
+            })
+            .collect();
+
+        (
+            format!(" {{\n{}\n}}", fields.join("\n")),
+            format!("{} {{\n{}\n\t\t}}", name, defaults.join("\n")),
+        )
+    };
+
+    let mod_file = format!(
+        r#"{}
+
+#[derive(NativeClass)]
+#[inherit(Resource)]
+#[user_data(user_data::LocalCellData<{}>)]
+pub struct {}{}
 
 {}
+impl {} {{
+	fn new({}) -> Self {{
+		{}
+	}}
 }}
 "#,
+        imports.join("\n"),
         name,
         name,
+        struct_body,
+        version.methods_attribute(),
         name,
-        format!("\t{}", init_string),
-        format!("\t{}", ready_string)
+        version.constructor_owner("Resource"),
+        init_body
     );
 
     return mod_file;
 }
 
+/// The GDScript types that a module created with `create --from-gd` can use besides the ones in
+/// `RESOURCE_PROPERTY_TYPES`. Each entry contains the GDScript type, the Rust type, and the Rust expression for its
+/// default value. Any other type becomes a `Variant`.
+const GDSCRIPT_TYPES: [(&str, &str, &str); 3] = [
+    ("Array", "VariantArray", "VariantArray::new_shared()"),
+    ("Dictionary", "Dictionary", "Dictionary::new_shared()"),
+    ("NodePath", "NodePath", "NodePath::default()"),
+];
+
+/// Returns the Rust type and the Rust default value of a GDScript type.
+///
+/// # Arguments
+///
+/// `gd_type` - The GDScript type, e.g. `float`.
+fn gdscript_type(gd_type: Option<&str>) -> (&'static str, &'static str) {
+    let gd_type = match gd_type {
+        Some(v) => v,
+        None => return ("Variant", "Variant::new()"),
+    };
+
+    if let Some((_, rust_type, rust_default, _)) =
+        RESOURCE_PROPERTY_TYPES.iter().find(|t| t.0 == gd_type)
+    {
+        return (rust_type, rust_default);
+    }
+    if let Some((_, rust_type, rust_default)) = GDSCRIPT_TYPES.iter().find(|t| t.0 == gd_type) {
+        return (rust_type, rust_default);
+    }
+
+    return ("Variant", "Variant::new()");
+}
+
+/// Returns the GDScript type of a default value written as a literal, e.g. `float` for `200.0`.
+///
+/// # Arguments
+///
+/// `value` - The default value as it's written in GDScript.
+fn gdscript_literal_type(value: &str) -> Option<&'static str> {
+    if value.parse::<i64>().is_ok() {
+        return Some("int");
+    }
+    if value.parse::<f64>().is_ok() {
+        return Some("float");
+    }
+    if value == "true" || value == "false" {
+        return Some("bool");
+    }
+    if value.starts_with('"') || value.starts_with('\'') {
+        return Some("String");
+    }
+
+    return ["Vector2", "Vector3", "Color"]
+        .iter()
+        .find(|t| value.starts_with(&format!("{}(", t)))
+        .copied();
+}
+
+/// Returns the Rust expression for a GDScript default value if it's a simple literal of the given type.
+///
+/// # Arguments
+///
+/// `gd_type` - The GDScript type of the value.
+/// `value` - The default value as it's written in GDScript.
+fn gdscript_value(gd_type: &str, value: &str) -> Option<String> {
+    let float = |v: &str| v.trim().parse::<f64>().ok().map(|f| format!("{:?}", f));
+
+    return match gd_type {
+        "int" => value.parse::<i64>().ok().map(|v| v.to_string()),
+        "float" => float(value),
+        "bool" if value == "true" || value == "false" => Some(value.to_string()),
+        "String" => {
+            let quote = value.chars().next()?;
+            let inner = value.strip_prefix(quote)?.strip_suffix(quote)?;
+            if (quote != '"' && quote != '\'') || inner.contains('"') {
+                return None;
+            }
+            Some(format!("String::from(\"{}\")", inner))
+        }
+        "Vector2" | "Vector3" | "Color" => {
+            let args = value
+                .strip_prefix(gd_type)?
+                .trim()
+                .strip_prefix('(')?
+                .strip_suffix(')')?
+                .split(',')
+                .map(float)
+                .collect::<Option<Vec<String>>>()?;
+            let constructor = match (gd_type, args.len()) {
+                ("Vector2", 2) | ("Vector3", 3) => "new",
+                ("Color", 3) => "rgb",
+                ("Color", 4) => "rgba",
+                _ => return None,
+            };
+            Some(format!("{}::{}({})", gd_type, constructor, args.join(", ")))
+        }
+        _ => None,
+    };
+}
+
+/// Returns a name that can be used as a Rust identifier, adding an underscore to the end of Rust keywords.
+///
+/// # Arguments
+///
+/// `name` - The name from the GDScript file.
+/// `notes` - The notes to add to if the name had to be changed.
+fn gdscript_identifier(name: &str, notes: &mut Vec<String>) -> String {
+    if naming::is_rust_keyword(name) {
+        notes.push(format!(
+            "{} is a Rust keyword so it was renamed to {}_",
+            name, name
+        ));
+        return format!("{}_", name);
+    }
+
+    return name.to_string();
+}
+
+/// Returns the lines of a GDScript function's body as comments inside of a method.
+///
+/// # Arguments
+///
+/// `body` - The lines of the body.
+fn gdscript_comment(body: &[String]) -> Vec<String> {
+    return body
+        .iter()
+        .map(|l| format!("\t\t// {}", l).trim_end().to_string())
+        .collect();
+}
+
+/// Creates the contents of the module file for a module that is converted from a GDScript file. The exported
+/// variables become properties, the signals are registered, and each function becomes an `#[export]` method that
+/// keeps the original body as a comment so that it can be ported by hand. The notes about anything that couldn't be
+/// converted exactly are returned along with the contents.
+///
+/// # Arguments
+///
+/// `name` - The name of the module.
+/// `base` - The Godot class that the module inherits from.
+/// `script` - The parsed GDScript file.
+/// `user_data` - The `user_data` wrapper that the module's struct is stored in.
+/// `version` - The version of gdnative that the library uses.
+pub fn create_mod_file_from_gd(
+    name: &str,
+    base: &str,
+    script: &GdScript,
+    user_data: UserData,
+    version: GdnativeVersion,
+) -> (String, Vec<String>) {
+    let mut notes = vec![];
+
+    let mut fields = vec![];
+    let mut defaults = vec![];
+    for export in &script.exports {
+        let gd_type = export
+            .type_hint
+            .as_deref()
+            .or_else(|| export.default.as_deref().and_then(gdscript_literal_type));
+        let (rust_type, rust_default) = gdscript_type(gd_type);
+        let rust_default = version.default_value(rust_default);
+        let field_name = gdscript_identifier(&export.name, &mut notes);
+
+        // Defaults that aren't simple literals are kept as a comment after the type's default value.
+        let value = match &export.default {
+            Some(default) => match gd_type.and_then(|t| gdscript_value(t, default)) {
+                Some(v) => format!("{},", v),
+                None => format!("{}, // {}", rust_default, default),
+            },
+            None => format!("{},", rust_default),
+        };
+
+        fields.push(format!(
+            "\t#[property]\n\tpub {}: {},",
+            field_name, rust_type
+        ));
+        defaults.push(format!("\t\t\t{}: {}", field_name, value));
+    }
+
+    let struct_body = if fields.is_empty() {
+        ";".to_string()
+    } else {
+        format!(" {{\n{}\n}}", fields.join("\n"))
+    };
+    let init_struct = if defaults.is_empty() {
+        name.to_string()
+    } else {
+        format!("{} {{\n{}\n\t\t}}", name, defaults.join("\n"))
+    };
+
+    // The body of `_init` goes in the constructor.
+    let init_body = script
+        .functions
+        .iter()
+        .find(|f| f.name == "_init")
+        .map(|f| {
+            gdscript_comment(&f.body)
+                .iter()
+                .map(|l| format!("{}\n", l))
+                .collect::<String>()
+        })
+        .unwrap_or_default();
+
+    let mut methods = vec![format!(
+        "\tfn new({}) -> Self {{\n{}\t\t{}\n\t}}",
+        version.constructor_owner(base),
+        init_body,
+        init_struct
+    )];
+
+    if !script.signals.is_empty() {
+        let signals: Vec<String> = script
+            .signals
+            .iter()
+            .map(|s| version.signal_registration(&s.name, &s.args))
+            .collect();
+
+        methods.push(format!(
+            "\tfn register_signals(builder: &ClassBuilder<Self>) {{\n{}\n\t}}",
+            signals.join("\n")
+        ));
+    }
+
+    for function in script.functions.iter().filter(|f| f.name != "_init") {
+        let mut args = vec![
+            user_data.method_receiver().to_string(),
+            version.method_owner(base),
+        ];
+        for arg in &function.args {
+            let gd_type = arg
+                .type_hint
+                .as_deref()
+                .or_else(|| arg.default.as_deref().and_then(gdscript_literal_type));
+            args.push(format!(
+                "_{}: {}",
+                arg.name.trim_start_matches('_'),
+                gdscript_type(gd_type).0
+            ));
+            if arg.default.is_some() {
+                notes.push(format!(
+                    "the default value of the {} argument of {} can't be kept",
+                    arg.name, function.name
+                ));
+            }
+        }
+
+        let return_type = match function.return_type.as_deref() {
+            None | Some("void") => String::new(),
+            Some(r) => format!(" -> {}", gdscript_type(Some(r)).0),
+        };
+
+        let mut body = gdscript_comment(&function.body);
+        if !return_type.is_empty() {
+            body.push("\t\tDefault::default()".to_string());
+        }
+
+        methods.push(format!(
+            "\t{}\n\tfn {}({}){} {{\n{}\n\t}}",
+            version.method_attribute(),
+            gdscript_identifier(&function.name, &mut notes),
+            args.join(", "),
+            return_type,
+            body.join("\n")
+        ));
+    }
+
+    for skipped in &script.skipped {
+        notes.push(format!("`{}` wasn't converted", skipped));
+    }
+
+    let register_with = if script.signals.is_empty() {
+        ""
+    } else {
+        "#[register_with(Self::register_signals)]\n"
+    };
+
+    let mod_file = format!(
+        r#"use gdnative::api::{};
+use {};
+use gdnative::prelude::*;
+
+#[derive(NativeClass)]
+#[inherit({})]
+#[user_data(user_data::{}<{}>)]
+{}pub struct {}{}
+
+{}
+impl {} {{
+{}
+}}
+"#,
+        base,
+        version.user_data_path(),
+        base,
+        user_data.wrapper_type(),
+        name,
+        register_with,
+        name,
+        struct_body,
+        version.methods_attribute(),
+        name,
+        methods.join("\n\n")
+    );
+
+    return (mod_file, notes);
+}
+
+/// Creates the contents of a .tres file that uses a resource's script and has the default value of each of its
+/// properties.
+///
+/// # Arguments
+///
+/// `gdns_res_path` - The `res://` path of the resource's .gdns file.
+/// `properties` - The properties that the resource exports.
+pub fn create_tres_file(gdns_res_path: &str, properties: &[ResourceProperty]) -> String {
+    let mut tres_string = format!(
+        r#"[gd_resource type="Resource" load_steps=2 format=2]
+
+[ext_resource path="{}" type="Script" id=1]
+
+[resource]
+script = ExtResource( 1 )
+"#,
+        gdns_res_path
+    );
+
+    for property in properties {
+        tres_string.push_str(&format!("{} = {}\n", property.name, property.type_info().2));
+    }
+
+    return tres_string;
+}
+
+/// Creates the contents of a scene whose only node uses a module's script.
+///
+/// # Arguments
+///
+/// `gdns_res_path` - The `res://` path of the module's .gdns file.
+/// `node_name` - The name of the scene's root node.
+/// `node_type` - The Godot class of the root node, which should be the class that the module inherits from.
+pub fn create_tscn_file(gdns_res_path: &str, node_name: &str, node_type: &str) -> String {
+    return format!(
+        r#"[gd_scene load_steps=2 format=2]
+
+[ext_resource path="{}" type="Script" id=1]
+
+[node name="{}" type="{}"]
+script = ExtResource( 1 )
+"#,
+        gdns_res_path, node_name, node_type
+    );
+}
+
+/// Creates the contents of a Godot 4 scene whose only node is an instance of a module's class. Classes registered
+/// through GDExtension are used as node types directly so the scene doesn't need a script.
+///
+/// # Arguments
+///
+/// `node_name` - The name of the scene's root node.
+/// `class_name` - The Godot class name of the module.
+pub fn create_gdext_tscn_file(node_name: &str, class_name: &str) -> String {
+    return format!(
+        r#"[gd_scene format=3]
+
+[node name="{}" type="{}"]
+"#,
+        node_name, class_name
+    );
+}
+
+/// Returns the contents of the gdextension file that Godot 4 loads the library with.
+///
+/// # Arguments
+///
+/// `name` - The name of the library.
+/// `output_path` - The path where the compiled files are being output relative to the Godot project.
+/// `targets` - The build targets of the library.
+pub fn create_gdextension_file(
+    name: &str,
+    output_path: &PathBuf,
+    targets: &[&str],
+) -> Result<String, String> {
+    let mut output_path_str = output_path.to_slash().unwrap_or_default().to_string();
+    if !output_path_str.ends_with('/') && !output_path_str.is_empty() {
+        output_path_str.push('/');
+    }
+
+    // Cargo replaces the dashes in the name of the library with underscores.
+    let file_name = name.replace('-', "_");
+
+    let mut libraries = vec![];
+    for &t in targets {
+        let (platforms, library) = match t {
+            "windows" => (
+                ["windows.debug.x86_64", "windows.release.x86_64"],
+                format!("{}.dll", file_name),
+            ),
+            "linux" => (
+                ["linux.debug.x86_64", "linux.release.x86_64"],
+                format!("lib{}.so", file_name),
+            ),
+            "osx" => (
+                ["macos.debug", "macos.release"],
+                format!("lib{}.dylib", file_name),
+            ),
+            _ => return Err(format!("An invalid target was specified: {}", t)),
+        };
+
+        for platform in &platforms {
+            libraries.push(format!(
+                "{} = \"res://{}{}\"",
+                platform, output_path_str, library
+            ));
+        }
+    }
+
+    return Ok(format!(
+        r#"[configuration]
+
+entry_symbol = "gdext_rust_init"
+compatibility_minimum = 4.1
+reloadable = true
+
+[libraries]
+
+{}
+"#,
+        libraries.join("\n")
+    ));
+}
+
 /// Returns the contents of the gdnlib file for the library.
 ///
 /// # Arguments
@@ -124,7 +1390,11 @@ impl {} {{
 /// `name` - The name of the library.
 /// `output_path` - The path where the compiled files are being output.
 /// `targets` - The build targets of the library.
-pub fn create_gdnlib_file(name: &str, output_path: &PathBuf, targets: &[&str]) -> String {
+pub fn create_gdnlib_file(
+    name: &str,
+    output_path: &PathBuf,
+    targets: &[&str],
+) -> Result<String, String> {
     let mut gdnlib_vec: Vec<Cow<str>> = vec![
         "[entry]".into(),
         "".into(),
@@ -142,7 +1412,7 @@ pub fn create_gdnlib_file(name: &str, output_path: &PathBuf, targets: &[&str]) -
     ];
 
     let entry_insert_point = 2;
-    let mut dep_insert_point = 6;
+    let dep_insert_start = 6;
 
     let mut output_path_str = output_path
         .to_owned()
@@ -152,12 +1422,13 @@ pub fn create_gdnlib_file(name: &str, output_path: &PathBuf, targets: &[&str]) -
         .replace("\\", "/");
 
     if !output_path_str.ends_with("/") && !output_path_str.is_empty() {
-        output_path_str.push_str("/");
+        output_path_str.push('/');
     }
 
     let mut o_path = PathBuf::from(output_path);
 
-    for &t in targets {
+    for (index, &t) in targets.iter().enumerate() {
+        let dep_insert_point = dep_insert_start + index;
         match t {
             "windows" => {
                 let f_name = format!("{}.dll", name);
@@ -187,32 +1458,57 @@ pub fn create_gdnlib_file(name: &str, output_path: &PathBuf, targets: &[&str]) -
                 gdnlib_vec.insert(entry_insert_point, file_name);
                 gdnlib_vec.insert(dep_insert_point, dep_entry);
             }
-            _ => unimplemented!(),
+            _ => return Err(format!("An invalid target was specified: {}", t)),
         }
-        dep_insert_point += 1;
     }
 
-    return gdnlib_vec.join("\n");
+    return Ok(gdnlib_vec.join("\n"));
+}
+
+/// Returns the targets and the paths, relative to the Godot project, of the library's dynamic libraries that a
+/// gdnlib or gdextension file loads.
+///
+/// # Arguments
+///
+/// `library_file` - The contents of the gdnlib or gdextension file.
+/// `name` - The name of the library.
+pub fn dynamic_library_paths(library_file: &str, name: &str) -> Vec<(String, String)> {
+    let file_names = [
+        ("windows", format!("{}.dll", name)),
+        ("linux", format!("lib{}.so", name)),
+        ("osx", format!("lib{}.dylib", name)),
+    ];
+
+    let mut paths = vec![];
+    for path in library_file
+        .split('"')
+        .filter_map(|s| s.strip_prefix("res://"))
+    {
+        let file_name = path.rsplit('/').next().unwrap_or_default();
+        if let Some((target, _file_name)) = file_names.iter().find(|(_t, f)| f == file_name) {
+            paths.push((target.to_string(), path.to_string()));
+        }
+    }
+
+    return paths;
 }
 
 /// Returns the contents of a class' .gdns file.
 ///
 /// # Arugments
 ///
-/// `lib_name` - The name of the library.
 /// `class_name` - The name of the class.
-/// `gdnlib_path` - The path to the gdnlib file.
-pub fn create_gdns_file(lib_name: &str, class_name: &str, gdnlib_path: &PathBuf) -> String {
-    let gdnlib_path_owned = gdnlib_path.to_owned();
-    let gdnlib_os_str = gdnlib_path_owned.into_os_string();
-    let gdnlib_path_str = gdnlib_os_str
-        .to_str()
-        .expect("Unable to convert gdnlib path to str");
-
-    let gdns_string = format!(
+/// `gdnlib_res_path` - The `res://` path of the library's gdnlib file.
+/// `module_config` - The settings of the module, which can make the class a named script class with an icon.
+pub fn create_gdns_file(
+    class_name: &str,
+    gdnlib_res_path: &str,
+    module_config: Option<&ConfigModule>,
+) -> String {
+    let mut gdns_string = format!(
         r#"[gd_resource type="NativeScript" load_steps=2 format=2]
 
-[ext_resource path="res://{}/{}.gdnlib" type="GDNativeLibrary" id=1]
+[ext_resource path="{}" type="GDNativeLibrary" id=1]
 
 [resource]
 
@@ -220,11 +1516,16 @@ resource_name = "{}"
 class_name = "{}"
 library = ExtResource( 1 )
 "#,
-        gdnlib_path_str.replace("\\", "/"),
-        lib_name,
-        class_name,
-        class_name,
+        gdnlib_res_path, class_name, class_name,
     );
 
+    // Named script classes show up in Godot's "Create New Node" dialog with their icon.
+    if let Some(module_config) = module_config.filter(|m| m.named) {
+        gdns_string.push_str(&format!("script_class_name = \"{}\"\n", class_name));
+        if let Some(icon) = &module_config.icon {
+            gdns_string.push_str(&format!("script_class_icon_path = \"{}\"\n", icon));
+        }
+    }
+
     return gdns_string;
 }
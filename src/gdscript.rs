@@ -0,0 +1,383 @@
+#![allow(clippy::needless_return)]
+
+/// The parts of a GDScript file that can be carried over to a module.
+#[derive(Debug, Default)]
+pub struct GdScript {
+    /// Whether the script starts with the `tool` keyword.
+    pub tool: bool,
+    /// The class or script path after `extends`, if there is one.
+    pub extends: Option<String>,
+    /// The name after `class_name`, if there is one.
+    pub class_name: Option<String>,
+    /// The variables declared with `export`.
+    pub exports: Vec<GdVariable>,
+    /// The signals declared with `signal`.
+    pub signals: Vec<GdSignal>,
+    /// The functions declared with `func`.
+    pub functions: Vec<GdFunction>,
+    /// The top level statements that couldn't be carried over, e.g. `const` and `enum` declarations.
+    pub skipped: Vec<String>,
+}
+
+/// An exported variable or a function argument.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GdVariable {
+    /// The name of the variable.
+    pub name: String,
+    /// The type from the export hint or the type annotation, e.g. `int`.
+    pub type_hint: Option<String>,
+    /// The default value as it's written in GDScript.
+    pub default: Option<String>,
+}
+
+/// A signal and the names of its arguments.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GdSignal {
+    /// The name of the signal.
+    pub name: String,
+    /// The names of the signal's arguments.
+    pub args: Vec<String>,
+}
+
+/// A function along with its body.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GdFunction {
+    /// The name of the function.
+    pub name: String,
+    /// The arguments of the function.
+    pub args: Vec<GdVariable>,
+    /// The return type if it's annotated, e.g. `int` for `-> int`.
+    pub return_type: Option<String>,
+    /// The lines of the function's body with the function's indentation removed.
+    pub body: Vec<String>,
+}
+
+/// Parses the declarations of a GDScript file. Only the top level of the script is looked at, inner classes and
+/// anything else that can't be carried over are listed in `skipped`.
+///
+/// # Arguments
+///
+/// `contents` - The contents of the GDScript file.
+pub fn parse(contents: &str) -> Result<GdScript, String> {
+    let lines: Vec<&str> = contents.lines().collect();
+    let mut script = GdScript::default();
+    let mut index = 0;
+
+    while index < lines.len() {
+        let line = strip_comment(lines[index]).trim_end();
+        index += 1;
+
+        // Only unindented statements are declarations, indented lines belong to a function or a class.
+        if line.trim().is_empty() || line.starts_with(char::is_whitespace) {
+            continue;
+        }
+
+        let (keyword, rest) = split_keyword(line);
+        match keyword {
+            "tool" => script.tool = true,
+            "extends" => script.extends = Some(rest.trim_matches('"').to_string()),
+            "class_name" => {
+                // The class name can be followed by the path to an icon.
+                let name = rest.split(',').next().unwrap_or("").trim();
+                script.class_name = Some(name.to_string());
+            }
+            "export" => match parse_export(rest) {
+                Some(v) => script.exports.push(v),
+                None => return Err(format!("unable to parse `{}`", line)),
+            },
+            "signal" => {
+                let (name, args) = match rest.find('(') {
+                    Some(i) => (
+                        &rest[..i],
+                        split_args(&rest[i + 1..rest.rfind(')').unwrap_or(rest.len())]),
+                    ),
+                    None => (rest, vec![]),
+                };
+                script.signals.push(GdSignal {
+                    name: name.trim().to_string(),
+                    args: args
+                        .iter()
+                        .map(|a| a.split(':').next().unwrap_or("").trim().to_string())
+                        .collect(),
+                });
+            }
+            "func" | "static" | "remote" | "master" | "puppet" | "remotesync" | "mastersync"
+            | "puppetsync" => {
+                // Signatures can span multiple lines so they continue until the parentheses are closed.
+                let mut signature = line.to_string();
+                while signature.matches('(').count() > signature.matches(')').count()
+                    && index < lines.len()
+                {
+                    signature.push(' ');
+                    signature.push_str(strip_comment(lines[index]).trim());
+                    index += 1;
+                }
+
+                let func_start = match signature.find("func ") {
+                    Some(v) => v,
+                    None => {
+                        script.skipped.push(line.to_string());
+                        continue;
+                    }
+                };
+                let (mut function, inline_body) =
+                    match parse_signature(&signature[func_start + 5..]) {
+                        Some(v) => v,
+                        None => return Err(format!("unable to parse `{}`", signature)),
+                    };
+
+                // A single line function has its body after the colon.
+                if !inline_body.is_empty() {
+                    function.body.push(inline_body);
+                }
+
+                let body_start = index;
+                while index < lines.len()
+                    && (lines[index].trim().is_empty()
+                        || lines[index].starts_with(char::is_whitespace))
+                {
+                    index += 1;
+                }
+                let mut body: Vec<&str> = lines[body_start..index].to_vec();
+                while body.last().map(|l| l.trim().is_empty()) == Some(true) {
+                    body.pop();
+                }
+                function.body.extend(dedent(&body));
+
+                script.functions.push(function);
+            }
+            _ => script.skipped.push(line.to_string()),
+        }
+    }
+
+    return Ok(script);
+}
+
+/// Parses the part of an export after the `export` keyword, e.g. `(int, 0, 10) var speed = 5` or
+/// `var speed: int = 5`.
+///
+/// # Arguments
+///
+/// `declaration` - The declaration after the `export` keyword.
+fn parse_export(declaration: &str) -> Option<GdVariable> {
+    let mut declaration = declaration.trim();
+    let mut hint = None;
+
+    if declaration.starts_with('(') {
+        let end = matching_paren(declaration)?;
+        let hint_args = split_args(&declaration[1..end]);
+        hint = hint_args.first().map(|h| h.trim().to_string());
+        declaration = declaration[end + 1..].trim();
+    }
+
+    // `onready` can come between `export` and `var`.
+    let (keyword, rest) = split_keyword(declaration);
+    let rest = match keyword {
+        "var" => rest,
+        "onready" => split_keyword(rest).1,
+        _ => return None,
+    };
+
+    // setget only changes how the variable is accessed so it isn't needed here.
+    let rest = match rest.find(" setget") {
+        Some(i) => &rest[..i],
+        None => rest,
+    };
+
+    let mut variable = parse_variable(rest)?;
+    if variable.type_hint.is_none() {
+        variable.type_hint = hint;
+    }
+
+    return Some(variable);
+}
+
+/// Parses a function signature after the `func` keyword, e.g. `move(delta: float) -> void:`. The function is returned
+/// along with anything after the colon, which is the body of a single line function.
+///
+/// # Arguments
+///
+/// `signature` - The signature after the `func` keyword.
+fn parse_signature(signature: &str) -> Option<(GdFunction, String)> {
+    let open = signature.find('(')?;
+    let close = open + matching_paren(&signature[open..])?;
+
+    let after_args = &signature[close + 1..];
+    let colon = after_args.find(':')?;
+    let return_type = after_args[..colon]
+        .trim()
+        .strip_prefix("->")
+        .map(|r| r.trim().to_string());
+
+    let mut args = vec![];
+    for arg in split_args(&signature[open + 1..close]) {
+        args.push(parse_variable(&arg)?);
+    }
+
+    return Some((
+        GdFunction {
+            name: signature[..open].trim().to_string(),
+            args,
+            return_type,
+            body: vec![],
+        },
+        after_args[colon + 1..].trim().to_string(),
+    ));
+}
+
+/// Parses a variable declaration such as `speed: float = 200.0`, `speed := 200.0`, or `speed = 200`.
+///
+/// # Arguments
+///
+/// `declaration` - The declaration without the `var` keyword.
+fn parse_variable(declaration: &str) -> Option<GdVariable> {
+    let (left, default) = match declaration.find('=') {
+        Some(i) => (
+            &declaration[..i],
+            Some(declaration[i + 1..].trim().to_string()),
+        ),
+        None => (declaration, None),
+    };
+
+    let (name, type_hint) = match left.find(':') {
+        Some(i) => {
+            let type_hint = left[i + 1..].trim();
+            // `:=` infers the type from the default value.
+            (
+                left[..i].trim(),
+                if type_hint.is_empty() {
+                    None
+                } else {
+                    Some(type_hint.to_string())
+                },
+            )
+        }
+        None => (left.trim(), None),
+    };
+
+    if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return None;
+    }
+
+    return Some(GdVariable {
+        name: name.to_string(),
+        type_hint,
+        default,
+    });
+}
+
+/// Returns the first word of a line and the rest of the line after it.
+///
+/// # Arguments
+///
+/// `line` - The line to split.
+fn split_keyword(line: &str) -> (&str, &str) {
+    let line = line.trim();
+    let end = line
+        .find(|c: char| c.is_whitespace() || c == '(')
+        .unwrap_or(line.len());
+
+    return (&line[..end], line[end..].trim());
+}
+
+/// Splits a list of arguments by the commas that aren't inside of parentheses, brackets, or strings.
+///
+/// # Arguments
+///
+/// `args` - The arguments without the surrounding parentheses.
+fn split_args(args: &str) -> Vec<String> {
+    let mut result = vec![];
+    let mut current = String::new();
+    let mut depth = 0;
+    let mut quote = None;
+
+    for c in args.chars() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => (),
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (None, '(') | (None, '[') | (None, '{') => depth += 1,
+            (None, ')') | (None, ']') | (None, '}') => depth -= 1,
+            (None, ',') if depth == 0 => {
+                result.push(current.trim().to_string());
+                current.clear();
+                continue;
+            }
+            _ => (),
+        }
+        current.push(c);
+    }
+
+    if !current.trim().is_empty() {
+        result.push(current.trim().to_string());
+    }
+
+    return result;
+}
+
+/// Returns the index of the parenthesis that closes the one at the start of the text.
+///
+/// # Arguments
+///
+/// `text` - The text that starts with an opening parenthesis.
+fn matching_paren(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (index, c) in text.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    return None;
+}
+
+/// Removes a `#` comment from the end of a line, leaving `#` characters inside of strings alone.
+///
+/// # Arguments
+///
+/// `line` - The line to remove the comment from.
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    for (index, c) in line.char_indices() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (None, '#') => return &line[..index],
+            _ => (),
+        }
+    }
+
+    return line;
+}
+
+/// Removes the indentation that all of the lines of a function's body have in common.
+///
+/// # Arguments
+///
+/// `lines` - The lines of the body.
+fn dedent(lines: &[&str]) -> Vec<String> {
+    let indent = lines
+        .iter()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+
+    return lines
+        .iter()
+        .map(|l| {
+            if l.trim().is_empty() {
+                String::new()
+            } else {
+                l[indent..].trim_end().to_string()
+            }
+        })
+        .collect();
+}
@@ -0,0 +1,279 @@
+#![allow(clippy::needless_return)]
+
+/// The classes that are built into Godot 3 along with the class that each one inherits from. This covers every node
+/// and the commonly used resources and other classes, which is enough to check the type of the node that a script is
+/// attached to and to keep modules from taking the name of a built-in class.
+const GODOT_CLASSES: &[(&str, &str)] = &[
+    ("Object", ""),
+    // Nodes.
+    ("Node", "Object"),
+    ("AnimationPlayer", "Node"),
+    ("AnimationTree", "Node"),
+    ("AnimationTreePlayer", "Node"),
+    ("AudioStreamPlayer", "Node"),
+    ("CanvasLayer", "Node"),
+    ("ParallaxBackground", "CanvasLayer"),
+    ("EditorPlugin", "Node"),
+    ("HTTPRequest", "Node"),
+    ("InstancePlaceholder", "Node"),
+    ("ResourcePreloader", "Node"),
+    ("SkeletonIK", "Node"),
+    ("Timer", "Node"),
+    ("Tween", "Node"),
+    ("Viewport", "Node"),
+    ("WorldEnvironment", "Node"),
+    // 2D nodes.
+    ("CanvasItem", "Node"),
+    ("Node2D", "CanvasItem"),
+    ("AnimatedSprite", "Node2D"),
+    ("AudioStreamPlayer2D", "Node2D"),
+    ("BackBufferCopy", "Node2D"),
+    ("Bone2D", "Node2D"),
+    ("Camera2D", "Node2D"),
+    ("CanvasModulate", "Node2D"),
+    ("CollisionObject2D", "Node2D"),
+    ("Area2D", "CollisionObject2D"),
+    ("PhysicsBody2D", "CollisionObject2D"),
+    ("KinematicBody2D", "PhysicsBody2D"),
+    ("RigidBody2D", "PhysicsBody2D"),
+    ("StaticBody2D", "PhysicsBody2D"),
+    ("CollisionPolygon2D", "Node2D"),
+    ("CollisionShape2D", "Node2D"),
+    ("CPUParticles2D", "Node2D"),
+    ("Joint2D", "Node2D"),
+    ("DampedSpringJoint2D", "Joint2D"),
+    ("GrooveJoint2D", "Joint2D"),
+    ("PinJoint2D", "Joint2D"),
+    ("Light2D", "Node2D"),
+    ("LightOccluder2D", "Node2D"),
+    ("Line2D", "Node2D"),
+    ("MeshInstance2D", "Node2D"),
+    ("MultiMeshInstance2D", "Node2D"),
+    ("Navigation2D", "Node2D"),
+    ("NavigationPolygonInstance", "Node2D"),
+    ("ParallaxLayer", "Node2D"),
+    ("Particles2D", "Node2D"),
+    ("Path2D", "Node2D"),
+    ("PathFollow2D", "Node2D"),
+    ("Polygon2D", "Node2D"),
+    ("Position2D", "Node2D"),
+    ("RayCast2D", "Node2D"),
+    ("RemoteTransform2D", "Node2D"),
+    ("Skeleton2D", "Node2D"),
+    ("Sprite", "Node2D"),
+    ("TileMap", "Node2D"),
+    ("TouchScreenButton", "Node2D"),
+    ("VisibilityNotifier2D", "Node2D"),
+    ("VisibilityEnabler2D", "VisibilityNotifier2D"),
+    ("YSort", "Node2D"),
+    // Control nodes.
+    ("Control", "CanvasItem"),
+    ("BaseButton", "Control"),
+    ("Button", "BaseButton"),
+    ("CheckBox", "Button"),
+    ("CheckButton", "Button"),
+    ("ColorPickerButton", "Button"),
+    ("MenuButton", "Button"),
+    ("OptionButton", "Button"),
+    ("ToolButton", "Button"),
+    ("LinkButton", "BaseButton"),
+    ("TextureButton", "BaseButton"),
+    ("ColorRect", "Control"),
+    ("Container", "Control"),
+    ("BoxContainer", "Container"),
+    ("HBoxContainer", "BoxContainer"),
+    ("VBoxContainer", "BoxContainer"),
+    ("ColorPicker", "BoxContainer"),
+    ("CenterContainer", "Container"),
+    ("GraphNode", "Container"),
+    ("GridContainer", "Container"),
+    ("MarginContainer", "Container"),
+    ("PanelContainer", "Container"),
+    ("ScrollContainer", "Container"),
+    ("SplitContainer", "Container"),
+    ("HSplitContainer", "SplitContainer"),
+    ("VSplitContainer", "SplitContainer"),
+    ("TabContainer", "Container"),
+    ("ViewportContainer", "Container"),
+    ("GraphEdit", "Control"),
+    ("ItemList", "Control"),
+    ("Label", "Control"),
+    ("LineEdit", "Control"),
+    ("NinePatchRect", "Control"),
+    ("Panel", "Control"),
+    ("Popup", "Control"),
+    ("PopupDialog", "Popup"),
+    ("PopupMenu", "Popup"),
+    ("PopupPanel", "Popup"),
+    ("WindowDialog", "Popup"),
+    ("AcceptDialog", "WindowDialog"),
+    ("ConfirmationDialog", "AcceptDialog"),
+    ("FileDialog", "ConfirmationDialog"),
+    ("Range", "Control"),
+    ("ProgressBar", "Range"),
+    ("ScrollBar", "Range"),
+    ("HScrollBar", "ScrollBar"),
+    ("VScrollBar", "ScrollBar"),
+    ("Slider", "Range"),
+    ("HSlider", "Slider"),
+    ("VSlider", "Slider"),
+    ("SpinBox", "Range"),
+    ("TextureProgress", "Range"),
+    ("ReferenceRect", "Control"),
+    ("RichTextLabel", "Control"),
+    ("Separator", "Control"),
+    ("HSeparator", "Separator"),
+    ("VSeparator", "Separator"),
+    ("Tabs", "Control"),
+    ("TextEdit", "Control"),
+    ("TextureRect", "Control"),
+    ("Tree", "Control"),
+    ("VideoPlayer", "Control"),
+    // 3D nodes.
+    ("Spatial", "Node"),
+    ("ARVRAnchor", "Spatial"),
+    ("ARVRController", "Spatial"),
+    ("ARVROrigin", "Spatial"),
+    ("AudioStreamPlayer3D", "Spatial"),
+    ("BoneAttachment", "Spatial"),
+    ("Camera", "Spatial"),
+    ("ARVRCamera", "Camera"),
+    ("ClippedCamera", "Camera"),
+    ("InterpolatedCamera", "Camera"),
+    ("CollisionObject", "Spatial"),
+    ("Area", "CollisionObject"),
+    ("PhysicsBody", "CollisionObject"),
+    ("KinematicBody", "PhysicsBody"),
+    ("PhysicalBone", "PhysicsBody"),
+    ("RigidBody", "PhysicsBody"),
+    ("VehicleBody", "RigidBody"),
+    ("StaticBody", "PhysicsBody"),
+    ("CollisionPolygon", "Spatial"),
+    ("CollisionShape", "Spatial"),
+    ("GridMap", "Spatial"),
+    ("Joint", "Spatial"),
+    ("ConeTwistJoint", "Joint"),
+    ("Generic6DOFJoint", "Joint"),
+    ("HingeJoint", "Joint"),
+    ("PinJoint", "Joint"),
+    ("SliderJoint", "Joint"),
+    ("Listener", "Spatial"),
+    ("Navigation", "Spatial"),
+    ("NavigationMeshInstance", "Spatial"),
+    ("Path", "Spatial"),
+    ("PathFollow", "Spatial"),
+    ("Position3D", "Spatial"),
+    ("ProximityGroup", "Spatial"),
+    ("RayCast", "Spatial"),
+    ("RemoteTransform", "Spatial"),
+    ("Skeleton", "Spatial"),
+    ("SpringArm", "Spatial"),
+    ("VehicleWheel", "Spatial"),
+    ("VisibilityNotifier", "Spatial"),
+    ("VisibilityEnabler", "VisibilityNotifier"),
+    ("VisualInstance", "Spatial"),
+    ("BakedLightmap", "VisualInstance"),
+    ("GIProbe", "VisualInstance"),
+    ("ReflectionProbe", "VisualInstance"),
+    ("Light", "VisualInstance"),
+    ("DirectionalLight", "Light"),
+    ("OmniLight", "Light"),
+    ("SpotLight", "Light"),
+    ("GeometryInstance", "VisualInstance"),
+    ("CPUParticles", "GeometryInstance"),
+    ("CSGShape", "GeometryInstance"),
+    ("CSGCombiner", "CSGShape"),
+    ("CSGPrimitive", "CSGShape"),
+    ("CSGBox", "CSGPrimitive"),
+    ("CSGCylinder", "CSGPrimitive"),
+    ("CSGMesh", "CSGPrimitive"),
+    ("CSGPolygon", "CSGPrimitive"),
+    ("CSGSphere", "CSGPrimitive"),
+    ("CSGTorus", "CSGPrimitive"),
+    ("ImmediateGeometry", "GeometryInstance"),
+    ("MeshInstance", "GeometryInstance"),
+    ("SoftBody", "MeshInstance"),
+    ("MultiMeshInstance", "GeometryInstance"),
+    ("Particles", "GeometryInstance"),
+    ("SpriteBase3D", "GeometryInstance"),
+    ("AnimatedSprite3D", "SpriteBase3D"),
+    ("Sprite3D", "SpriteBase3D"),
+    // References and resources.
+    ("Reference", "Object"),
+    ("Resource", "Reference"),
+    ("Animation", "Resource"),
+    ("AudioStream", "Resource"),
+    ("BitMap", "Resource"),
+    ("Curve", "Resource"),
+    ("Environment", "Resource"),
+    ("Font", "Resource"),
+    ("Gradient", "Resource"),
+    ("Image", "Resource"),
+    ("Material", "Resource"),
+    ("Mesh", "Resource"),
+    ("MeshLibrary", "Resource"),
+    ("PackedScene", "Resource"),
+    ("Script", "Resource"),
+    ("Shader", "Resource"),
+    ("Shape", "Resource"),
+    ("Shape2D", "Resource"),
+    ("SpriteFrames", "Resource"),
+    ("StyleBox", "Resource"),
+    ("Texture", "Resource"),
+    ("Theme", "Resource"),
+    ("TileSet", "Resource"),
+    ("World", "Resource"),
+    ("World2D", "Resource"),
+    ("Directory", "Reference"),
+    ("File", "Reference"),
+    ("RandomNumberGenerator", "Reference"),
+    ("RegEx", "Reference"),
+    ("Thread", "Reference"),
+    ("Mutex", "Reference"),
+    ("Semaphore", "Reference"),
+    // Singletons.
+    ("ClassDB", "Object"),
+    ("Engine", "Object"),
+    ("Input", "Object"),
+    ("InputMap", "Object"),
+    ("JSON", "Object"),
+    ("OS", "Object"),
+    ("Performance", "Object"),
+    ("ProjectSettings", "Object"),
+    ("ResourceLoader", "Object"),
+    ("ResourceSaver", "Object"),
+];
+
+/// Returns whether a name is the name of a class that is built into Godot.
+///
+/// # Arguments
+///
+/// `name` - The name to check.
+pub fn is_builtin_class(name: &str) -> bool {
+    return GODOT_CLASSES.iter().any(|(class, _)| *class == name);
+}
+
+/// Returns whether a built-in class is, or inherits from, another class. `None` is returned if the class isn't a
+/// known built-in class so its ancestors can't be checked.
+///
+/// # Arguments
+///
+/// `class` - The class to check, e.g. `KinematicBody2D`.
+/// `base` - The class that it should inherit from, e.g. `Node2D`.
+pub fn inherits(class: &str, base: &str) -> Option<bool> {
+    if class == base {
+        return Some(true);
+    }
+
+    let mut current = class;
+    loop {
+        let parent = GODOT_CLASSES.iter().find(|(c, _)| *c == current)?.1;
+        if parent == base {
+            return Some(true);
+        }
+        if parent.is_empty() {
+            return Some(false);
+        }
+        current = parent;
+    }
+}
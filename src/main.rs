@@ -1,8 +1,14 @@
-#[macro_use]
-
+mod cargo_toml;
 mod commands;
+mod config_file;
 mod configs;
 mod content;
+mod gdscript;
+mod godot_classes;
+mod migrate;
+mod naming;
+mod rust_source;
+mod scene_file;
 mod utils;
 
 use std::path::PathBuf;
@@ -15,10 +21,10 @@ use structopt::StructOpt;
 enum GodotRustHelper {
 	/// Creates the library that will contain your Rust modules.
 	/// The name of the library that will contain your Rust modules. The name of the library is recommended to be the same or similar in name to your game.
-	/// Also keep in mind that the library is created using `cargo new` so you should abide by the cargo project naming standards.
+	/// Also keep in mind that the name of the library is used as the name of its crate so you should abide by the cargo project naming standards.
 	New {
 		/// The name of the library that will contain your Rust modules. The name of the library is recommended to be the same name as your game, snake_case,
-		/// maybe with `_modules` at the end. Also keep in mind that the library is created using `cargo new`
+		/// maybe with `_modules` at the end. Also keep in mind that the name is used as the name of the library's crate.
 		#[structopt(parse(from_os_str))]
 		destination: PathBuf,
 		/// The directory that contains the project.godot file of the game that the modules are for.
@@ -34,13 +40,103 @@ enum GodotRustHelper {
 		/// The directory in the Godot project where the .gdns scripts should be output to.
 		#[structopt(long, short, default_value = "")]
 		nativescript_path: PathBuf,
+		/// A prefix to add to the class names of the modules created in the library so that they don't collide with Godot's classes, e.g. 'Rs' to create 'RsTimer' from 'Timer'.
+		#[structopt(long)]
+		class_prefix: Option<String>,
+		/// The version of gdnative that the generated code is written for.
+		#[structopt(long, default_value = "0.9", possible_values = &configs::GDNATIVE_VERSIONS)]
+		gdnative: configs::GdnativeVersion,
+		/// The version of Godot that the library is for. Godot 4 libraries use GDExtension and gdext instead of GDNative. Defaults to the version that the Godot project was saved with.
+		#[structopt(long, possible_values = &configs::GODOT_VERSIONS)]
+		godot: Option<configs::GodotVersion>,
+		/// Leaves the godot_rust_helper_ext dependency out of the Cargo.toml file.
+		#[structopt(long)]
+		no_ext: bool,
+		/// Where the gdnative dependency comes from, either a version or an inline table such as '{ path = "../godot-rust/gdnative" }' or '{ git = "https://github.com/godot-rust/godot-rust", features = ["serde"] }'. This is saved in the config.
+		#[structopt(long)]
+		gdnative_dep: Option<configs::Dependency>,
+		/// Where the godot_rust_helper_ext dependency comes from, either a version or an inline table like --gdnative-dep. This is saved in the config.
+		#[structopt(long, conflicts_with = "no-ext")]
+		ext_dep: Option<configs::Dependency>,
+		/// The Rust edition that the library's crate uses.
+		#[structopt(long, default_value = "2021", possible_values = &content::EDITIONS)]
+		edition: String,
+		/// A comma separated list of the authors of the library's crate.
+		#[structopt(long, use_delimiter = true)]
+		authors: Vec<String>,
+		/// The SPDX license expression of the library's crate, e.g. 'MIT OR Apache-2.0'.
+		#[structopt(long)]
+		license: Option<String>,
+		/// Initializes a git repository in the library.
+		#[structopt(long)]
+		git: bool,
+	},
+	/// Sets up an existing crate to be used with godot_rust_helper.
+	/// The crate is made to build as a cdylib and the gdnlib or gdextension file, .gdns files, and NativeClass structs that already exist are recorded in the config instead of being overwritten.
+	/// At the root of a Cargo workspace this writes the workspace's config with the member libraries that have been set up already.
+	Init {
+		/// The directory that contains the project.godot file of the game that the modules are for. Defaults to the first directory above the crate, or the crate itself, that contains a project.godot file.
+		#[structopt(long, parse(from_os_str))]
+		godot: Option<PathBuf>,
+		/// The build targets that should be set. Defaults to the targets in the existing gdnlib or gdextension file, or windows if there isn't one.
+		#[structopt(long, short)]
+		targets: Option<String>,
+		/// The directory in the Godot project, relative to it, where the gdnlib and the compiled files go if the library doesn't have a gdnlib file yet.
+		#[structopt(long, short, default_value = "")]
+		output_path: PathBuf,
+		/// The directory in the Godot project, relative to it, where the .gdns scripts that don't exist yet should be output to. Defaults to the directory that has most of the existing ones.
+		#[structopt(long, short, default_value = "")]
+		nativescript_path: PathBuf,
 	},
 	/// Creates a new module inside of the library.
 	/// The name passed to this command should be the class name of the module. Class names must start with capital letters. Examples include 'Player', 'Princess', 'Mob', 'HUD', etc.
 	Create {
-		/// The class name of the module to create; examples include 'Player', 'Princess', 'Mob', 'HUD', etc.
+		/// The class name of the module to create; examples include 'Player', 'Princess', 'Mob', 'HUD', etc. Defaults to the class_name or file name of the script passed to --from-gd.
+		#[structopt(required_unless = "from-gd")]
+		name: Option<String>,
+		/// Registers the class as a tool class so that it also runs in the editor.
+		#[structopt(long, short)]
+		tool: bool,
+		/// The user_data wrapper that the struct is stored in. Use mutex, rw-lock, or arc for classes that are used from other threads.
+		#[structopt(long, short, default_value = "local-cell", possible_values = &content::USER_DATA_WRAPPERS)]
+		user_data: content::UserData,
+		/// Adds the module to the autoloads in the Godot project's project.godot file so that it's loaded as a singleton.
+		#[structopt(long, short)]
+		autoload: bool,
+		/// Creates a scene whose root node uses the module's script. The path is relative to the Godot project and defaults to the module's name in the root of the project.
+		#[structopt(long, short)]
+		scene: Option<Option<PathBuf>>,
+		/// Converts a GDScript file into the module. The exported variables, signals, and functions are carried over and the bodies of the functions are kept as comments to port by hand.
+		#[structopt(long, parse(from_os_str))]
+		from_gd: Option<PathBuf>,
+		/// Updates the scenes and resources that use the GDScript file passed to --from-gd to use the module instead.
+		#[structopt(long, requires = "from-gd")]
+		swap_references: bool,
+		/// Makes the class a named script class so that it shows up in Godot's "Create New Node" dialog.
+		#[structopt(long)]
+		named: bool,
+		/// The icon that Godot shows for the named script class. Icons outside of the Godot project are copied next to the class' gdns file.
+		#[structopt(long, requires = "named", parse(from_os_str))]
+		icon: Option<PathBuf>,
+		/// The version of Godot that the library is for. The command stops if the library was created for a different version.
+		#[structopt(long, possible_values = &configs::GODOT_VERSIONS)]
+		godot: Option<configs::GodotVersion>,
+		/// The library to create the module in when the command is used at the root of a workspace, either its path in the workspace or the name of its directory.
+		#[structopt(long)]
+		lib: Option<String>,
+	},
+	/// Creates a new module that inherits from Resource and exports the given properties.
+	/// The resource's .gdns file is created like any other module and a sample .tres file that uses it can be created so that its data can be edited in Godot right away.
+	CreateResource {
+		/// The class name of the resource to create; examples include 'Item', 'EnemyStats', 'Dialogue', etc.
 		#[structopt()]
 		name: String,
+		/// A property to export in the form of name:type, e.g. 'health:int'. The supported types are int, float, bool, String, Vector2, Vector3, and Color. This can be used more than once.
+		#[structopt(long = "property", short, number_of_values = 1)]
+		properties: Vec<content::ResourceProperty>,
+		/// The path to a sample .tres file to create relative to the Godot project, e.g. 'data/sword.tres'.
+		#[structopt(long, short, parse(from_os_str))]
+		tres: Option<PathBuf>,
 	},
 	/// Removes a module created with `create`.
 	/// The name passed to this command should be the same name that was passed when the module was created.
@@ -48,12 +144,75 @@ enum GodotRustHelper {
 		/// The name of the module to destory.
 		#[structopt()]
 		name: String,
+		/// Destroys the module even if scenes, resources, or scripts in the Godot project still reference it.
+		#[structopt(long, short)]
+		force: bool,
+		/// Keeps the module's Rust file and only unregisters the class and removes its gdns file.
+		#[structopt(long, short)]
+		keep_source: bool,
+	},
+	/// Renames a module created with `create`.
+	/// This renames the module's file, struct, and .gdns file and updates any scenes or resources in the Godot project that use it.
+	Rename {
+		/// The current name of the module.
+		#[structopt()]
+		name: String,
+		/// The new name of the module.
+		#[structopt()]
+		new_name: String,
+	},
+	/// Adds structs that derive NativeClass but weren't created with `create` to the library.
+	/// The structs are added to the config, registered in the lib file, and get a gdns file if they don't have one already.
+	Import {},
+	/// Brings the library in line with its config.
+	/// The lib file is regenerated, missing .gdns files are created, stale .gdns library paths are fixed, and files that aren't used by any module are reported.
+	Sync {
+		/// Removes the .gdns and .rs files that aren't used by any module.
+		#[structopt(long, short)]
+		prune: bool,
+	},
+	/// Lists the modules in the library along with their files and how they are registered.
+	/// The library's targets and whether the deployed dynamic library is out of date are shown as well.
+	List {
+		/// Prints the list as JSON.
+		#[structopt(long, short)]
+		json: bool,
+	},
+	/// Adds a module to the autoloads in the Godot project's project.godot file so that it's loaded as a singleton.
+	/// The autoload is named after the module's class and can be removed again with `--remove`.
+	Autoload {
+		/// The name of the module.
+		#[structopt()]
+		name: String,
+		/// Removes the module from the autoloads instead.
+		#[structopt(long, short)]
+		remove: bool,
+	},
+	/// Attaches a module's script to a node in an existing scene.
+	/// The module's gdns file is added to the scene's resources and set as the node's script after checking that the node's type matches the class that the module inherits from.
+	Attach {
+		/// The name of the module.
+		#[structopt()]
+		name: String,
+		/// The scene to attach the script in, relative to the Godot project or as a res:// path.
+		#[structopt(parse(from_os_str))]
+		scene: PathBuf,
+		/// The path to the node relative to the scene's root node, e.g. 'Player/Sprite'. Defaults to the root node.
+		#[structopt(long, short, default_value = ".")]
+		node: String,
+		/// Attaches the script even if the node's type doesn't match the class that the module inherits from.
+		#[structopt(long, short)]
+		force: bool,
 	},
 	/// Runs the `cargo build` command and copies the build files to the Godot project.
+	/// At the root of a workspace every library is built and copied to its own output path.
 	Build {
 		// Indicates whether the godot_rust_helper should watch the project for changes and rebuild automatically or not.
 		#[structopt(long, short)]
 		watch: bool,
+		/// The version of Godot that the library is for. The command stops if the library was created for a different version.
+		#[structopt(long, possible_values = &configs::GODOT_VERSIONS)]
+		godot: Option<configs::GodotVersion>,
 	},
 	/// Changes the project path and the godot project path in the config and optionally sets new targets.
 	/// This is useful if you cloned a project using godot_rust_helper.
@@ -65,6 +224,22 @@ enum GodotRustHelper {
 		#[structopt(long, short, default_value = "")]
 		targets: String,
 	},
+	/// Deploys the library to another Godot project along with the one it was created for, e.g. a level editor that uses the same classes as the game.
+	/// The project gets its own gdnlib and gdns files, and `create`, `destroy`, and `build` keep it up to date like the first project.
+	Project {
+		/// The directory that contains the project.godot file of the other Godot project.
+		#[structopt(parse(from_os_str))]
+		godot_project_dir: PathBuf,
+		/// The directory in the Godot project, relative to it, where the gdnlib and the compiled files go. Defaults to the root of the Godot project.
+		#[structopt(long, short, default_value = "")]
+		output_path: PathBuf,
+		/// The directory in the Godot project, relative to it, where the .gdns scripts go. Defaults to the root of the Godot project.
+		#[structopt(long, short, default_value = "")]
+		nativescript_path: PathBuf,
+		/// Stops deploying the library to the Godot project. The files that were created in it are left alone.
+		#[structopt(long, short)]
+		remove: bool,
+	},
 	/// Update a library from using godot_rust_helper v1.x to v2.x or v2.x to v3.x.
 	Update {
 		/// As of godot_rust_helper 2.x the 'rust-modules' directory no longer exists and is customizable. You can change this to a different directory at this time but you'll have to fix all references in Godot.
@@ -73,6 +248,9 @@ enum GodotRustHelper {
 		/// As of godot_rust_helper 3.x the nativescript files can be placed into a custom directory within the Godot project.
 		#[structopt(long, short, default_value = "")]
 		nativescript_path: PathBuf,
+		/// Converts the library to a newer version of gdnative by bumping the dependency and rewriting the API changes in its modules.
+		#[structopt(long, possible_values = &configs::GDNATIVE_VERSIONS)]
+		gdnative: Option<configs::GdnativeVersion>,
 	},
 	/// Creates a plugin similar to the `new` command but also builds the correct structure and plugin config.
 	Plugin {
@@ -97,6 +275,27 @@ enum GodotRustHelper {
 		/// The build targets that should be set. As of writing this, the available targets are windows, linux, and osx with the default being just windows.
 		#[structopt(long, short, default_value = "windows")]
 		targets: String,
+		/// The version of Godot that the plugin is for. Defaults to the version that the Godot project was saved with.
+		#[structopt(long, possible_values = &configs::GODOT_VERSIONS)]
+		godot: Option<configs::GodotVersion>,
+		/// Leaves the godot_rust_helper_ext dependency out of the Cargo.toml file.
+		#[structopt(long)]
+		no_ext: bool,
+		/// Where the gdnative dependency comes from, either a version or an inline table such as '{ path = "../godot-rust/gdnative" }' or '{ git = "https://github.com/godot-rust/godot-rust", features = ["serde"] }'. This is saved in the config.
+		#[structopt(long)]
+		gdnative_dep: Option<configs::Dependency>,
+		/// Where the godot_rust_helper_ext dependency comes from, either a version or an inline table like --gdnative-dep. This is saved in the config.
+		#[structopt(long, conflicts_with = "no-ext")]
+		ext_dep: Option<configs::Dependency>,
+		/// The Rust edition that the plugin's crate uses.
+		#[structopt(long, default_value = "2021", possible_values = &content::EDITIONS)]
+		edition: String,
+		/// The SPDX license expression of the plugin's crate, e.g. 'MIT OR Apache-2.0'.
+		#[structopt(long)]
+		license: Option<String>,
+		/// Initializes a git repository in the plugin's library.
+		#[structopt(long)]
+		git: bool,
 	},
 }
 
@@ -109,6 +308,16 @@ fn main() {
 			targets,
 			output_path,
 			nativescript_path,
+			class_prefix,
+			gdnative,
+			godot,
+			no_ext,
+			gdnative_dep,
+			ext_dep,
+			edition,
+			authors,
+			license,
+			git,
 		} => {
 			commands::create_library(
 				destination,
@@ -116,22 +325,114 @@ fn main() {
 				targets,
 				output_path,
 				nativescript_path,
+				commands::LibraryOptions {
+					class_prefix,
+					gdnative_version: gdnative,
+					godot,
+					dependencies: configs::ConfigDependencies {
+						gdnative: gdnative_dep,
+						ext: !no_ext,
+						godot_rust_helper_ext: ext_dep,
+					},
+					crate_options: commands::CrateOptions {
+						edition,
+						authors,
+						license,
+						git,
+					},
+				},
 			);
 		}
+		// When the `init` command is used we run the `commands::init_library` function to set up an existing crate.
+		GodotRustHelper::Init {
+			godot,
+			targets,
+			output_path,
+			nativescript_path,
+		} => {
+			commands::init_library(godot, targets, output_path, nativescript_path);
+		}
 		// When the `create` command is used we run the `commands::create_module` function to create a module inside of the library.
-		GodotRustHelper::Create { name } => {
-			commands::create_module(&name.to_owned());
+		GodotRustHelper::Create {
+			name,
+			tool,
+			user_data,
+			autoload,
+			scene,
+			from_gd,
+			swap_references,
+			named,
+			icon,
+			godot,
+			lib,
+		} => {
+			commands::create_module(
+				name.as_deref(),
+				commands::CreateOptions {
+					tool,
+					user_data,
+					autoload,
+					scene: scene.map(|s| s.unwrap_or_default()),
+					from_gd,
+					swap_references,
+					named,
+					icon,
+					godot,
+					lib,
+				},
+			);
+		}
+		// When the `create-resource` command is used we run the `commands::create_resource` function to create a Resource module inside of the library.
+		GodotRustHelper::CreateResource {
+			name,
+			properties,
+			tres,
+		} => {
+			commands::create_resource(&name, &properties, tres);
 		}
 		// When the `destroy` command is used we run the `commands::destory_module` function to remove a module inside of the library
-		GodotRustHelper::Destroy { name } => {
-			commands::destroy_module(&name.to_owned());
+		GodotRustHelper::Destroy {
+			name,
+			force,
+			keep_source,
+		} => {
+			commands::destroy_module(&name.to_owned(), force, keep_source);
+		}
+		// When the `rename` command is used we run the `commands::rename_module` function to rename a module inside of the library.
+		GodotRustHelper::Rename { name, new_name } => {
+			commands::rename_module(&name, &new_name);
+		}
+		// When the `import` command is used we run the `commands::import_modules` function to adopt existing NativeClass structs.
+		GodotRustHelper::Import {} => {
+			commands::import_modules();
+		}
+		// When the `sync` command is used we run the `commands::sync_library` function to make the library match its config.
+		GodotRustHelper::Sync { prune } => {
+			commands::sync_library(prune);
+		}
+		// When the `list` command is used we run the `commands::list_modules` function to show the modules and their files.
+		GodotRustHelper::List { json } => {
+			commands::list_modules(json);
+		}
+		// When the `autoload` command is used we run the `commands::autoload_module` function to add or remove a module's autoload.
+		GodotRustHelper::Autoload { name, remove } => {
+			commands::autoload_module(&name, remove);
+		}
+		// When the `attach` command is used we run the `commands::attach_module` function to set a module's script on a node in a scene.
+		GodotRustHelper::Attach {
+			name,
+			scene,
+			node,
+			force,
+		} => {
+			commands::attach_module(&name, scene, &node, force);
 		}
 		// When the `build` command is used we run the `commands::build_library` function to generate the build files and copy them to Godot project.
-		GodotRustHelper::Build { watch } => {
+		GodotRustHelper::Build { watch, godot } => {
 			if watch {
-				commands::watch_library()
+				commands::watch_library(godot)
 			} else {
-				commands::build_library()
+				commands::build_library(godot)
 			}
 		}
 		// When the `rebase` command is used we run the `commands::rebase` function to update the config file.
@@ -141,12 +442,22 @@ fn main() {
 		} => {
 			commands::rebase(godot_project_dir, targets);
 		}
+		// When the `project` command is used we run the `commands::godot_project` function to add or remove another Godot project.
+		GodotRustHelper::Project {
+			godot_project_dir,
+			output_path,
+			nativescript_path,
+			remove,
+		} => {
+			commands::godot_project(godot_project_dir, output_path, nativescript_path, remove);
+		}
 		// When the `update` command is used we run the `commands::update` function to update the project from using godot_rust_helper 1.x to godot_rust_helper 2.x.
 		GodotRustHelper::Update {
 			output_path,
 			nativescript_path,
+			gdnative,
 		} => {
-			commands::update(output_path, nativescript_path);
+			commands::update(output_path, nativescript_path, gdnative);
 		}
 		// When the `plugin` command is used we run the `commands::plugin` function to create a new plugin setup.
 		GodotRustHelper::Plugin {
@@ -157,15 +468,42 @@ fn main() {
 			author,
 			version,
 			targets,
+			godot,
+			no_ext,
+			gdnative_dep,
+			ext_dep,
+			edition,
+			license,
+			git,
 		} => {
+			// The author of the plugin is also the author of its crate.
+			let authors = if author.is_empty() {
+				vec![]
+			} else {
+				vec![author.clone()]
+			};
 			commands::create_plugin(
 				name,
 				destination,
 				godot_project_dir,
-				description,
-				author,
-				version,
 				targets,
+				commands::PluginOptions {
+					description,
+					author,
+					version,
+					godot,
+					dependencies: configs::ConfigDependencies {
+						gdnative: gdnative_dep,
+						ext: !no_ext,
+						godot_rust_helper_ext: ext_dep,
+					},
+					crate_options: commands::CrateOptions {
+						edition,
+						authors,
+						license,
+						git,
+					},
+				},
 			);
 		}
 	}
@@ -0,0 +1,401 @@
+#![allow(clippy::needless_return)]
+
+use proc_macro2::{LineColumn, Span};
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+
+use crate::configs::GdnativeVersion;
+
+/// The paths that gdnative 0.10 moved out of `gdnative::nativescript`. Each entry is the old path and the path that
+/// replaces it, with the more specific paths first.
+const PATH_CHANGES_0_10: [(&str, &str); 5] = [
+    (
+        "gdnative::nativescript::InitHandle",
+        "gdnative::init::InitHandle",
+    ),
+    (
+        "gdnative::nativescript::init::property",
+        "gdnative::export::property",
+    ),
+    ("gdnative::nativescript::init", "gdnative::export"),
+    (
+        "gdnative::nativescript::Instance",
+        "gdnative::object::Instance",
+    ),
+    ("gdnative::nativescript", "gdnative::export"),
+];
+
+/// The items that gdnative 0.10 removed without a direct replacement.
+const REMOVED_ITEMS_0_10: [&str; 3] = ["Signal", "SignalArgument", "RefInstance"];
+
+/// A change to a source file that replaces the text between two byte offsets.
+struct Edit {
+    start: usize,
+    end: usize,
+    text: String,
+}
+
+/// Visits a parsed source file and collects the edits that convert it from gdnative 0.9 to 0.10.
+struct Migration<'a> {
+    /// The name of the file used in the notes.
+    file_name: &'a str,
+    /// The contents of the file.
+    source: &'a str,
+    /// The byte offset of the start of each line in the file.
+    line_starts: Vec<usize>,
+    edits: Vec<Edit>,
+    /// The things that couldn't be converted and have to be changed by hand.
+    notes: Vec<String>,
+    /// Whether the file has a `#[methods]` impl that needs the gdnative prelude.
+    needs_prelude: bool,
+    /// Whether the file already imports the gdnative prelude.
+    uses_prelude: bool,
+}
+
+impl<'a> Migration<'a> {
+    fn new(file_name: &'a str, source: &'a str) -> Migration<'a> {
+        let mut line_starts = vec![0];
+        line_starts.extend(source.match_indices('\n').map(|(i, _)| i + 1));
+
+        Migration {
+            file_name,
+            source,
+            line_starts,
+            edits: vec![],
+            notes: vec![],
+            needs_prelude: false,
+            uses_prelude: false,
+        }
+    }
+
+    /// Returns the byte offset of a line and column from a span, where the column is counted in characters.
+    fn offset(&self, position: LineColumn) -> usize {
+        let line_start = self.line_starts[position.line - 1];
+        let line = &self.source[line_start..];
+
+        return line_start
+            + line
+                .char_indices()
+                .nth(position.column)
+                .map(|(i, _)| i)
+                .unwrap_or_else(|| line.len());
+    }
+
+    /// Replaces the text from the start of one span to the end of another.
+    fn replace(&mut self, from: Span, to: Span, text: &str) {
+        self.edits.push(Edit {
+            start: self.offset(from.start()),
+            end: self.offset(to.end()),
+            text: text.to_string(),
+        });
+    }
+
+    /// Inserts text at the start of a span.
+    fn insert(&mut self, at: Span, text: &str) {
+        let offset = self.offset(at.start());
+        self.edits.push(Edit {
+            start: offset,
+            end: offset,
+            text: text.to_string(),
+        });
+    }
+
+    /// Records something that has to be converted by hand along with where it is.
+    fn note(&mut self, at: Span, message: &str) {
+        self.notes.push(format!(
+            "{}:{}: {}",
+            self.file_name,
+            at.start().line,
+            message
+        ));
+    }
+
+    /// Records the items that were removed in gdnative 0.10 if a path refers to one of them.
+    fn note_removed_item(&mut self, at: Span, path: &str) {
+        let item = path.rsplit("::").next().unwrap_or(path);
+        if path.contains("nativescript") && REMOVED_ITEMS_0_10.contains(&item) {
+            self.note(at, &format!("{} was removed in gdnative 0.10", item));
+        }
+    }
+}
+
+impl<'a, 'ast> Visit<'ast> for Migration<'a> {
+    fn visit_item_use(&mut self, item: &'ast syn::ItemUse) {
+        let mut paths = vec![];
+        flatten_use_tree(&item.tree, &mut vec![], &mut paths);
+
+        let mut changed = false;
+        let mut new_paths = vec![];
+        for path in &paths {
+            self.note_removed_item(item.span(), path);
+
+            let new_path = migrate_path(path);
+            changed = changed || new_path.is_some();
+            new_paths.push(new_path.unwrap_or_else(|| path.clone()));
+        }
+        if new_paths.iter().any(|path| path == "gdnative::prelude::*") {
+            self.uses_prelude = true;
+        }
+        if !changed {
+            return;
+        }
+
+        // Each path gets its own `use` so that the groups don't have to be rebuilt.
+        let start = match item.vis {
+            syn::Visibility::Inherited => item.use_token.span,
+            _ => item.vis.span(),
+        };
+        let start_offset = self.offset(start.start());
+        let visibility = &self.source[start_offset..self.offset(item.use_token.span.start())];
+        let line_start = self.line_starts[start.start().line - 1];
+        let indentation = &self.source[line_start..start_offset];
+        let text = new_paths
+            .iter()
+            .map(|path| format!("{}use {};", visibility, path))
+            .collect::<Vec<String>>()
+            .join(&format!("\n{}", indentation));
+
+        self.replace(start, item.semi_token.span, &text);
+    }
+
+    fn visit_path(&mut self, path: &'ast syn::Path) {
+        let segments: Vec<String> = path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect();
+
+        for (old_path, new_path) in PATH_CHANGES_0_10.iter() {
+            let old_segments: Vec<&str> = old_path.split("::").collect();
+            if segments.len() >= old_segments.len()
+                && segments.iter().zip(&old_segments).all(|(a, b)| a == b)
+            {
+                self.note_removed_item(path.span(), &segments.join("::"));
+
+                let first = path.segments[0].ident.span();
+                let last = path.segments[old_segments.len() - 1].ident.span();
+                self.replace(first, last, new_path);
+                break;
+            }
+        }
+
+        visit::visit_path(self, path);
+    }
+
+    fn visit_item_impl(&mut self, item: &'ast syn::ItemImpl) {
+        let mut is_methods_impl = false;
+        for attr in &item.attrs {
+            if attr.path.is_ident("methods") {
+                is_methods_impl = true;
+            } else if path_is(&attr.path, &["gdnative", "methods"]) {
+                is_methods_impl = true;
+                self.needs_prelude = true;
+                let segments = &attr.path.segments;
+                self.replace(
+                    segments[0].ident.span(),
+                    segments[1].ident.span(),
+                    "methods",
+                );
+            }
+        }
+
+        if is_methods_impl {
+            for impl_item in &item.items {
+                if let syn::ImplItem::Method(method) = impl_item {
+                    let export = method
+                        .attrs
+                        .iter()
+                        .find(|attr| attr.path.is_ident("export") && attr.tokens.is_empty());
+                    if let Some(attr) = export {
+                        let span = attr.path.segments[0].ident.span();
+                        self.replace(span, span, "method");
+
+                        // The owner is the first argument after the receiver and has to be marked with `#[base]`.
+                        let owner = method.sig.inputs.iter().find_map(|input| match input {
+                            syn::FnArg::Typed(arg) => Some(arg),
+                            syn::FnArg::Receiver(_) => None,
+                        });
+                        if let Some(owner) = owner {
+                            if owner.attrs.is_empty() {
+                                self.insert(owner.pat.span(), "#[base] ");
+                            }
+                        }
+                    } else if method.attrs.iter().any(|attr| attr.path.is_ident("export")) {
+                        self.note(
+                            method.sig.ident.span(),
+                            "#[export] with arguments has to be converted to #[method] by hand",
+                        );
+                    }
+                }
+            }
+        }
+
+        visit::visit_item_impl(self, item);
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast syn::ExprCall) {
+        if let syn::Expr::Path(func) = &*call.func {
+            let segments = &func.path.segments;
+            if call.args.is_empty() && path_is(&func.path, &["Variant", "new"]) {
+                let span = segments[1].ident.span();
+                self.replace(span, span, "nil");
+            } else if call.args.is_empty()
+                && (path_is(&func.path, &["Vector2", "zero"])
+                    || path_is(&func.path, &["Vector3", "zero"]))
+            {
+                self.replace(segments[1].ident.span(), call.paren_token.span, "ZERO");
+            }
+        }
+
+        visit::visit_expr_call(self, call);
+    }
+
+    fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+        if call.method == "add_signal" {
+            self.note(
+                call.method.span(),
+                "add_signal has to be replaced with the signal builder by hand",
+            );
+        }
+
+        visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        // The tokens of macros aren't parsed so any paths in them can't be converted.
+        if mac.tokens.to_string().contains("nativescript") {
+            let name = mac
+                .path
+                .segments
+                .last()
+                .map(|segment| segment.ident.to_string())
+                .unwrap_or_default();
+            self.note(
+                mac.path.span(),
+                &format!("the {}! macro uses gdnative::nativescript", name),
+            );
+        }
+    }
+}
+
+/// Returns whether a path is made of exactly the given segments.
+fn path_is(path: &syn::Path, segments: &[&str]) -> bool {
+    return path.segments.len() == segments.len()
+        && path
+            .segments
+            .iter()
+            .zip(segments)
+            .all(|(segment, name)| segment.ident == name);
+}
+
+/// Collects every path imported by a `use` tree, e.g. `a::{b, c::*}` becomes `a::b` and `a::c::*`.
+fn flatten_use_tree(tree: &syn::UseTree, prefix: &mut Vec<String>, paths: &mut Vec<String>) {
+    let join = |prefix: &Vec<String>, last: &str| {
+        let mut segments = prefix.clone();
+        if last != "self" || segments.is_empty() {
+            segments.push(last.to_string());
+        }
+        return segments.join("::");
+    };
+
+    match tree {
+        syn::UseTree::Path(path) => {
+            prefix.push(path.ident.to_string());
+            flatten_use_tree(&path.tree, prefix, paths);
+            prefix.pop();
+        }
+        syn::UseTree::Name(name) => paths.push(join(prefix, &name.ident.to_string())),
+        syn::UseTree::Rename(rename) => paths.push(format!(
+            "{} as {}",
+            join(prefix, &rename.ident.to_string()),
+            rename.rename
+        )),
+        syn::UseTree::Glob(_) => paths.push(join(prefix, "*")),
+        syn::UseTree::Group(group) => {
+            for tree in &group.items {
+                flatten_use_tree(tree, prefix, paths);
+            }
+        }
+    }
+}
+
+/// Returns the gdnative 0.10 version of a path or `None` if the path didn't change.
+fn migrate_path(path: &str) -> Option<String> {
+    for (old_path, new_path) in PATH_CHANGES_0_10.iter() {
+        if path == *old_path {
+            return Some(new_path.to_string());
+        }
+        if let Some(rest) = path.strip_prefix(old_path) {
+            if rest.starts_with("::") || rest.starts_with(" as ") {
+                return Some(format!("{}{}", new_path, rest));
+            }
+        }
+    }
+
+    return None;
+}
+
+/// Rewrites the parts of a Rust source file that changed between two versions of gdnative. The new contents are
+/// returned along with notes about anything that has to be converted by hand.
+///
+/// # Arguments
+///
+/// `file_name` - The name of the file used in the notes.
+/// `source` - The contents of the file.
+/// `from` - The version of gdnative that the file currently uses.
+/// `to` - The version of gdnative to convert the file to.
+pub fn migrate_source(
+    file_name: &str,
+    source: &str,
+    from: GdnativeVersion,
+    to: GdnativeVersion,
+) -> Result<(String, Vec<String>), String> {
+    if from != GdnativeVersion::V0_9 || to != GdnativeVersion::V0_10 {
+        return Ok((source.to_string(), vec![]));
+    }
+
+    let file = syn::parse_file(source).map_err(|e| {
+        format!(
+            "{}:{}: couldn't be parsed: {}",
+            file_name,
+            e.span().start().line,
+            e
+        )
+    })?;
+
+    let mut migration = Migration::new(file_name, source);
+    migration.visit_file(&file);
+
+    if migration.needs_prelude && !migration.uses_prelude {
+        let last_use = file.items.iter().rev().find_map(|item| match item {
+            syn::Item::Use(item) => Some(item.semi_token.span),
+            _ => None,
+        });
+        match last_use {
+            Some(span) => {
+                let offset = migration.offset(span.end());
+                migration.edits.push(Edit {
+                    start: offset,
+                    end: offset,
+                    text: "\nuse gdnative::prelude::*;".to_string(),
+                });
+            }
+            None => migration.edits.push(Edit {
+                start: 0,
+                end: 0,
+                text: "use gdnative::prelude::*;\n\n".to_string(),
+            }),
+        }
+    }
+
+    // The edits are applied from the end of the file so that the offsets of the earlier edits stay the same.
+    let mut new_source = source.to_string();
+    migration
+        .edits
+        .sort_by(|a, b| b.start.cmp(&a.start).then(b.end.cmp(&a.end)));
+    for edit in &migration.edits {
+        new_source.replace_range(edit.start..edit.end, &edit.text);
+    }
+
+    return Ok((new_source, migration.notes));
+}
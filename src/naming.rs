@@ -0,0 +1,342 @@
+#![allow(clippy::needless_return)]
+
+use crate::godot_classes;
+
+/// The Rust keywords that can't be used as a `mod` identifier without being written as a raw identifier.
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "extern", "false",
+    "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "static", "struct", "trait", "true", "type", "unsafe", "use", "where", "while",
+    "abstract", "become", "box", "do", "final", "macro", "override", "priv", "try", "typeof",
+    "unsized", "virtual", "yield",
+];
+
+/// The Rust keywords that can't be used as identifiers at all, not even as raw identifiers.
+const RESERVED_PATH_KEYWORDS: &[&str] = &["crate", "self", "Self", "super"];
+
+/// Returns whether a name is a Rust keyword, including the ones that can't be written as a raw identifier.
+///
+/// # Arguments
+///
+/// `name` - The name to check.
+pub fn is_rust_keyword(name: &str) -> bool {
+    return RUST_KEYWORDS.contains(&name) || RESERVED_PATH_KEYWORDS.contains(&name);
+}
+
+/// Returns the snake_case version of a module's class name. This is used as the base for the module's file names.
+///
+/// Words are split where a lowercase letter is followed by an uppercase letter, where an acronym is followed by a
+/// capitalized word, and where a number is followed by a capitalized word. Numbers stay attached to the word before
+/// them. For example, 'MainScene' becomes 'main_scene', 'HUD' becomes 'hud', 'HTTPServer' becomes 'http_server', and
+/// 'Level2Boss' becomes 'level2_boss'.
+///
+/// # Arguments
+///
+/// `class_name` - The class name of the module.
+pub fn snake_case(class_name: &str) -> String {
+    let chars: Vec<char> = class_name.chars().collect();
+    let mut snake = String::new();
+
+    for (index, &c) in chars.iter().enumerate() {
+        if c == '_' || c == '-' || c.is_whitespace() {
+            if !snake.is_empty() && !snake.ends_with('_') {
+                snake.push('_');
+            }
+            continue;
+        }
+
+        if c.is_uppercase() && index > 0 {
+            let prev = chars[index - 1];
+            let next_is_lower = chars
+                .get(index + 1)
+                .map(|n| n.is_lowercase())
+                .unwrap_or(false);
+
+            let is_boundary = prev.is_lowercase()
+                || (prev.is_uppercase() && next_is_lower)
+                || (prev.is_ascii_digit() && next_is_lower);
+
+            if is_boundary && !snake.ends_with('_') {
+                snake.push('_');
+            }
+        }
+
+        snake.extend(c.to_lowercase());
+    }
+
+    return snake.trim_end_matches('_').to_string();
+}
+
+/// Returns the name of the module's Rust file, e.g. 'main_scene.rs'.
+///
+/// # Arguments
+///
+/// `class_name` - The class name of the module.
+pub fn mod_file_name(class_name: &str) -> String {
+    return format!("{}.rs", snake_case(class_name));
+}
+
+/// Returns the identifier used to declare the module in the lib file, e.g. 'main_scene'. If the snake_case name of
+/// the module is a Rust keyword then it is written as a raw identifier.
+///
+/// # Arguments
+///
+/// `class_name` - The class name of the module.
+pub fn mod_name(class_name: &str) -> String {
+    let snake = snake_case(class_name);
+
+    if RUST_KEYWORDS.contains(&snake.as_str()) {
+        return format!("r#{}", snake);
+    }
+
+    return snake;
+}
+
+/// Returns the name of the module's .gdns file, e.g. 'main_scene.gdns'.
+///
+/// # Arguments
+///
+/// `class_name` - The class name of the module.
+pub fn gdns_file_name(class_name: &str) -> String {
+    return format!("{}.gdns", snake_case(class_name));
+}
+
+/// Returns the name that Godot uses to refer to the module's class, which is the class name with the library's class
+/// name prefix added to it. Names that already start with the prefix followed by a capitalized word are left as they
+/// are.
+///
+/// # Arguments
+///
+/// `class_name` - The class name of the module.
+/// `prefix` - The library's class name prefix, if it has one.
+pub fn godot_class_name(class_name: &str, prefix: Option<&str>) -> String {
+    return match prefix {
+        Some(prefix)
+            if class_name.starts_with(prefix)
+                && class_name[prefix.len()..]
+                    .chars()
+                    .next()
+                    .map(|c| c.is_ascii_uppercase())
+                    .unwrap_or(false) =>
+        {
+            class_name.to_string()
+        }
+        Some(prefix) => format!("{}{}", prefix, class_name),
+        None => class_name.to_string(),
+    };
+}
+
+/// The names of the files in `src` that a module's file can't replace.
+const RESERVED_FILE_NAMES: &[&str] = &["lib", "main"];
+
+/// Checks that a class name can be used for a new module and returns a message that explains the problem if it can't.
+/// The name has to be a Rust identifier that starts with a capital letter, it can't be a Rust keyword, its module
+/// can't collide with the lib file or the library's crate, and the name that Godot uses for the class can't be a
+/// class that's built into Godot.
+///
+/// # Arguments
+///
+/// `class_name` - The class name of the module.
+/// `crate_name` - The name of the library's crate, if modules can't share its name.
+/// `prefix` - The library's class name prefix, if it has one.
+pub fn validate_class_name(
+    class_name: &str,
+    crate_name: Option<&str>,
+    prefix: Option<&str>,
+) -> Result<(), String> {
+    if !class_name
+        .chars()
+        .next()
+        .map(|c| c.is_ascii_uppercase())
+        .unwrap_or(false)
+    {
+        return Err(format!(
+            "{} is not a valid class name, class names have to start with a capital letter",
+            class_name
+        ));
+    }
+    if !class_name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        return Err(format!(
+            "{} is not a valid class name, class names can only contain letters, numbers, and underscores",
+            class_name
+        ));
+    }
+    if is_rust_keyword(class_name) {
+        return Err(format!(
+            "{} can't be used as a class name because it's a Rust keyword",
+            class_name
+        ));
+    }
+
+    let snake = snake_case(class_name);
+    if RESERVED_PATH_KEYWORDS.contains(&snake.as_str()) {
+        return Err(format!(
+            "{} can't be used as a class name because its module would be named {}, which is a Rust keyword",
+            class_name, snake
+        ));
+    }
+    if RESERVED_FILE_NAMES.contains(&snake.as_str()) {
+        return Err(format!(
+            "{} can't be used as a class name because its module file would replace src/{}.rs",
+            class_name, snake
+        ));
+    }
+    if let Some(crate_name) = crate_name.filter(|c| snake == c.replace('-', "_")) {
+        return Err(format!(
+            "{} can't be used as a class name because its module would have the same name as the {} crate",
+            class_name, crate_name
+        ));
+    }
+
+    let godot_name = godot_class_name(class_name, prefix);
+    if godot_classes::is_builtin_class(&godot_name) {
+        return Err(format!(
+            "{} can't be used as a class name because Godot already has a class named {}, use a different name or set a class_prefix in godot-rust-helper.toml",
+            class_name, godot_name
+        ));
+    }
+
+    return Ok(());
+}
+
+/// Checks that a class name prefix can be put at the start of a class name and returns a message that explains the
+/// problem if it can't.
+///
+/// # Arguments
+///
+/// `prefix` - The class name prefix.
+pub fn validate_class_prefix(prefix: &str) -> Result<(), String> {
+    let is_valid = prefix
+        .chars()
+        .next()
+        .map(|c| c.is_ascii_uppercase())
+        .unwrap_or(false)
+        && prefix
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !is_valid {
+        return Err(format!(
+            "{} is not a valid class prefix, prefixes have to start with a capital letter and can only contain letters, numbers, and underscores",
+            prefix
+        ));
+    }
+
+    return Ok(());
+}
+
+/// Checks that a library's directory name can be used as the name of its crate and returns a message that explains
+/// the problem if it can't.
+///
+/// # Arguments
+///
+/// `crate_name` - The name of the crate.
+pub fn validate_crate_name(crate_name: &str) -> Result<(), String> {
+    let is_valid = crate_name
+        .chars()
+        .next()
+        .map(|c| c.is_ascii_alphabetic() || c == '_')
+        .unwrap_or(false)
+        && crate_name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if !is_valid {
+        return Err(format!(
+            "{} is not a valid crate name, crate names have to start with a letter or underscore and can only contain letters, numbers, dashes, and underscores",
+            crate_name
+        ));
+    }
+    if is_rust_keyword(crate_name) {
+        return Err(format!(
+            "{} is a Rust keyword so it can't be used as a crate name",
+            crate_name
+        ));
+    }
+
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snake_case_splits_words() {
+        assert_eq!(snake_case("MainScene"), "main_scene");
+        assert_eq!(snake_case("HUD"), "hud");
+        assert_eq!(snake_case("Level2Boss"), "level2_boss");
+        assert_eq!(snake_case("HTTPServer"), "http_server");
+        assert_eq!(snake_case("Player2D"), "player2d");
+        assert_eq!(snake_case("Main_Scene"), "main_scene");
+    }
+
+    #[test]
+    fn file_names_use_snake_case() {
+        assert_eq!(mod_file_name("HTTPServer"), "http_server.rs");
+        assert_eq!(mod_file_name("Player2D"), "player2d.rs");
+        assert_eq!(gdns_file_name("HUD"), "hud.gdns");
+        assert_eq!(gdns_file_name("Level2Boss"), "level2_boss.gdns");
+    }
+
+    #[test]
+    fn mod_name_escapes_keywords() {
+        assert_eq!(mod_name("Level2Boss"), "level2_boss");
+        assert_eq!(mod_name("Match"), "r#match");
+        assert_eq!(mod_name("Type"), "r#type");
+        assert_eq!(mod_name("Async"), "r#async");
+    }
+
+    #[test]
+    fn validate_class_name_accepts_tricky_names() {
+        assert_eq!(validate_class_name("HUD", None, None), Ok(()));
+        assert_eq!(validate_class_name("Level2Boss", None, None), Ok(()));
+        assert_eq!(validate_class_name("HTTPServer", None, None), Ok(()));
+        assert_eq!(validate_class_name("Player2D", None, None), Ok(()));
+        assert_eq!(validate_class_name("Match", None, None), Ok(()));
+    }
+
+    #[test]
+    fn validate_class_name_rejects_invalid_names() {
+        assert!(validate_class_name("player", None, None).is_err());
+        assert!(validate_class_name("2Player", None, None).is_err());
+        assert!(validate_class_name("Main-Scene", None, None).is_err());
+        assert!(validate_class_name("Self", None, None).is_err());
+        assert!(validate_class_name("Crate", None, None).is_err());
+        assert!(validate_class_name("Super", None, None).is_err());
+        assert!(validate_class_name("Lib", None, None).is_err());
+        assert!(validate_class_name("Node2D", None, None).is_err());
+    }
+
+    #[test]
+    fn godot_class_name_adds_prefix() {
+        assert_eq!(godot_class_name("Timer", None), "Timer");
+        assert_eq!(godot_class_name("Timer", Some("Rs")), "RsTimer");
+        assert_eq!(godot_class_name("RsTimer", Some("Rs")), "RsTimer");
+        assert_eq!(godot_class_name("Rsvp", Some("Rs")), "RsRsvp");
+    }
+
+    #[test]
+    fn validate_class_name_checks_prefixed_name() {
+        assert!(validate_class_name("Timer", None, None).is_err());
+        assert_eq!(validate_class_name("Timer", None, Some("Rs")), Ok(()));
+        assert_eq!(validate_class_name("Player", None, Some("Rs")), Ok(()));
+        assert!(validate_class_name("Body", None, Some("Kinematic")).is_err());
+    }
+
+    #[test]
+    fn validate_class_name_rejects_crate_name() {
+        assert!(
+            validate_class_name("PlatformerModules", Some("platformer_modules"), None).is_err()
+        );
+        assert!(
+            validate_class_name("PlatformerModules", Some("platformer-modules"), None).is_err()
+        );
+        assert_eq!(
+            validate_class_name("Platformer", Some("platformer_modules"), None),
+            Ok(())
+        );
+        assert_eq!(validate_class_name("PlatformerModules", None, None), Ok(()));
+    }
+}
@@ -0,0 +1,291 @@
+#![allow(clippy::needless_return)]
+
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+use proc_macro2::{LineColumn, Span};
+use syn::visit::{self, Visit};
+
+use crate::utils;
+
+/// A struct that derives `NativeClass` that was found in the library's source files.
+#[derive(Debug)]
+pub struct NativeClass {
+    /// The name of the struct.
+    pub name: String,
+    /// The Godot class that the struct inherits from, taken from its `#[inherit]` attribute.
+    pub base: Option<String>,
+    /// The path of the Rust module that contains the struct relative to the crate root, e.g. `enemies::goblin`. This
+    /// is empty if the struct is in the crate root.
+    pub mod_path: String,
+    /// The file that contains the struct.
+    pub file: PathBuf,
+}
+
+/// Returns all of the structs that derive `NativeClass` in the .rs files of a library's src directory.
+///
+/// # Arguments
+///
+/// `src_dir` - The src directory of the library.
+pub fn find_native_classes(src_dir: &Path) -> Vec<NativeClass> {
+    let mut classes = vec![];
+
+    for file in utils::find_files_with_extensions(src_dir, &["rs"]) {
+        let file_string = match read_to_string(&file) {
+            Ok(v) => v,
+            Err(_e) => continue,
+        };
+        let syntax = match syn::parse_file(&file_string) {
+            Ok(v) => v,
+            Err(e) => {
+                println!("Unable to parse {}: {}", file.display(), e);
+                continue;
+            }
+        };
+
+        let mod_path = file_mod_path(src_dir, &file);
+        find_native_classes_in_items(&syntax.items, &mod_path, &file, &mut classes);
+    }
+
+    return classes;
+}
+
+/// Returns the path of the Rust module that a file declares relative to the crate root.
+/// For example `src/lib.rs` is the crate root, `src/player.rs` is `player`, and `src/enemies/goblin.rs` is `enemies::goblin`.
+///
+/// # Arguments
+///
+/// `src_dir` - The src directory of the library.
+/// `file` - The .rs file to get the module path of.
+pub fn file_mod_path(src_dir: &Path, file: &Path) -> String {
+    let relative_path = file.strip_prefix(src_dir).unwrap_or(file);
+    let mut segments: Vec<String> = relative_path
+        .with_extension("")
+        .iter()
+        .map(|s| s.to_string_lossy().to_string())
+        .collect();
+
+    if segments.len() == 1 && (segments[0] == "lib" || segments[0] == "main") {
+        segments.clear();
+    } else if segments.last().map(|s| s == "mod").unwrap_or(false) {
+        segments.pop();
+    }
+
+    return segments.join("::");
+}
+
+/// Adds every struct that derives `NativeClass` in a list of items to the list of classes, including the items of
+/// any inline modules.
+///
+/// # Arguments
+///
+/// `items` - The items to search.
+/// `mod_path` - The path of the module that contains the items.
+/// `file` - The file that contains the items.
+/// `classes` - The list of classes to add to.
+fn find_native_classes_in_items(
+    items: &[syn::Item],
+    mod_path: &str,
+    file: &Path,
+    classes: &mut Vec<NativeClass>,
+) {
+    for item in items {
+        match item {
+            syn::Item::Struct(item_struct) => {
+                if !derives_native_class(&item_struct.attrs) {
+                    continue;
+                }
+
+                classes.push(NativeClass {
+                    name: item_struct.ident.to_string(),
+                    base: inherited_class(&item_struct.attrs),
+                    mod_path: mod_path.to_string(),
+                    file: file.to_owned(),
+                });
+            }
+            syn::Item::Mod(item_mod) => {
+                if let Some((_, inline_items)) = &item_mod.content {
+                    let inline_mod_path = if mod_path.is_empty() {
+                        item_mod.ident.to_string()
+                    } else {
+                        format!("{}::{}", mod_path, item_mod.ident)
+                    };
+                    find_native_classes_in_items(inline_items, &inline_mod_path, file, classes);
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Returns whether a list of attributes contains a `#[derive(NativeClass)]`.
+///
+/// # Arguments
+///
+/// `attrs` - The attributes of the struct.
+fn derives_native_class(attrs: &[syn::Attribute]) -> bool {
+    return attrs.iter().any(|attr| {
+        if !attr.path.is_ident("derive") {
+            return false;
+        }
+
+        match attr.parse_meta() {
+            Ok(syn::Meta::List(list)) => list.nested.iter().any(|nested| match nested {
+                syn::NestedMeta::Meta(meta) => meta
+                    .path()
+                    .segments
+                    .last()
+                    .map(|s| s.ident == "NativeClass")
+                    .unwrap_or(false),
+                _ => false,
+            }),
+            _ => false,
+        }
+    });
+}
+
+/// Returns the class from a struct's `#[inherit]` attribute if it has one.
+///
+/// # Arguments
+///
+/// `attrs` - The attributes of the struct.
+fn inherited_class(attrs: &[syn::Attribute]) -> Option<String> {
+    for attr in attrs {
+        if !attr.path.is_ident("inherit") {
+            continue;
+        }
+
+        if let Ok(path) = attr.parse_args::<syn::Path>() {
+            return path.segments.last().map(|s| s.ident.to_string());
+        }
+    }
+
+    return None;
+}
+
+/// Returns the names of the modules that are declared in a file with `mod name;`, without any `r#` prefix. Inline
+/// modules aren't included since they don't have a file of their own.
+///
+/// # Arguments
+///
+/// `file_string` - The contents of the .rs file.
+pub fn declared_mods(file_string: &str) -> Result<Vec<String>, String> {
+    let syntax = syn::parse_file(file_string).map_err(|e| e.to_string())?;
+
+    let mods = syntax
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            syn::Item::Mod(item_mod) if item_mod.content.is_none() => Some(
+                item_mod
+                    .ident
+                    .to_string()
+                    .trim_start_matches("r#")
+                    .to_string(),
+            ),
+            _ => None,
+        })
+        .collect();
+
+    return Ok(mods);
+}
+
+/// Visits a parsed source file and collects the spans of the identifiers that refer to a struct.
+struct StructRename<'a> {
+    /// The current name of the struct.
+    name: &'a str,
+    /// The spans of the identifiers to rename.
+    spans: Vec<Span>,
+}
+
+impl<'a, 'ast> Visit<'ast> for StructRename<'a> {
+    fn visit_item_struct(&mut self, item: &'ast syn::ItemStruct) {
+        if item.ident == self.name {
+            self.spans.push(item.ident.span());
+        }
+        visit::visit_item_struct(self, item);
+    }
+
+    // Impl self types, field and parameter types, and constructors like `Player` or `Player { .. }` all refer to the
+    // struct through a path that starts with its name. Paths like `Kind::Player` refer to something else.
+    fn visit_path(&mut self, path: &'ast syn::Path) {
+        if path.leading_colon.is_none() {
+            if let Some(first) = path.segments.first() {
+                if first.ident == self.name {
+                    self.spans.push(first.ident.span());
+                }
+            }
+        }
+        visit::visit_path(self, path);
+    }
+
+    // The struct is also the type argument of the `user_data` wrapper, which syn doesn't parse as part of the attribute.
+    fn visit_attribute(&mut self, attr: &'ast syn::Attribute) {
+        if attr.path.is_ident("user_data") {
+            if let Ok(user_data) = attr.parse_args::<syn::Type>() {
+                self.visit_type(&user_data);
+            }
+        }
+    }
+}
+
+/// Renames a struct in the contents of a .rs file along with its impl blocks and the places that use it as a type or
+/// create it. Comments, strings, and other items that happen to have the same name are left alone.
+///
+/// # Arguments
+///
+/// `source` - The contents of the .rs file.
+/// `name` - The current name of the struct.
+/// `new_name` - The name to rename the struct to.
+pub fn rename_struct(source: &str, name: &str, new_name: &str) -> Result<String, String> {
+    let file = syn::parse_file(source).map_err(|e| e.to_string())?;
+
+    let mut rename = StructRename {
+        name,
+        spans: vec![],
+    };
+    rename.visit_file(&file);
+
+    let mut line_starts = vec![0];
+    line_starts.extend(source.match_indices('\n').map(|(i, _)| i + 1));
+
+    // The identifiers are replaced from the end of the file so that the offsets of the earlier ones stay the same.
+    let mut ranges: Vec<(usize, usize)> = rename
+        .spans
+        .iter()
+        .map(|span| {
+            (
+                source_offset(source, &line_starts, span.start()),
+                source_offset(source, &line_starts, span.end()),
+            )
+        })
+        .collect();
+    ranges.sort();
+    ranges.dedup();
+
+    let mut new_source = source.to_string();
+    for (start, end) in ranges.into_iter().rev() {
+        new_source.replace_range(start..end, new_name);
+    }
+
+    return Ok(new_source);
+}
+
+/// Returns the byte offset of a line and column from a span, where the column is counted in characters.
+///
+/// # Arguments
+///
+/// `source` - The contents of the file.
+/// `line_starts` - The byte offset of the start of each line in the file.
+/// `position` - The line and column.
+fn source_offset(source: &str, line_starts: &[usize], position: LineColumn) -> usize {
+    let line_start = line_starts[position.line - 1];
+    let line = &source[line_start..];
+
+    return line_start
+        + line
+            .char_indices()
+            .nth(position.column)
+            .map(|(i, _)| i)
+            .unwrap_or_else(|| line.len());
+}
@@ -0,0 +1,233 @@
+#![allow(clippy::needless_return)]
+
+/// A Godot scene (.tscn) that can be edited without changing the parts of the file that aren't touched.
+#[derive(Debug)]
+pub struct SceneFile {
+    /// The lines of the file.
+    lines: Vec<String>,
+}
+
+/// A node in a scene along with the lines that it's on.
+#[derive(Debug)]
+pub struct SceneNode {
+    /// The name of the node.
+    pub name: String,
+    /// The Godot class of the node. Nodes that are instances of other scenes don't have one.
+    pub node_type: Option<String>,
+    /// The index of the line with the node's header.
+    header: usize,
+    /// The index of the line after the node's last property.
+    end: usize,
+}
+
+impl SceneFile {
+    /// Parses the contents of a scene.
+    ///
+    /// Returns an error if the file doesn't start with a `gd_scene` header.
+    ///
+    /// # Arguments
+    ///
+    /// `contents` - The contents of the file.
+    pub fn parse(contents: &str) -> Result<SceneFile, String> {
+        let lines: Vec<String> = contents.split('\n').map(|l| l.to_string()).collect();
+        if !lines
+            .iter()
+            .find(|l| !l.trim().is_empty())
+            .map(|l| l.trim_start().starts_with("[gd_scene"))
+            .unwrap_or(false)
+        {
+            return Err("the file doesn't start with a gd_scene header".to_string());
+        }
+
+        return Ok(SceneFile { lines });
+    }
+
+    /// Returns the node at a NodePath relative to the scene's root node, e.g. `.` for the root node or `Player/Sprite`
+    /// for a grandchild of the root node.
+    ///
+    /// # Arguments
+    ///
+    /// `node_path` - The path to the node.
+    pub fn find_node(&self, node_path: &str) -> Option<SceneNode> {
+        let node_path = node_path.trim_matches('/');
+        let (parent, name) = match node_path.rfind('/') {
+            Some(i) => (&node_path[..i], &node_path[i + 1..]),
+            None => (".", node_path),
+        };
+
+        for (index, line) in self.lines.iter().enumerate() {
+            if !line.starts_with("[node ") {
+                continue;
+            }
+
+            let node_parent = header_attribute(line, "parent");
+            let is_match = if node_path.is_empty() || node_path == "." {
+                node_parent.is_none()
+            } else {
+                node_parent.as_deref() == Some(parent)
+                    && header_attribute(line, "name").as_deref() == Some(name)
+            };
+            if !is_match {
+                continue;
+            }
+
+            let mut end = index + 1;
+            while end < self.lines.len() && !self.lines[end].starts_with('[') {
+                end += 1;
+            }
+            while end > index + 1 && self.lines[end - 1].trim().is_empty() {
+                end -= 1;
+            }
+
+            return Some(SceneNode {
+                name: header_attribute(line, "name").unwrap_or_default(),
+                node_type: header_attribute(line, "type"),
+                header: index,
+                end,
+            });
+        }
+
+        return None;
+    }
+
+    /// Returns the id of the external resource with a path, adding the resource if the scene doesn't have it yet.
+    /// Adding a resource also increases the scene's `load_steps`.
+    ///
+    /// # Arguments
+    ///
+    /// `path` - The `res://` path of the resource.
+    /// `resource_type` - The type of the resource, e.g. `Script`.
+    pub fn ext_resource(&mut self, path: &str, resource_type: &str) -> u32 {
+        let mut last_index = None;
+        let mut max_id = 0;
+
+        for (index, line) in self.lines.iter().enumerate() {
+            if !line.starts_with("[ext_resource ") {
+                continue;
+            }
+
+            let id = header_attribute(line, "id")
+                .and_then(|id| id.parse::<u32>().ok())
+                .unwrap_or(0);
+            if header_attribute(line, "path").as_deref() == Some(path) {
+                return id;
+            }
+
+            max_id = max_id.max(id);
+            last_index = Some(index);
+        }
+
+        let id = max_id + 1;
+        let resource_line = format!(
+            "[ext_resource path=\"{}\" type=\"{}\" id={}]",
+            path, resource_type, id
+        );
+        match last_index {
+            Some(index) => self.lines.insert(index + 1, resource_line),
+            None => {
+                // The first external resource goes right after the header with a blank line on both sides.
+                let header_index = self
+                    .lines
+                    .iter()
+                    .position(|l| l.trim_start().starts_with("[gd_scene"))
+                    .unwrap_or(0);
+                self.lines.splice(
+                    header_index + 1..header_index + 1,
+                    vec![String::new(), resource_line],
+                );
+            }
+        }
+
+        self.increase_load_steps();
+
+        return id;
+    }
+
+    /// Sets a property of a node, replacing the property if the node already has it.
+    ///
+    /// # Arguments
+    ///
+    /// `node` - The node to set the property of.
+    /// `property` - The name of the property.
+    /// `value` - The value as it should be written in the file, e.g. `ExtResource( 1 )`.
+    pub fn set_node_property(&mut self, node: &SceneNode, property: &str, value: &str) {
+        let property_line = format!("{} = {}", property, value);
+
+        for index in node.header + 1..node.end {
+            let line = &self.lines[index];
+            if line.split('=').next().map(|k| k.trim()) == Some(property) {
+                self.lines[index] = property_line;
+                return;
+            }
+        }
+
+        self.lines.insert(node.end, property_line);
+    }
+
+    /// Returns the value of a node's property as it's written in the file.
+    ///
+    /// # Arguments
+    ///
+    /// `node` - The node to get the property of.
+    /// `property` - The name of the property.
+    pub fn node_property(&self, node: &SceneNode, property: &str) -> Option<String> {
+        return self.lines[node.header + 1..node.end]
+            .iter()
+            .find(|l| l.split('=').next().map(|k| k.trim()) == Some(property))
+            .and_then(|l| l.find('=').map(|i| l[i + 1..].trim().to_string()));
+    }
+
+    /// Increases the `load_steps` in the scene's header by one. Scenes without any resources don't have `load_steps`
+    /// so it's added to them.
+    fn increase_load_steps(&mut self) {
+        let header_index = match self
+            .lines
+            .iter()
+            .position(|l| l.trim_start().starts_with("[gd_scene"))
+        {
+            Some(v) => v,
+            None => return,
+        };
+        let header = &self.lines[header_index];
+
+        self.lines[header_index] = match header_attribute(header, "load_steps") {
+            Some(load_steps) => {
+                let steps = load_steps.parse::<u32>().unwrap_or(1);
+                header.replacen(
+                    &format!("load_steps={}", load_steps),
+                    &format!("load_steps={}", steps + 1),
+                    1,
+                )
+            }
+            None => header.replacen("[gd_scene", "[gd_scene load_steps=2", 1),
+        };
+    }
+}
+
+impl std::fmt::Display for SceneFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        return write!(f, "{}", self.lines.join("\n"));
+    }
+}
+
+/// Returns the value of an attribute in a header such as `[node name="Player" type="KinematicBody2D"]` without any
+/// quotes around it.
+///
+/// # Arguments
+///
+/// `header` - The header line.
+/// `attribute` - The name of the attribute, e.g. `type`.
+fn header_attribute(header: &str, attribute: &str) -> Option<String> {
+    let pattern = format!(" {}=", attribute);
+    let start = header.find(&pattern)? + pattern.len();
+    let rest = &header[start..];
+
+    let value = if let Some(quoted) = rest.strip_prefix('"') {
+        &quoted[..quoted.find('"')?]
+    } else {
+        let end = rest.find([' ', ']']).unwrap_or(rest.len());
+        &rest[..end]
+    };
+
+    return Some(value.to_string());
+}
@@ -1,8 +1,11 @@
-extern crate regex;
+#![allow(clippy::needless_return)]
 
 use std::env;
 use std::io;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use convert_case::{Case, Casing};
 extern crate dunce;
 
 /// Returns the path of the specified file.
@@ -31,27 +34,6 @@ pub fn find_file(file_to_find: String) -> std::path::PathBuf {
     return dir_to_check.to_owned();
 }
 
-/// Splits a string on capitals, joins it back together with an underscore, and finally converts it to lowercase.
-///
-/// # Arguments
-///
-/// `str_to_format` - The string to format.
-pub fn format_str(str_to_format: String) -> String {
-    let mut ret = String::new();
-    let mut count = 1;
-
-    let re = regex::Regex::new(r"([A-Z][a-z]+)").expect("Unable to create regex pattern");
-    for field in re.find_iter(&str_to_format) {
-        if count > 1 {
-            ret.push_str("_")
-        }
-        ret.push_str(&field.as_str().to_lowercase());
-        count = count + 1;
-    }
-
-    return ret;
-}
-
 /// Returns the absolute path of a relative path.
 ///
 /// # Arguments
@@ -83,6 +65,119 @@ where
     Ok(absolute_path)
 }
 
+/// Returns the paths of all of the files within a directory, and its subdirectories, that have one of the
+/// specified extensions. Hidden directories, such as Godot's `.import` directory, are skipped.
+///
+/// # Arguments
+///
+/// `dir` - The directory to search.
+/// `extensions` - The file extensions to look for, without the leading dot.
+pub fn find_files_with_extensions(dir: &Path, extensions: &[&str]) -> Vec<PathBuf> {
+    let mut files = vec![];
+
+    let entries = match std::fs::read_dir(dir) {
+        Ok(v) => v,
+        Err(_e) => return files,
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let is_hidden = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .map(|n| n.starts_with('.'))
+            .unwrap_or(false);
+
+        if path.is_dir() {
+            if !is_hidden {
+                files.append(&mut find_files_with_extensions(&path, extensions));
+            }
+        } else if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+            if extensions.contains(&ext) {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort();
+
+    return files;
+}
+
+/// Returns the paths of all of the files within a directory, and its subdirectories, that have one of the
+/// specified extensions and contain the specified text.
+///
+/// # Arguments
+///
+/// `dir` - The directory to search.
+/// `extensions` - The file extensions to look for, without the leading dot.
+/// `text` - The text to look for in each file.
+pub fn find_files_containing(dir: &Path, extensions: &[&str], text: &str) -> Vec<PathBuf> {
+    return find_files_with_extensions(dir, extensions)
+        .into_iter()
+        .filter(|path| match std::fs::read_to_string(path) {
+            Ok(contents) => contents.contains(text),
+            Err(_e) => false,
+        })
+        .collect();
+}
+
+/// Returns the `res://` path that Godot uses to refer to a file inside of the Godot project.
+///
+/// # Arguments
+///
+/// `godot_path` - The path to the Godot project.
+/// `file_path` - The absolute path to the file inside of the Godot project.
+pub fn godot_resource_path(godot_path: &Path, file_path: &Path) -> String {
+    let relative_path =
+        pathdiff::diff_paths(file_path, godot_path).expect("Unable to get resource path diff");
+    let relative_path_str = relative_path
+        .to_str()
+        .expect("Unable to convert resource path to str")
+        .replace("\\", "/");
+
+    return format!("res://{}", relative_path_str);
+}
+
+/// Returns whether a path is relative and stays inside of the directory that it's relative to, which is used to make
+/// sure that files passed to commands end up inside of the Godot project.
+///
+/// # Arguments
+///
+/// `path` - The path to check.
+pub fn is_contained_relative_path(path: &Path) -> bool {
+    return path.is_relative()
+        && path.components().all(|c| {
+            matches!(
+                c,
+                std::path::Component::Normal(_) | std::path::Component::CurDir
+            )
+        });
+}
+
+/// Returns the name of the dynamic library file that `cargo build` creates for a library.
+///
+/// # Arguments
+///
+/// `lib_name` - The name of the library.
+pub fn dynamic_library_file_name(lib_name: &str) -> String {
+    let ext = get_dynamic_library_ext();
+    let extra = if cfg!(windows) { "" } else { "lib" };
+
+    let file = format!("{}{}.{}", extra, lib_name, ext);
+
+    return file.to_case(Case::Snake);
+}
+
+/// Returns the time that a file was last modified, or `None` if the file doesn't exist.
+///
+/// # Arguments
+///
+/// `path` - The path to the file.
+pub fn modified_time(path: &Path) -> Option<SystemTime> {
+    return std::fs::metadata(path).and_then(|m| m.modified()).ok();
+}
+
 /// Returns the build file extension for windows.
 #[cfg(target_os = "windows")]
 pub fn get_dynamic_library_ext() -> &'static str {
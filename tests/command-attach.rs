@@ -0,0 +1,205 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use assert_cmd::prelude::*;
+
+use std::env::set_current_dir;
+use std::error::Error;
+use std::fs::{create_dir_all, read_to_string, write};
+use std::process::Command;
+
+mod test_utilities;
+use test_utilities::{cleanup_test_files, init_test};
+
+const LEVEL_SCENE: &str = "[gd_scene load_steps=2 format=2]\n\n[ext_resource path=\"res://tiles.tres\" type=\"TileSet\" id=1]\n\n[node name=\"Level\" type=\"Node2D\"]\n\n[node name=\"Tiles\" type=\"TileMap\" parent=\".\"]\ntile_set = ExtResource( 1 )\n\n[node name=\"Spawner\" type=\"Timer\" parent=\"Tiles\"]\nwait_time = 2.0\n";
+
+// It should add the module's gdns as a resource of the scene and set it as the script of the given node.
+#[test]
+fn attach_module_to_node() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--output-path")
+    .arg("platformer/godot-rust-helper-output")
+    .arg("--nativescript-path")
+    .arg("platformer/godot-rust-helper-scripts");
+
+  cmd.assert().success();
+
+  create_dir_all("platformer/levels")?;
+  write("platformer/levels/level.tscn", LEVEL_SCENE)?;
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Spawner")
+    .output()
+    .expect("Unable to execute cargo run");
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("attach")
+    .arg("Spawner")
+    .arg("levels/level.tscn")
+    .arg("--node")
+    .arg("Tiles/Spawner")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let scene_file =
+    read_to_string("../platformer/levels/level.tscn").expect("Unable to read scene file");
+
+  assert_eq!(output.status.success(), true);
+  assert_eq!(
+    scene_file,
+    "[gd_scene load_steps=3 format=2]\n\n[ext_resource path=\"res://tiles.tres\" type=\"TileSet\" id=1]\n[ext_resource path=\"res://godot-rust-helper-scripts/spawner.gdns\" type=\"Script\" id=2]\n\n[node name=\"Level\" type=\"Node2D\"]\n\n[node name=\"Tiles\" type=\"TileMap\" parent=\".\"]\ntile_set = ExtResource( 1 )\n\n[node name=\"Spawner\" type=\"Timer\" parent=\"Tiles\"]\nwait_time = 2.0\nscript = ExtResource( 2 )\n"
+  );
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should refuse to attach the module to a node that isn't the class that the module inherits from unless forced.
+#[test]
+fn attach_module_type_mismatch() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--output-path")
+    .arg("platformer/godot-rust-helper-output")
+    .arg("--nativescript-path")
+    .arg("platformer/godot-rust-helper-scripts");
+
+  cmd.assert().success();
+
+  write(
+    "platformer/main.tscn",
+    "[gd_scene format=2]\n\n[node name=\"Main\" type=\"Node2D\"]\n",
+  )?;
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Level")
+    .output()
+    .expect("Unable to execute cargo run");
+  write(
+    "src/level.rs",
+    read_to_string("src/level.rs")?.replace("#[inherit(Node)]", "#[inherit(Spatial)]"),
+  )?;
+
+  let failed_output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("attach")
+    .arg("Level")
+    .arg("res://main.tscn")
+    .output()
+    .expect("Unable to execute cargo run");
+  let scene_file_after_failure =
+    read_to_string("../platformer/main.tscn").expect("Unable to read scene file");
+
+  let forced_output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("attach")
+    .arg("Level")
+    .arg("res://main.tscn")
+    .arg("--force")
+    .output()
+    .expect("Unable to execute cargo run");
+  let scene_file = read_to_string("../platformer/main.tscn").expect("Unable to read scene file");
+
+  assert_eq!(failed_output.status.success(), false);
+  assert_eq!(
+    scene_file_after_failure,
+    "[gd_scene format=2]\n\n[node name=\"Main\" type=\"Node2D\"]\n"
+  );
+  assert_eq!(forced_output.status.success(), true);
+  assert_eq!(
+    scene_file,
+    "[gd_scene load_steps=2 format=2]\n\n[ext_resource path=\"res://godot-rust-helper-scripts/level.gdns\" type=\"Script\" id=1]\n\n[node name=\"Main\" type=\"Node2D\"]\nscript = ExtResource( 1 )\n"
+  );
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should check the node against the base class that was saved in the config for an imported module.
+#[test]
+fn attach_imported_module_type_mismatch() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--output-path")
+    .arg("platformer/godot-rust-helper-output")
+    .arg("--nativescript-path")
+    .arg("platformer/godot-rust-helper-scripts");
+
+  cmd.assert().success();
+
+  write(
+    "platformer/main.tscn",
+    "[gd_scene format=2]\n\n[node name=\"Main\" type=\"Node2D\"]\n",
+  )?;
+  write(
+    "platformer_modules/src/enemies.rs",
+    "use gdnative::prelude::*;\n\n#[derive(NativeClass)]\n#[inherit(Spatial)]\npub struct Goblin;\n",
+  )?;
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("import")
+    .output()
+    .expect("Unable to execute cargo run");
+  let config_file = read_to_string("godot-rust-helper.toml").expect("Unable to read config file");
+
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("attach")
+    .arg("Goblin")
+    .arg("res://main.tscn")
+    .output()
+    .expect("Unable to execute cargo run");
+  let stdout = String::from_utf8_lossy(&output.stdout);
+
+  assert_eq!(
+    config_file.contains("[modules.Goblin]\npath = \"enemies\"\nbase = \"Spatial\"\n"),
+    true
+  );
+  assert_eq!(output.status.success(), false);
+  assert_eq!(
+    stdout.contains("Goblin inherits from Spatial but the Main node is a Node2D"),
+    true
+  );
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
@@ -0,0 +1,179 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use assert_cmd::prelude::*;
+
+use std::env::set_current_dir;
+use std::error::Error;
+use std::fs::{read_to_string, write};
+use std::path::Path;
+use std::process::Command;
+
+mod test_utilities;
+use test_utilities::{cleanup_test_files, init_test};
+
+const PROJECT_FILE: &str = "; Engine configuration file.\n\nconfig_version=4\n\n[application]\n\nconfig/name=\"Platformer\"\n\n[input]\n\njump={\n\"deadzone\": 0.5,\n\"events\": [  ]\n}\n";
+
+// It should add the module created with --autoload to the autoloads and keep the rest of project.godot intact.
+#[test]
+fn autoload_create_module() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  write("platformer/project.godot", PROJECT_FILE)?;
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--output-path")
+    .arg("platformer/godot-rust-helper-output")
+    .arg("--nativescript-path")
+    .arg("platformer/godot-rust-helper-scripts");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("GameManager")
+    .arg("--autoload")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let project_file =
+    read_to_string("../platformer/project.godot").expect("Unable to read project file");
+
+  assert_eq!(
+    project_file,
+    "; Engine configuration file.\n\nconfig_version=4\n\n[application]\n\nconfig/name=\"Platformer\"\n\n[autoload]\n\nGameManager=\"*res://godot-rust-helper-scripts/game_manager.gdns\"\n\n[input]\n\njump={\n\"deadzone\": 0.5,\n\"events\": [  ]\n}\n"
+  );
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should add and remove a module's autoload with the autoload command.
+#[test]
+fn autoload_add_and_remove() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  write("platformer/project.godot", PROJECT_FILE)?;
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--output-path")
+    .arg("platformer/godot-rust-helper-output")
+    .arg("--nativescript-path")
+    .arg("platformer/godot-rust-helper-scripts");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Audio")
+    .output()
+    .expect("Unable to execute cargo run");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("autoload")
+    .arg("Audio")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let project_file =
+    read_to_string("../platformer/project.godot").expect("Unable to read project file");
+
+  assert_eq!(
+    project_file
+      .contains("[autoload]\n\nAudio=\"*res://godot-rust-helper-scripts/audio.gdns\"\n\n[input]"),
+    true
+  );
+
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("autoload")
+    .arg("Audio")
+    .arg("--remove")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let project_file =
+    read_to_string("../platformer/project.godot").expect("Unable to read project file");
+
+  assert_eq!(project_file, PROJECT_FILE);
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should remove the autoload when the module is destroyed without treating it as a reference.
+#[test]
+fn autoload_destroy_module() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--output-path")
+    .arg("platformer/godot-rust-helper-output")
+    .arg("--nativescript-path")
+    .arg("platformer/godot-rust-helper-scripts");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("GameManager")
+    .arg("--autoload")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let project_file =
+    read_to_string("../platformer/project.godot").expect("Unable to read project file");
+  assert_eq!(
+    project_file,
+    "[autoload]\n\nGameManager=\"*res://godot-rust-helper-scripts/game_manager.gdns\"\n"
+  );
+
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("destroy")
+    .arg("GameManager")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let project_file =
+    read_to_string("../platformer/project.godot").expect("Unable to read project file");
+
+  assert_eq!(output.status.success(), true);
+  assert_eq!(project_file.contains("GameManager"), false);
+  assert_eq!(Path::new("src/game_manager.rs").exists(), false);
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
@@ -1,12 +1,15 @@
+#![allow(clippy::bool_assert_comparison)]
+
 use assert_cmd::prelude::*;
 
 use std::env::set_current_dir;
 use std::error::Error;
+use std::fs::{create_dir_all, read_to_string, write};
 use std::path::Path;
 use std::process::Command;
 
 mod test_utilities;
-use test_utilities::{cleanup_test_files, init_test, BUILD_FILE_NAME, TARGET};
+use test_utilities::{cleanup_test_files, init_test, BUILD_FILE_NAME, DELIMITER, TARGET};
 
 #[test]
 fn build_library() -> Result<(), Box<dyn Error>> {
@@ -137,3 +140,113 @@ fn build_specify_output_correct_build_file_location() -> Result<(), Box<dyn Erro
 
   Ok(())
 }
+
+// It should build every library in a workspace and copy each one to its own output path.
+#[test]
+fn build_workspace() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  create_dir_all("platformer_modules")?;
+  create_dir_all("platformer/gameplay")?;
+  create_dir_all("platformer/networking")?;
+  write(
+    "platformer_modules/Cargo.toml",
+    "[workspace]\nmembers = []\nresolver = \"2\"\n",
+  )?;
+
+  set_current_dir("platformer_modules").expect("Unable to change to workspace directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("init")
+    .output()
+    .expect("Unable to execute cargo run");
+  for library in &["gameplay", "networking"] {
+    Command::new("cargo")
+      .arg("run")
+      .arg("--manifest-path=../../Cargo.toml")
+      .arg("new")
+      .arg(library)
+      .arg("../platformer")
+      .arg("--output-path")
+      .arg(format!("../platformer/{}", library))
+      .arg("--no-ext")
+      .arg(format!("--targets={}", TARGET))
+      .output()
+      .expect("Unable to execute cargo run");
+
+    // The libraries don't use any dependencies so that they can be built without downloading them.
+    let cargo_toml_path = format!("{}/Cargo.toml", library);
+    let cargo_toml = read_to_string(&cargo_toml_path).expect("Unable to read Cargo.toml");
+    write(
+      &cargo_toml_path,
+      cargo_toml.replace("gdnative = \"0.9.1\"\n", ""),
+    )?;
+    write(format!("{}/src/lib.rs", library), "pub fn hello() {}\n")?;
+  }
+
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("build")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  for library in &["gameplay", "networking"] {
+    let build_file_name = format!(
+      "../platformer/{}/{}",
+      library,
+      BUILD_FILE_NAME.replace("platformer_modules", library)
+    );
+    assert_eq!(Path::new(&build_file_name).exists(), true);
+  }
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should report a workspace member whose config can't be parsed instead of panicking.
+#[test]
+fn build_workspace_malformed_member_config() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  create_dir_all("platformer_modules/gameplay")?;
+  write(
+    "platformer_modules/godot-rust-helper.toml",
+    "[workspace]\nmembers = [\"gameplay\"]\n",
+  )?;
+  write(
+    "platformer_modules/gameplay/godot-rust-helper.toml",
+    "[general]\nname = \"gameplay\"\nmodules = \"Player\"\n",
+  )?;
+
+  set_current_dir("platformer_modules").expect("Unable to change to workspace directory");
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("build")
+    .output()
+    .expect("Unable to execute cargo run");
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  let stderr = String::from_utf8_lossy(&output.stderr);
+
+  assert_eq!(output.status.success(), false);
+  assert_eq!(stdout.contains("Unable to parse "), true);
+  assert_eq!(
+    stdout.contains(&format!(
+      "platformer_modules{}gameplay{}godot-rust-helper.toml: ",
+      DELIMITER, DELIMITER
+    )),
+    true
+  );
+  assert_eq!(stderr.contains("panicked"), false);
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
@@ -0,0 +1,154 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use assert_cmd::prelude::*;
+
+use std::env::set_current_dir;
+use std::error::Error;
+use std::fs::read_to_string;
+use std::path::Path;
+use std::process::Command;
+
+mod test_utilities;
+use test_utilities::{cleanup_test_files, init_test};
+
+// It should create a Resource module with the properties and register it like any other module.
+#[test]
+fn create_resource_with_properties() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--output-path")
+    .arg("platformer/godot-rust-helper-output")
+    .arg("--nativescript-path")
+    .arg("platformer/godot-rust-helper-scripts");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create-resource")
+    .arg("EnemyStats")
+    .arg("--property")
+    .arg("health:int")
+    .arg("--property")
+    .arg("speed:f64")
+    .arg("--property")
+    .arg("spawn_offset:Vector2")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let lib_file = read_to_string("src/lib.rs").expect("Unable to read lib file");
+  let lib_file_split = lib_file.split("\n").collect::<Vec<&str>>();
+
+  let config_file = read_to_string("godot-rust-helper.toml").expect("Unable to read config file");
+  let config_split = config_file.split("\n").collect::<Vec<&str>>();
+
+  let mod_file = read_to_string("src/enemy_stats.rs").expect("Unable to read module file");
+  let mod_file_split = mod_file.split("\n").collect::<Vec<&str>>();
+
+  assert_eq!(config_split[3], "modules = [\"EnemyStats\"]");
+  assert_eq!(lib_file_split[4], "mod enemy_stats;");
+  assert_eq!(
+    lib_file_split[9],
+    "\thandle.add_class::<enemy_stats::EnemyStats>();"
+  );
+  assert_eq!(mod_file_split[0], "use gdnative::api::Resource;");
+  assert_eq!(mod_file_split[1], "use gdnative::core_types::Vector2;");
+  assert_eq!(mod_file_split[5], "#[inherit(Resource)]");
+  assert_eq!(mod_file_split[7], "pub struct EnemyStats {");
+  assert_eq!(mod_file_split[8], "\t#[property]");
+  assert_eq!(mod_file_split[9], "\tpub health: i64,");
+  assert_eq!(mod_file_split[11], "\tpub speed: f64,");
+  assert_eq!(mod_file_split[13], "\tpub spawn_offset: Vector2,");
+  assert_eq!(
+    mod_file.contains("\tfn new(_owner: &Resource) -> Self {"),
+    true
+  );
+  assert_eq!(
+    mod_file.contains("\t\t\tspawn_offset: Vector2::zero(),"),
+    true
+  );
+  assert_eq!(
+    Path::new("../platformer/godot-rust-helper-scripts/enemy_stats.gdns").exists(),
+    true
+  );
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should create a sample tres file that uses the resource's script when --tres is used.
+#[test]
+fn create_resource_sample_tres() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--output-path")
+    .arg("platformer/godot-rust-helper-output")
+    .arg("--nativescript-path")
+    .arg("platformer/godot-rust-helper-scripts");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create-resource")
+    .arg("Item")
+    .arg("--property")
+    .arg("name:String")
+    .arg("--property")
+    .arg("stackable:bool")
+    .arg("--tres")
+    .arg("data/sword.tres")
+    .output()
+    .expect("Unable to execute cargo run");
+  let invalid_output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create-resource")
+    .arg("Broken")
+    .arg("--property")
+    .arg("weight:u8")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let tres_file =
+    read_to_string("../platformer/data/sword.tres").expect("Unable to read tres file");
+  let tres_file_split = tres_file.split("\n").collect::<Vec<&str>>();
+
+  assert_eq!(
+    tres_file_split[0],
+    "[gd_resource type=\"Resource\" load_steps=2 format=2]"
+  );
+  assert_eq!(
+    tres_file_split[2],
+    "[ext_resource path=\"res://godot-rust-helper-scripts/item.gdns\" type=\"Script\" id=1]"
+  );
+  assert_eq!(tres_file_split[4], "[resource]");
+  assert_eq!(tres_file_split[5], "script = ExtResource( 1 )");
+  assert_eq!(tres_file_split[6], "name = \"\"");
+  assert_eq!(tres_file_split[7], "stackable = false");
+  assert_eq!(invalid_output.status.success(), false);
+  assert_eq!(Path::new("src/broken.rs").exists(), false);
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
@@ -1,8 +1,10 @@
+#![allow(clippy::bool_assert_comparison)]
+
 use assert_cmd::prelude::*;
 
 use std::env::set_current_dir;
 use std::error::Error;
-use std::fs::{read_to_string, remove_file};
+use std::fs::{create_dir_all, read_to_string, remove_file, write};
 use std::path::Path;
 use std::process::Command;
 
@@ -125,16 +127,20 @@ fn create_add_module_to_lib() -> Result<(), Box<dyn Error>> {
   assert_eq!(lib_file_split[0], "#[macro_use]");
   assert_eq!(lib_file_split[1], "extern crate gdnative;");
   assert_eq!(lib_file_split[2], "");
-  assert_eq!(lib_file_split[3], "mod hello;");
-  assert_eq!(lib_file_split[4], "");
+  assert_eq!(lib_file_split[3], "// godot_rust_helper: begin modules");
+  assert_eq!(lib_file_split[4], "mod hello;");
+  assert_eq!(lib_file_split[5], "// godot_rust_helper: end modules");
+  assert_eq!(lib_file_split[6], "");
   assert_eq!(
-    lib_file_split[5],
+    lib_file_split[7],
     "fn init(handle: gdnative::nativescript::InitHandle) {"
   );
-  assert_eq!(lib_file_split[6], "\thandle.add_class::<hello::Hello>();");
-  assert_eq!(lib_file_split[7], "}");
-  assert_eq!(lib_file_split[8], "");
-  assert_eq!(lib_file_split[9], "godot_init!(init);");
+  assert_eq!(lib_file_split[8], "\t// godot_rust_helper: begin classes");
+  assert_eq!(lib_file_split[9], "\thandle.add_class::<hello::Hello>();");
+  assert_eq!(lib_file_split[10], "\t// godot_rust_helper: end classes");
+  assert_eq!(lib_file_split[11], "}");
+  assert_eq!(lib_file_split[12], "");
+  assert_eq!(lib_file_split[13], "godot_init!(init);");
 
   set_current_dir("../").expect("Unable to change to parent directory");
 
@@ -351,9 +357,9 @@ fn create_multiple_captial_letters() -> Result<(), Box<dyn Error>> {
 
   assert_eq!(config_split[3], "modules = [\"MainScene\"]");
 
-  assert_eq!(lib_file_split[3], "mod main_scene;");
+  assert_eq!(lib_file_split[4], "mod main_scene;");
   assert_eq!(
-    lib_file_split[6],
+    lib_file_split[9],
     "\thandle.add_class::<main_scene::MainScene>();"
   );
 
@@ -364,6 +370,75 @@ fn create_multiple_captial_letters() -> Result<(), Box<dyn Error>> {
   Ok(())
 }
 
+// It should name the files and mod declarations of modules with acronyms and numbers correctly.
+#[test]
+fn create_acronyms_and_numbers() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--output-path")
+    .arg("platformer/godot-rust-helper-output")
+    .arg("--nativescript-path")
+    .arg("platformer/godot-rust-helper-scripts");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  for name in &["HUD", "Level2Boss", "HTTPServer"] {
+    Command::new("cargo")
+      .arg("run")
+      .arg("--manifest-path=../../Cargo.toml")
+      .arg("create")
+      .arg(name)
+      .output()
+      .expect("Unable to execute cargo run");
+  }
+
+  let lib_file = read_to_string("src/lib.rs").expect("Unable to read lib file");
+  let lib_file_split = lib_file.split("\n").collect::<Vec<&str>>();
+
+  let hud_gdns_file = read_to_string("../platformer/godot-rust-helper-scripts/hud.gdns")
+    .expect("Unable to read gdns file");
+  let hud_gdns_file_split = hud_gdns_file.split("\n").collect::<Vec<&str>>();
+
+  assert_eq!(Path::new("src/hud.rs").exists(), true);
+  assert_eq!(Path::new("src/level2_boss.rs").exists(), true);
+  assert_eq!(Path::new("src/http_server.rs").exists(), true);
+  assert_eq!(
+    Path::new("../platformer/godot-rust-helper-scripts/level2_boss.gdns").exists(),
+    true
+  );
+  assert_eq!(
+    Path::new("../platformer/godot-rust-helper-scripts/http_server.gdns").exists(),
+    true
+  );
+
+  assert_eq!(lib_file_split[4], "mod hud;");
+  assert_eq!(lib_file_split[5], "mod level2_boss;");
+  assert_eq!(lib_file_split[6], "mod http_server;");
+  assert_eq!(lib_file_split[11], "\thandle.add_class::<hud::HUD>();");
+  assert_eq!(
+    lib_file_split[12],
+    "\thandle.add_class::<level2_boss::Level2Boss>();"
+  );
+  assert_eq!(
+    lib_file_split[13],
+    "\thandle.add_class::<http_server::HTTPServer>();"
+  );
+
+  assert_eq!(hud_gdns_file_split[7], "class_name = \"HUD\"");
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
 // It should create multiple modules.
 #[test]
 fn create_multiple_modules() -> Result<(), Box<dyn Error>> {
@@ -451,7 +526,7 @@ fn create_multiple_modules_and_add_to_lib() -> Result<(), Box<dyn Error>> {
     .arg("run")
     .arg("--manifest-path=../../Cargo.toml")
     .arg("create")
-    .arg("World")
+    .arg("Level")
     .output()
     .expect("Unable to execute cargo run");
 
@@ -461,18 +536,22 @@ fn create_multiple_modules_and_add_to_lib() -> Result<(), Box<dyn Error>> {
   assert_eq!(lib_file_split[0], "#[macro_use]");
   assert_eq!(lib_file_split[1], "extern crate gdnative;");
   assert_eq!(lib_file_split[2], "");
-  assert_eq!(lib_file_split[3], "mod hello;");
-  assert_eq!(lib_file_split[4], "mod world;");
-  assert_eq!(lib_file_split[5], "");
+  assert_eq!(lib_file_split[3], "// godot_rust_helper: begin modules");
+  assert_eq!(lib_file_split[4], "mod hello;");
+  assert_eq!(lib_file_split[5], "mod level;");
+  assert_eq!(lib_file_split[6], "// godot_rust_helper: end modules");
+  assert_eq!(lib_file_split[7], "");
   assert_eq!(
-    lib_file_split[6],
+    lib_file_split[8],
     "fn init(handle: gdnative::nativescript::InitHandle) {"
   );
-  assert_eq!(lib_file_split[7], "\thandle.add_class::<hello::Hello>();");
-  assert_eq!(lib_file_split[8], "\thandle.add_class::<world::World>();");
-  assert_eq!(lib_file_split[9], "}");
-  assert_eq!(lib_file_split[10], "");
-  assert_eq!(lib_file_split[11], "godot_init!(init);");
+  assert_eq!(lib_file_split[9], "\t// godot_rust_helper: begin classes");
+  assert_eq!(lib_file_split[10], "\thandle.add_class::<hello::Hello>();");
+  assert_eq!(lib_file_split[11], "\thandle.add_class::<level::Level>();");
+  assert_eq!(lib_file_split[12], "\t// godot_rust_helper: end classes");
+  assert_eq!(lib_file_split[13], "}");
+  assert_eq!(lib_file_split[14], "");
+  assert_eq!(lib_file_split[15], "godot_init!(init);");
 
   set_current_dir("../").expect("Unable to change to parent directory");
 
@@ -514,24 +593,28 @@ fn create_additional_plugin_script() -> Result<(), Box<dyn Error>> {
   assert_eq!(lib_file_split[0], "#[macro_use]");
   assert_eq!(lib_file_split[1], "extern crate gdnative;");
   assert_eq!(lib_file_split[2], "");
-  assert_eq!(lib_file_split[3], "mod directory_browser;");
-  assert_eq!(lib_file_split[4], "mod folder_structure;");
-  assert_eq!(lib_file_split[5], "");
+  assert_eq!(lib_file_split[3], "// godot_rust_helper: begin modules");
+  assert_eq!(lib_file_split[4], "mod directory_browser;");
+  assert_eq!(lib_file_split[5], "mod folder_structure;");
+  assert_eq!(lib_file_split[6], "// godot_rust_helper: end modules");
+  assert_eq!(lib_file_split[7], "");
   assert_eq!(
-    lib_file_split[6],
+    lib_file_split[8],
     "fn init(handle: gdnative::nativescript::InitHandle) {"
   );
+  assert_eq!(lib_file_split[9], "\t// godot_rust_helper: begin classes");
   assert_eq!(
-    lib_file_split[7],
+    lib_file_split[10],
     "\thandle.add_tool_class::<directory_browser::DirectoryBrowser>();"
   );
   assert_eq!(
-    lib_file_split[8],
+    lib_file_split[11],
     "\thandle.add_tool_class::<folder_structure::FolderStructure>();"
   );
-  assert_eq!(lib_file_split[9], "}");
-  assert_eq!(lib_file_split[10], "");
-  assert_eq!(lib_file_split[11], "godot_init!(init);");
+  assert_eq!(lib_file_split[12], "\t// godot_rust_helper: end classes");
+  assert_eq!(lib_file_split[13], "}");
+  assert_eq!(lib_file_split[14], "");
+  assert_eq!(lib_file_split[15], "godot_init!(init);");
 
   assert_eq!(Path::new("src/folder_structure.rs").exists(), true);
 
@@ -546,3 +629,868 @@ fn create_additional_plugin_script() -> Result<(), Box<dyn Error>> {
 
   Ok(())
 }
+
+// It should only update the generated regions of the lib file.
+#[test]
+fn create_preserve_hand_written_lib_code() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--output-path")
+    .arg("platformer/godot-rust-helper-output")
+    .arg("--nativescript-path")
+    .arg("platformer/godot-rust-helper-scripts");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+
+  let lib_file = read_to_string("src/lib.rs").expect("Unable to read lib file");
+  let custom_lib_file = lib_file
+    .replace(
+      "extern crate gdnative;\n",
+      "extern crate gdnative;\n\nmod utilities;\n",
+    )
+    .replace(
+      "\t// godot_rust_helper: end classes\n",
+      "\t// godot_rust_helper: end classes\n\tutilities::init_logging();\n",
+    )
+    .replace(
+      "godot_init!(init);",
+      "godot_gdnative_terminate!(terminate);\ngodot_init!(init);",
+    );
+  write("src/lib.rs", &custom_lib_file).expect("Unable to write lib file");
+
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Hello")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let lib_file = read_to_string("src/lib.rs").expect("Unable to read lib file");
+  let lib_file_split = lib_file.split("\n").collect::<Vec<&str>>();
+
+  assert_eq!(lib_file_split[3], "mod utilities;");
+  assert_eq!(lib_file_split[5], "// godot_rust_helper: begin modules");
+  assert_eq!(lib_file_split[6], "mod hello;");
+  assert_eq!(lib_file_split[11], "\thandle.add_class::<hello::Hello>();");
+  assert_eq!(lib_file_split[12], "\t// godot_rust_helper: end classes");
+  assert_eq!(lib_file_split[13], "\tutilities::init_logging();");
+  assert_eq!(lib_file_split[16], "godot_gdnative_terminate!(terminate);");
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should refuse to create a module if the lib file is missing the generated region markers.
+#[test]
+fn create_missing_lib_markers() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--output-path")
+    .arg("platformer/godot-rust-helper-output")
+    .arg("--nativescript-path")
+    .arg("platformer/godot-rust-helper-scripts");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+
+  write(
+    "src/lib.rs",
+    "#[macro_use]\nextern crate gdnative;\n\nfn init(handle: gdnative::nativescript::InitHandle) {\n}\n\ngodot_init!(init);",
+  )
+  .expect("Unable to write lib file");
+
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Hello")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let stdout = String::from_utf8(output.stdout)?;
+
+  let config_file = read_to_string("godot-rust-helper.toml").expect("Unable to read config file");
+  let config_split = config_file.split("\n").collect::<Vec<&str>>();
+
+  assert_eq!(output.status.success(), false);
+  assert_eq!(stdout.contains("// godot_rust_helper: begin modules"), true);
+  assert_eq!(config_split[3], "modules = []");
+  assert_eq!(Path::new("src/hello.rs").exists(), false);
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should register modules created with --tool as tool classes and the rest as runtime classes.
+#[test]
+fn create_tool_module() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--output-path")
+    .arg("platformer/godot-rust-helper-output")
+    .arg("--nativescript-path")
+    .arg("platformer/godot-rust-helper-scripts");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Player")
+    .output()
+    .expect("Unable to execute cargo run");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("LevelPreview")
+    .arg("--tool")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let lib_file = read_to_string("src/lib.rs").expect("Unable to read lib file");
+  let lib_file_split = lib_file.split("\n").collect::<Vec<&str>>();
+
+  let config_file = read_to_string("godot-rust-helper.toml").expect("Unable to read config file");
+
+  assert_eq!(
+    lib_file_split[10],
+    "\thandle.add_class::<player::Player>();"
+  );
+  assert_eq!(
+    lib_file_split[11],
+    "\thandle.add_tool_class::<level_preview::LevelPreview>();"
+  );
+  assert_eq!(
+    config_file.contains("[modules.LevelPreview]\ntool = true"),
+    true
+  );
+  assert_eq!(config_file.contains("[modules.Player]"), false);
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should store the struct in the user_data wrapper passed with --user-data and adjust the method receivers.
+#[test]
+fn create_thread_safe_user_data() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--output-path")
+    .arg("platformer/godot-rust-helper-output")
+    .arg("--nativescript-path")
+    .arg("platformer/godot-rust-helper-scripts");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Spawner")
+    .arg("--user-data")
+    .arg("mutex")
+    .output()
+    .expect("Unable to execute cargo run");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Pathfinder")
+    .arg("--user-data")
+    .arg("arc")
+    .output()
+    .expect("Unable to execute cargo run");
+  let invalid_output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Broken")
+    .arg("--user-data")
+    .arg("refcell")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let mutex_mod_file = read_to_string("src/spawner.rs").expect("Unable to read module file");
+  let arc_mod_file = read_to_string("src/pathfinder.rs").expect("Unable to read module file");
+
+  assert_eq!(
+    mutex_mod_file.contains("#[user_data(user_data::MutexData<Spawner>)]"),
+    true
+  );
+  assert_eq!(
+    mutex_mod_file.contains("\tfn _ready(&mut self, _owner: &Node) {"),
+    true
+  );
+  assert_eq!(
+    arc_mod_file.contains("#[user_data(user_data::ArcData<Pathfinder>)]"),
+    true
+  );
+  assert_eq!(
+    arc_mod_file.contains("\tfn _ready(&self, _owner: &Node) {"),
+    true
+  );
+  assert_eq!(invalid_output.status.success(), false);
+  assert_eq!(Path::new("src/broken.rs").exists(), false);
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should create a scene whose root node uses the module's script when --scene is used.
+#[test]
+fn create_scene() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--output-path")
+    .arg("platformer/godot-rust-helper-output")
+    .arg("--nativescript-path")
+    .arg("platformer/godot-rust-helper-scripts");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("MainScene")
+    .arg("--scene")
+    .output()
+    .expect("Unable to execute cargo run");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Player")
+    .arg("--scene")
+    .arg("characters/player.tscn")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let main_scene_file =
+    read_to_string("../platformer/main_scene.tscn").expect("Unable to read scene file");
+  let player_scene_file =
+    read_to_string("../platformer/characters/player.tscn").expect("Unable to read scene file");
+  let player_scene_split = player_scene_file.split("\n").collect::<Vec<&str>>();
+
+  assert_eq!(
+    main_scene_file.contains("[node name=\"MainScene\" type=\"Node\"]"),
+    true
+  );
+  assert_eq!(player_scene_split[0], "[gd_scene load_steps=2 format=2]");
+  assert_eq!(
+    player_scene_split[2],
+    "[ext_resource path=\"res://godot-rust-helper-scripts/player.gdns\" type=\"Script\" id=1]"
+  );
+  assert_eq!(
+    player_scene_split[4],
+    "[node name=\"Player\" type=\"Node\"]"
+  );
+  assert_eq!(player_scene_split[5], "script = ExtResource( 1 )");
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should convert a GDScript file into a module and swap the scenes that use the script over to the module.
+#[test]
+fn create_from_gd() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--output-path")
+    .arg("platformer/godot-rust-helper-output")
+    .arg("--nativescript-path")
+    .arg("platformer/godot-rust-helper-scripts");
+
+  cmd.assert().success();
+
+  write(
+    "platformer/player.gd",
+    "extends KinematicBody2D\nclass_name Player\n\nsignal hit(damage)\n\nexport var speed = 200\nexport(float) var gravity = 9.8\n\nfunc _ready():\n\tpass\n\nfunc get_velocity(delta: float) -> Vector2:\n\t# Falling.\n\treturn Vector2(speed, gravity * delta)\n",
+  )?;
+  write(
+    "platformer/player.tscn",
+    "[gd_scene load_steps=2 format=2]\n\n[ext_resource path=\"res://player.gd\" type=\"Script\" id=1]\n\n[node name=\"Player\" type=\"KinematicBody2D\"]\nscript = ExtResource( 1 )\n",
+  )?;
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("--from-gd")
+    .arg("player.gd")
+    .arg("--swap-references")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let config = read_to_string("godot-rust-helper.toml").expect("Unable to read config");
+  let mod_file = read_to_string("src/player.rs").expect("Unable to read module file");
+  let scene_file = read_to_string("../platformer/player.tscn").expect("Unable to read scene file");
+
+  assert_eq!(output.status.success(), true);
+  assert_eq!(config.contains("modules = [\"Player\"]"), true);
+  assert_eq!(
+    config.contains("[modules.Player]\nbase = \"KinematicBody2D\"\n"),
+    true
+  );
+  assert_eq!(
+    mod_file,
+    "use gdnative::api::KinematicBody2D;\nuse gdnative::nativescript::user_data;\nuse gdnative::prelude::*;\n\n#[derive(NativeClass)]\n#[inherit(KinematicBody2D)]\n#[user_data(user_data::LocalCellData<Player>)]\n#[register_with(Self::register_signals)]\npub struct Player {\n\t#[property]\n\tpub speed: i64,\n\t#[property]\n\tpub gravity: f64,\n}\n\n#[gdnative::methods]\nimpl Player {\n\tfn new(_owner: &KinematicBody2D) -> Self {\n\t\tPlayer {\n\t\t\tspeed: 200,\n\t\t\tgravity: 9.8,\n\t\t}\n\t}\n\n\tfn register_signals(builder: &ClassBuilder<Self>) {\n\t\tbuilder.add_signal(Signal {\n\t\t\tname: \"hit\",\n\t\t\targs: &[\n\t\t\t\tSignalArgument {\n\t\t\t\t\tname: \"damage\",\n\t\t\t\t\tdefault: Variant::new(),\n\t\t\t\t\texport_info: ExportInfo::new(VariantType::Nil),\n\t\t\t\t\tusage: PropertyUsage::DEFAULT,\n\t\t\t\t},\n\t\t\t],\n\t\t});\n\t}\n\n\t#[export]\n\tfn _ready(&self, _owner: &KinematicBody2D) {\n\t\t// pass\n\t}\n\n\t#[export]\n\tfn get_velocity(&self, _owner: &KinematicBody2D, _delta: f64) -> Vector2 {\n\t\t// # Falling.\n\t\t// return Vector2(speed, gravity * delta)\n\t\tDefault::default()\n\t}\n}\n"
+  );
+  assert_eq!(
+    scene_file,
+    "[gd_scene load_steps=2 format=2]\n\n[ext_resource path=\"res://godot-rust-helper-scripts/player.gdns\" type=\"Script\" id=1]\n\n[node name=\"Player\" type=\"KinematicBody2D\"]\nscript = ExtResource( 1 )\n"
+  );
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should refuse class names that aren't Rust identifiers or that collide with Godot's classes, the lib file, or the crate.
+#[test]
+fn create_invalid_class_names() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--output-path")
+    .arg("platformer/godot-rust-helper-output")
+    .arg("--nativescript-path")
+    .arg("platformer/godot-rust-helper-scripts");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+
+  let mut outputs = vec![];
+  for name in &[
+    "player",
+    "Main-Scene",
+    "Self",
+    "Lib",
+    "Timer",
+    "PlatformerModules",
+  ] {
+    let output = Command::new("cargo")
+      .arg("run")
+      .arg("--manifest-path=../../Cargo.toml")
+      .arg("create")
+      .arg(name)
+      .output()
+      .expect("Unable to execute cargo run");
+    outputs.push((output.status.success(), String::from_utf8(output.stdout)?));
+  }
+
+  let config = read_to_string("godot-rust-helper.toml").expect("Unable to read config");
+
+  assert_eq!(outputs.iter().all(|(success, _)| !*success), true);
+  assert_eq!(
+    outputs[0].1.contains("have to start with a capital letter"),
+    true
+  );
+  assert_eq!(
+    outputs[1]
+      .1
+      .contains("can only contain letters, numbers, and underscores"),
+    true
+  );
+  assert_eq!(outputs[2].1.contains("Rust keyword"), true);
+  assert_eq!(outputs[3].1.contains("would replace src/lib.rs"), true);
+  assert_eq!(
+    outputs[4]
+      .1
+      .contains("Godot already has a class named Timer"),
+    true
+  );
+  assert_eq!(
+    outputs[5]
+      .1
+      .contains("same name as the platformer_modules crate"),
+    true
+  );
+  assert_eq!(config.contains("modules = []"), true);
+  assert_eq!(Path::new("src/timer.rs").exists(), false);
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should add the library's class prefix to the class names of new modules.
+#[test]
+fn create_with_class_prefix() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--output-path")
+    .arg("platformer/godot-rust-helper-output")
+    .arg("--nativescript-path")
+    .arg("platformer/godot-rust-helper-scripts")
+    .arg("--class-prefix")
+    .arg("Rs")
+    .arg("--gdnative")
+    .arg("0.10");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Timer")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let config = read_to_string("godot-rust-helper.toml").expect("Unable to read config");
+  let gdns_file = read_to_string("../platformer/godot-rust-helper-scripts/timer.gdns")
+    .expect("Unable to read gdns file");
+  let mod_file = read_to_string("src/timer.rs").expect("Unable to read module file");
+  let lib_file = read_to_string("src/lib.rs").expect("Unable to read lib file");
+
+  assert_eq!(output.status.success(), true);
+  assert_eq!(config.contains("class_prefix = \"Rs\""), true);
+  assert_eq!(config.contains("modules = [\"Timer\"]"), true);
+  assert_eq!(
+    config.contains("[modules.Timer]\nclass_name = \"RsTimer\"\n"),
+    true
+  );
+  assert_eq!(gdns_file.contains("class_name = \"RsTimer\""), true);
+  assert_eq!(mod_file.contains("pub struct Timer;"), true);
+  assert_eq!(
+    lib_file.contains("handle.add_class_as::<timer::Timer>(\"RsTimer\".to_string());"),
+    true
+  );
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should refuse a class prefix when the library uses gdnative 0.9.
+#[test]
+fn create_with_class_prefix_gdnative_0_9() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--class-prefix")
+    .arg("Rs");
+
+  cmd.assert().failure();
+
+  assert_eq!(Path::new("platformer_modules").exists(), false);
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should only add the class prefix to modules that are created after it was set so that the existing classes keep
+// the names that the Godot project uses for them.
+#[test]
+fn create_with_class_prefix_existing_modules() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd.arg("new").arg("platformer_modules").arg("platformer");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Player")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let config = read_to_string("godot-rust-helper.toml").expect("Unable to read config");
+  write(
+    "godot-rust-helper.toml",
+    config.replace("[general]\n", "[general]\nclass_prefix = \"Rs\"\n"),
+  )
+  .expect("Unable to write config");
+
+  let update_output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("update")
+    .arg("--gdnative")
+    .arg("0.10")
+    .output()
+    .expect("Unable to execute cargo run");
+  let create_output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Enemy")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let config = read_to_string("godot-rust-helper.toml").expect("Unable to read config");
+  let lib_file = read_to_string("src/lib.rs").expect("Unable to read lib file");
+  let player_gdns_file =
+    read_to_string("../platformer/player.gdns").expect("Unable to read gdns file");
+  let enemy_gdns_file =
+    read_to_string("../platformer/enemy.gdns").expect("Unable to read gdns file");
+
+  assert_eq!(update_output.status.success(), true);
+  assert_eq!(create_output.status.success(), true);
+  assert_eq!(
+    config.contains("[modules.Enemy]\nclass_name = \"RsEnemy\"\n"),
+    true
+  );
+  assert_eq!(config.contains("[modules.Player]"), false);
+  assert_eq!(
+    lib_file.contains("\thandle.add_class::<player::Player>();\n"),
+    true
+  );
+  assert_eq!(
+    lib_file.contains("\thandle.add_class_as::<enemy::Enemy>(\"RsEnemy\".to_string());\n"),
+    true
+  );
+  assert_eq!(player_gdns_file.contains("class_name = \"Player\""), true);
+  assert_eq!(enemy_gdns_file.contains("class_name = \"RsEnemy\""), true);
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should make the class a named script class and copy its icon into the Godot project.
+#[test]
+fn create_named_with_icon() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--output-path")
+    .arg("platformer/godot-rust-helper-output")
+    .arg("--nativescript-path")
+    .arg("platformer/godot-rust-helper-scripts");
+
+  cmd.assert().success();
+
+  write("enemy.svg", "<svg xmlns=\"http://www.w3.org/2000/svg\"/>")?;
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Enemy")
+    .arg("--named")
+    .arg("--icon")
+    .arg("../enemy.svg")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let config = read_to_string("godot-rust-helper.toml").expect("Unable to read config");
+  let gdns_file = read_to_string("../platformer/godot-rust-helper-scripts/enemy.gdns")
+    .expect("Unable to read gdns file");
+  let icon_file = read_to_string("../platformer/godot-rust-helper-scripts/enemy.svg")
+    .expect("Unable to read icon file");
+
+  assert_eq!(output.status.success(), true);
+  assert_eq!(
+    config.contains(
+      "[modules.Enemy]\nnamed = true\nicon = \"res://godot-rust-helper-scripts/enemy.svg\"\n"
+    ),
+    true
+  );
+  assert_eq!(
+    gdns_file.ends_with("class_name = \"Enemy\"\nlibrary = ExtResource( 1 )\nscript_class_name = \"Enemy\"\nscript_class_icon_path = \"res://godot-rust-helper-scripts/enemy.svg\"\n"),
+    true
+  );
+  assert_eq!(icon_file, "<svg xmlns=\"http://www.w3.org/2000/svg\"/>");
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  remove_file("enemy.svg")?;
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should generate the lib and module files for gdnative 0.10 when the library was created for it.
+#[test]
+fn create_mod_file_gdnative_0_10() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--gdnative")
+    .arg("0.10");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Hello")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let cargo_toml = read_to_string("Cargo.toml").expect("Unable to read Cargo.toml");
+  let config = read_to_string("godot-rust-helper.toml").expect("Unable to read config");
+  let lib_file = read_to_string("src/lib.rs").expect("Unable to read lib file");
+  let lib_file_split = lib_file.split("\n").collect::<Vec<&str>>();
+  let mod_file = read_to_string("src/hello.rs").expect("Unable to read module file");
+  let mod_file_split = mod_file.split("\n").collect::<Vec<&str>>();
+
+  assert_eq!(cargo_toml.contains("gdnative = \"0.10\"\n"), true);
+  assert_eq!(config.contains("gdnative_version = \"0.10\"\n"), true);
+
+  assert_eq!(lib_file_split[0], "use gdnative::prelude::*;");
+  assert_eq!(lib_file_split[1], "");
+  assert_eq!(lib_file_split[3], "mod hello;");
+  assert_eq!(lib_file_split[6], "fn init(handle: InitHandle) {");
+  assert_eq!(lib_file_split[8], "\thandle.add_class::<hello::Hello>();");
+
+  assert_eq!(mod_file_split[0], "use gdnative::api::Node;");
+  assert_eq!(mod_file_split[1], "use gdnative::export::user_data;");
+  assert_eq!(mod_file_split[2], "use gdnative::prelude::*;");
+  assert_eq!(mod_file_split[3], "");
+  assert_eq!(mod_file_split[9], "#[methods]");
+  assert_eq!(mod_file_split[10], "impl Hello {");
+  assert_eq!(mod_file_split[11], "\tfn new(_base: &Node) -> Self {");
+  assert_eq!(mod_file_split[15], "\t#[method]");
+  assert_eq!(
+    mod_file_split[16],
+    "\tfn _ready(&self, #[base] _base: TRef<Node>) {"
+  );
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should create a gdext module without a gdns file in a library for Godot 4.
+#[test]
+fn create_godot_4_module() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--godot")
+    .arg("4");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Hello")
+    .arg("--tool")
+    .arg("--scene")
+    .arg("--godot")
+    .arg("4")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let lib_file = read_to_string("src/lib.rs").expect("Unable to read lib file");
+  let mod_file = read_to_string("src/hello.rs").expect("Unable to read module file");
+  let mod_file_split = mod_file.split("\n").collect::<Vec<&str>>();
+  let scene_file = read_to_string("../platformer/hello.tscn").expect("Unable to read scene file");
+
+  assert_eq!(output.status.success(), true);
+  assert_eq!(
+    lib_file.contains(
+      "// godot_rust_helper: begin modules\nmod hello;\n// godot_rust_helper: end modules\n"
+    ),
+    true
+  );
+  assert_eq!(lib_file.contains("add_class"), false);
+  assert_eq!(mod_file_split[0], "use godot::classes::{INode, Node};");
+  assert_eq!(mod_file_split[1], "use godot::prelude::*;");
+  assert_eq!(mod_file_split[3], "#[derive(GodotClass)]");
+  assert_eq!(mod_file_split[4], "#[class(tool, base=Node)]");
+  assert_eq!(mod_file_split[5], "pub struct Hello {");
+  assert_eq!(mod_file_split[6], "\tbase: Base<Node>,");
+  assert_eq!(mod_file_split[9], "#[godot_api]");
+  assert_eq!(mod_file_split[10], "impl INode for Hello {");
+  assert_eq!(mod_file_split[11], "\tfn init(base: Base<Node>) -> Self {");
+  assert_eq!(
+    scene_file,
+    "[gd_scene format=3]\n\n[node name=\"Hello\" type=\"Hello\"]\n"
+  );
+  assert_eq!(Path::new("../platformer/hello.gdns").exists(), false);
+
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Goodbye")
+    .arg("--godot")
+    .arg("3")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  assert_eq!(output.status.success(), false);
+  assert_eq!(Path::new("src/goodbye.rs").exists(), false);
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should create the module in the workspace library passed to --lib.
+#[test]
+fn create_in_workspace_library() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  create_dir_all("platformer_modules")?;
+  write(
+    "platformer_modules/Cargo.toml",
+    "[workspace]\nmembers = []\n",
+  )?;
+
+  set_current_dir("platformer_modules").expect("Unable to change to workspace directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("init")
+    .output()
+    .expect("Unable to execute cargo run");
+  for library in &["gameplay", "networking"] {
+    Command::new("cargo")
+      .arg("run")
+      .arg("--manifest-path=../../Cargo.toml")
+      .arg("new")
+      .arg(library)
+      .arg("../platformer")
+      .output()
+      .expect("Unable to execute cargo run");
+  }
+
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Player")
+    .output()
+    .expect("Unable to execute cargo run");
+  let stdout = String::from_utf8_lossy(&output.stdout);
+
+  assert_eq!(
+    stdout
+      .contains("Use --lib to pick the library to use the create command in: gameplay, networking"),
+    true
+  );
+  assert_eq!(Path::new("gameplay/src/player.rs").exists(), false);
+
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Player")
+    .arg("--lib")
+    .arg("gameplay")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let config = read_to_string("gameplay/godot-rust-helper.toml").expect("Unable to read config");
+
+  assert_eq!(Path::new("gameplay/src/player.rs").exists(), true);
+  assert_eq!(Path::new("networking/src/player.rs").exists(), false);
+  assert_eq!(config.contains("modules = [\"Player\"]"), true);
+  assert_eq!(Path::new("../platformer/player.gdns").exists(), true);
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
@@ -1,8 +1,10 @@
+#![allow(clippy::bool_assert_comparison)]
+
 use assert_cmd::prelude::*;
 
 use std::env::set_current_dir;
 use std::error::Error;
-use std::fs::read_to_string;
+use std::fs::{read_to_string, write};
 use std::path::Path;
 use std::process::Command;
 
@@ -55,13 +57,18 @@ fn destroy_remove_created_module() -> Result<(), Box<dyn Error>> {
   assert_eq!(lib_file_split[0], "#[macro_use]");
   assert_eq!(lib_file_split[1], "extern crate gdnative;");
   assert_eq!(lib_file_split[2], "");
+  assert_eq!(lib_file_split[3], "// godot_rust_helper: begin modules");
+  assert_eq!(lib_file_split[4], "// godot_rust_helper: end modules");
+  assert_eq!(lib_file_split[5], "");
   assert_eq!(
-    lib_file_split[3],
+    lib_file_split[6],
     "fn init(handle: gdnative::nativescript::InitHandle) {"
   );
-  assert_eq!(lib_file_split[4], "}");
-  assert_eq!(lib_file_split[5], "");
-  assert_eq!(lib_file_split[6], "godot_init!(init);");
+  assert_eq!(lib_file_split[7], "\t// godot_rust_helper: begin classes");
+  assert_eq!(lib_file_split[8], "\t// godot_rust_helper: end classes");
+  assert_eq!(lib_file_split[9], "}");
+  assert_eq!(lib_file_split[10], "");
+  assert_eq!(lib_file_split[11], "godot_init!(init);");
   assert_eq!(config_split[3], "modules = []");
   assert_eq!(mod_file_path.exists(), false);
   assert_eq!(hello_gdns_file.exists(), false);
@@ -102,14 +109,14 @@ fn destroy_create_two_remove_one() -> Result<(), Box<dyn Error>> {
     .arg("run")
     .arg("--manifest-path=../../Cargo.toml")
     .arg("create")
-    .arg("World")
+    .arg("Level")
     .output()
     .expect("Unable to execute cargo run");
   Command::new("cargo")
     .arg("run")
     .arg("--manifest-path=../../Cargo.toml")
     .arg("destroy")
-    .arg("World")
+    .arg("Level")
     .output()
     .expect("Unable to execute cargo run");
 
@@ -120,32 +127,207 @@ fn destroy_create_two_remove_one() -> Result<(), Box<dyn Error>> {
   let config_split = config_file.split("\n").collect::<Vec<&str>>();
 
   let hello_mod_file_path = Path::new("src/hello.rs");
-  let world_mod_file_path = Path::new("src/world.rs");
+  let level_mod_file_path = Path::new("src/level.rs");
 
   let hello_gdns_file = Path::new("../platformer/godot-rust-helper-scripts/hello.gdns");
-  let world_gdns_file = Path::new("../platformer/godot-rust-helper-scripts/world.gdns");
+  let level_gdns_file = Path::new("../platformer/godot-rust-helper-scripts/level.gdns");
 
   assert_eq!(lib_file_split[0], "#[macro_use]");
   assert_eq!(lib_file_split[1], "extern crate gdnative;");
   assert_eq!(lib_file_split[2], "");
-  assert_eq!(lib_file_split[3], "mod hello;");
-  assert_eq!(lib_file_split[4], "");
+  assert_eq!(lib_file_split[3], "// godot_rust_helper: begin modules");
+  assert_eq!(lib_file_split[4], "mod hello;");
+  assert_eq!(lib_file_split[5], "// godot_rust_helper: end modules");
+  assert_eq!(lib_file_split[6], "");
   assert_eq!(
-    lib_file_split[5],
+    lib_file_split[7],
     "fn init(handle: gdnative::nativescript::InitHandle) {"
   );
-  assert_eq!(lib_file_split[6], "\thandle.add_class::<hello::Hello>();");
-  assert_eq!(lib_file_split[7], "}");
-  assert_eq!(lib_file_split[8], "");
-  assert_eq!(lib_file_split[9], "godot_init!(init);");
+  assert_eq!(lib_file_split[8], "\t// godot_rust_helper: begin classes");
+  assert_eq!(lib_file_split[9], "\thandle.add_class::<hello::Hello>();");
+  assert_eq!(lib_file_split[10], "\t// godot_rust_helper: end classes");
+  assert_eq!(lib_file_split[11], "}");
+  assert_eq!(lib_file_split[12], "");
+  assert_eq!(lib_file_split[13], "godot_init!(init);");
 
   assert_eq!(config_split[3], "modules = [\"Hello\"]");
 
   assert_eq!(hello_mod_file_path.exists(), true);
-  assert_eq!(world_mod_file_path.exists(), false);
+  assert_eq!(level_mod_file_path.exists(), false);
 
   assert_eq!(hello_gdns_file.exists(), true);
-  assert_eq!(world_gdns_file.exists(), false);
+  assert_eq!(level_gdns_file.exists(), false);
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should refuse to destroy a module that is still used by a scene.
+#[test]
+fn destroy_refuse_referenced_module() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--output-path")
+    .arg("platformer/godot-rust-helper-output")
+    .arg("--nativescript-path")
+    .arg("platformer/godot-rust-helper-scripts");
+
+  cmd.assert().success();
+
+  write(
+    "platformer/level.tscn",
+    "[gd_scene load_steps=2 format=2]\n\n[ext_resource path=\"res://godot-rust-helper-scripts/hello.gdns\" type=\"Script\" id=1]\n\n[node name=\"Hello\" type=\"Node\"]\nscript = ExtResource( 1 )\n",
+  )?;
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Hello")
+    .output()
+    .expect("Unable to execute cargo run");
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("destroy")
+    .arg("Hello")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let stdout = String::from_utf8(output.stdout)?;
+
+  let config_file = read_to_string("godot-rust-helper.toml").expect("Unable to read config file");
+  let config_split = config_file.split("\n").collect::<Vec<&str>>();
+
+  let mod_file_path = Path::new("src/hello.rs");
+  let hello_gdns_file = Path::new("../platformer/godot-rust-helper-scripts/hello.gdns");
+
+  assert_eq!(output.status.success(), false);
+  assert_eq!(stdout.contains("level.tscn"), true);
+  assert_eq!(config_split[3], "modules = [\"Hello\"]");
+  assert_eq!(mod_file_path.exists(), true);
+  assert_eq!(hello_gdns_file.exists(), true);
+
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("destroy")
+    .arg("Hello")
+    .arg("--force")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  assert_eq!(mod_file_path.exists(), false);
+  assert_eq!(hello_gdns_file.exists(), false);
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should unregister a module but keep its source file.
+#[test]
+fn destroy_keep_source() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--output-path")
+    .arg("platformer/godot-rust-helper-output")
+    .arg("--nativescript-path")
+    .arg("platformer/godot-rust-helper-scripts");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Hello")
+    .output()
+    .expect("Unable to execute cargo run");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("destroy")
+    .arg("Hello")
+    .arg("--keep-source")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let lib_file = read_to_string("src/lib.rs").expect("Unable to read lib file");
+
+  let config_file = read_to_string("godot-rust-helper.toml").expect("Unable to read config file");
+  let config_split = config_file.split("\n").collect::<Vec<&str>>();
+
+  let mod_file_path = Path::new("src/hello.rs");
+  let hello_gdns_file = Path::new("../platformer/godot-rust-helper-scripts/hello.gdns");
+
+  assert_eq!(lib_file.contains("hello"), false);
+  assert_eq!(config_split[3], "modules = []");
+  assert_eq!(mod_file_path.exists(), true);
+  assert_eq!(hello_gdns_file.exists(), false);
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should remove the gdns file of a module whose name has multiple words.
+#[test]
+fn destroy_multiple_capital_letters() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--output-path")
+    .arg("platformer/godot-rust-helper-output")
+    .arg("--nativescript-path")
+    .arg("platformer/godot-rust-helper-scripts");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("MainScene")
+    .output()
+    .expect("Unable to execute cargo run");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("destroy")
+    .arg("MainScene")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let mod_file_path = Path::new("src/main_scene.rs");
+  let gdns_file_path = Path::new("../platformer/godot-rust-helper-scripts/main_scene.gdns");
+
+  assert_eq!(mod_file_path.exists(), false);
+  assert_eq!(gdns_file_path.exists(), false);
 
   set_current_dir("../").expect("Unable to change to parent directory");
 
@@ -0,0 +1,153 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use assert_cmd::prelude::*;
+
+use std::env::set_current_dir;
+use std::error::Error;
+use std::fs::{read_to_string, write};
+use std::path::Path;
+use std::process::Command;
+
+mod test_utilities;
+use test_utilities::{cleanup_test_files, init_test};
+
+// It should add hand-written NativeClass structs to the config and lib file.
+#[test]
+fn import_native_classes() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--output-path")
+    .arg("platformer/godot-rust-helper-output")
+    .arg("--nativescript-path")
+    .arg("platformer/godot-rust-helper-scripts");
+
+  cmd.assert().success();
+
+  write(
+    "platformer_modules/src/goblin.rs",
+    "use gdnative::api::Node2D;\n\n#[derive(NativeClass)]\n#[inherit(Node2D)]\npub struct Goblin;\n",
+  )?;
+  write(
+    "platformer_modules/src/characters.rs",
+    "use gdnative::prelude::*;\n\n#[derive(NativeClass)]\n#[inherit(KinematicBody2D)]\npub struct Knight;\n\n#[derive(Debug, gdnative::NativeClass)]\n#[inherit(gdnative::api::KinematicBody2D)]\npub struct Wizard;\n\npub struct NotAClass;\n",
+  )?;
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("import")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let lib_file = read_to_string("src/lib.rs").expect("Unable to read lib file");
+  let lib_file_split = lib_file.split("\n").collect::<Vec<&str>>();
+
+  let config_file = read_to_string("godot-rust-helper.toml").expect("Unable to read config file");
+  let config_split = config_file.split("\n").collect::<Vec<&str>>();
+
+  assert_eq!(
+    config_split[3],
+    "modules = [\"Knight\", \"Wizard\", \"Goblin\"]"
+  );
+  assert_eq!(
+    config_file.contains("[modules.Knight]\npath = \"characters\"\nbase = \"KinematicBody2D\""),
+    true
+  );
+  assert_eq!(
+    config_file.contains("[modules.Wizard]\npath = \"characters\"\nbase = \"KinematicBody2D\""),
+    true
+  );
+  assert_eq!(
+    config_file.contains("[modules.Goblin]\nbase = \"Node2D\""),
+    true
+  );
+
+  assert_eq!(lib_file_split[4], "mod characters;");
+  assert_eq!(lib_file_split[5], "mod goblin;");
+  assert_eq!(lib_file_split[6], "// godot_rust_helper: end modules");
+  assert_eq!(
+    lib_file_split[10],
+    "\thandle.add_class::<characters::Knight>();"
+  );
+  assert_eq!(
+    lib_file_split[11],
+    "\thandle.add_class::<characters::Wizard>();"
+  );
+  assert_eq!(
+    lib_file_split[12],
+    "\thandle.add_class::<goblin::Goblin>();"
+  );
+
+  assert_eq!(
+    Path::new("../platformer/godot-rust-helper-scripts/knight.gdns").exists(),
+    true
+  );
+  assert_eq!(
+    Path::new("../platformer/godot-rust-helper-scripts/wizard.gdns").exists(),
+    true
+  );
+  assert_eq!(
+    Path::new("../platformer/godot-rust-helper-scripts/goblin.gdns").exists(),
+    true
+  );
+  assert_eq!(
+    Path::new("../platformer/godot-rust-helper-scripts/not_a_class.gdns").exists(),
+    false
+  );
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should not import modules that were created with the create command again.
+#[test]
+fn import_skip_existing_modules() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--output-path")
+    .arg("platformer/godot-rust-helper-output")
+    .arg("--nativescript-path")
+    .arg("platformer/godot-rust-helper-scripts");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Hello")
+    .output()
+    .expect("Unable to execute cargo run");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("import")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let config_file = read_to_string("godot-rust-helper.toml").expect("Unable to read config file");
+  let config_split = config_file.split("\n").collect::<Vec<&str>>();
+
+  assert_eq!(config_split[3], "modules = [\"Hello\"]");
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
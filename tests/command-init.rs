@@ -0,0 +1,290 @@
+#![allow(clippy::bool_assert_comparison)]
+#![allow(clippy::needless_return)]
+
+use assert_cmd::prelude::*;
+
+use std::env::set_current_dir;
+use std::error::Error;
+use std::fs::{create_dir_all, read_to_string, write};
+use std::path::Path;
+use std::process::Command;
+
+mod test_utilities;
+use test_utilities::{cleanup_test_files, init_test, DELIMITER};
+
+const GDNLIB_FILE: &str = "[entry]\n\nX11.64=\"res://bin/libplatformer_modules.so\"\nWindows.64=\"res://bin/platformer_modules.dll\"\n\n[dependencies]\n\nX11.64=[  ]\nWindows.64=[  ]\n\n[general]\n\nsingleton=false\nload_once=true\nsymbol_prefix=\"godot_\"\nreloadable=true\n";
+
+const LIB_FILE: &str = "use gdnative::prelude::*;\n\nmod characters;\nmod player;\n\nfn init(handle: InitHandle) {\n  handle.add_class::<player::Player>();\n  handle.add_class::<characters::Knight>();\n}\n\ngodot_init!(init);\n";
+
+/// Returns the contents of a .gdns file that uses the gdnlib file in platformer/bin.
+///
+/// # Arguments
+///
+/// `class_name` - The name of the class.
+fn gdns_file(class_name: &str) -> String {
+  return format!("[gd_resource type=\"NativeScript\" load_steps=2 format=2]\n\n[ext_resource path=\"res://bin/platformer.gdnlib\" type=\"GDNativeLibrary\" id=1]\n\n[resource]\n\nresource_name = \"{}\"\nclass_name = \"{}\"\nlibrary = ExtResource( 1 )\n", class_name, class_name);
+}
+
+/// Creates a crate with a Player and a Knight NativeClass in the directory.
+///
+/// # Arguments
+///
+/// `dir` - The directory of the crate.
+fn create_existing_crate(dir: &Path) {
+  create_dir_all(dir.join("src")).expect("Unable to create crate directory");
+  write(
+    dir.join("Cargo.toml"),
+    "[package]\nname = \"platformer_modules\"\nversion = \"0.1.0\"\nedition = \"2018\"\n\n[dependencies]\ngdnative = \"0.10\" # the bindings\n",
+  )
+  .expect("Unable to write Cargo.toml");
+  write(
+    dir.join("src").join("player.rs"),
+    "use gdnative::prelude::*;\n\n#[derive(NativeClass)]\n#[inherit(KinematicBody2D)]\npub struct Player;\n",
+  )
+  .expect("Unable to write module file");
+  write(
+    dir.join("src").join("characters.rs"),
+    "use gdnative::prelude::*;\n\n#[derive(NativeClass)]\npub struct Knight;\n",
+  )
+  .expect("Unable to write module file");
+}
+
+// It should record the existing gdnlib and gdns files and NativeClass structs in the config without changing them.
+#[test]
+fn init_adopts_existing_files() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  create_existing_crate(Path::new("platformer_modules"));
+  write("platformer_modules/src/lib.rs", LIB_FILE)?;
+  create_dir_all("platformer/bin")?;
+  create_dir_all("platformer/scripts")?;
+  create_dir_all("platformer/characters")?;
+  write("platformer/bin/platformer.gdnlib", GDNLIB_FILE)?;
+  write("platformer/scripts/player.gdns", gdns_file("Player"))?;
+  write("platformer/scripts/slime.gdns", gdns_file("Slime"))?;
+  write("platformer/characters/knight.gdns", gdns_file("Knight"))?;
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("init")
+    .arg("--godot")
+    .arg("../platformer")
+    .output()
+    .expect("Unable to execute cargo run");
+  let stdout = String::from_utf8_lossy(&output.stdout);
+
+  let cargo_toml = read_to_string("Cargo.toml").expect("Unable to read Cargo.toml");
+  let config = read_to_string("godot-rust-helper.toml").expect("Unable to read config");
+
+  assert_eq!(
+    cargo_toml,
+    "[package]\nname = \"platformer_modules\"\nversion = \"0.1.0\"\nedition = \"2018\"\n\n[lib]\ncrate-type = [\"cdylib\"]\n\n[dependencies]\ngdnative = \"0.10\" # the bindings\n"
+  );
+
+  assert_eq!(
+    config.contains("targets = [\"linux\", \"windows\"]\n"),
+    true
+  );
+  assert_eq!(
+    config.contains("modules = [\"Knight\", \"Player\"]\n"),
+    true
+  );
+  assert_eq!(config.contains("gdnative_version = \"0.10\"\n"), true);
+  assert_eq!(
+    config.contains(&format!("{}platformer{}bin\"\n", DELIMITER, DELIMITER)),
+    true
+  );
+  assert_eq!(
+    config.contains(&format!(
+      "library_file = \"{}{}tests{}platformer{}bin{}platformer.gdnlib\"\n",
+      test_utilities::get_root_path(),
+      DELIMITER,
+      DELIMITER,
+      DELIMITER,
+      DELIMITER
+    )),
+    true
+  );
+  assert_eq!(
+    config.contains(&format!(
+      "[modules.Knight]\npath = \"characters\"\ngdns = \"{}{}tests{}platformer{}characters{}knight.gdns\"\n",
+      test_utilities::get_root_path(),
+      DELIMITER,
+      DELIMITER,
+      DELIMITER,
+      DELIMITER
+    )),
+    true
+  );
+  assert_eq!(
+    config.contains("[modules.Player]\nbase = \"KinematicBody2D\"\n"),
+    true
+  );
+
+  assert_eq!(
+    read_to_string("src/lib.rs").expect("Unable to read lib file"),
+    LIB_FILE
+  );
+  assert_eq!(
+    read_to_string("../platformer/bin/platformer.gdnlib").expect("Unable to read gdnlib file"),
+    GDNLIB_FILE
+  );
+  assert_eq!(
+    read_to_string("../platformer/characters/knight.gdns").expect("Unable to read gdns file"),
+    gdns_file("Knight")
+  );
+  assert_eq!(
+    Path::new("../platformer/platformer_modules.gdnlib").exists(),
+    false
+  );
+  assert_eq!(
+    Path::new("../platformer/scripts/knight.gdns").exists(),
+    false
+  );
+
+  assert_eq!(
+    stdout.contains("uses the class Slime which isn't in the library"),
+    true
+  );
+  assert_eq!(
+    stdout.contains("doesn't have the begin and end markers"),
+    true
+  );
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should find the Godot project above the crate and create the files that don't exist yet.
+#[test]
+fn init_creates_missing_files() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  create_existing_crate(Path::new("platformer/platformer_modules"));
+
+  set_current_dir("platformer/platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../../Cargo.toml")
+    .arg("init")
+    .arg("--nativescript-path")
+    .arg("scripts")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let config = read_to_string("godot-rust-helper.toml").expect("Unable to read config");
+  let lib_file = read_to_string("src/lib.rs").expect("Unable to read lib file");
+  let knight_gdns = read_to_string("../scripts/knight.gdns").expect("Unable to read gdns file");
+
+  assert_eq!(config.contains("targets = [\"windows\"]\n"), true);
+  assert_eq!(config.contains("library_file"), false);
+  assert_eq!(Path::new("../platformer_modules.gdnlib").exists(), true);
+  assert_eq!(Path::new("../scripts/player.gdns").exists(), true);
+  assert_eq!(
+    knight_gdns.contains("[ext_resource path=\"res://platformer_modules.gdnlib\""),
+    true
+  );
+
+  assert_eq!(lib_file.contains("mod characters;\nmod player;\n"), true);
+  assert_eq!(
+    lib_file.contains("handle.add_class::<characters::Knight>();"),
+    true
+  );
+
+  set_current_dir("../../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should not run in a crate that already has a config.
+#[test]
+fn init_existing_config() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd.arg("new").arg("platformer_modules").arg("platformer");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  let config = read_to_string("godot-rust-helper.toml").expect("Unable to read config");
+
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("init")
+    .arg("--godot")
+    .arg("../platformer")
+    .output()
+    .expect("Unable to execute cargo run");
+  let stdout = String::from_utf8_lossy(&output.stdout);
+
+  assert_eq!(output.status.success(), false);
+  assert_eq!(
+    stdout.contains("The crate already has a godot-rust-helper.toml config file"),
+    true
+  );
+  assert_eq!(
+    read_to_string("godot-rust-helper.toml").expect("Unable to read config"),
+    config
+  );
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should write the config of a workspace with the members that have a config.
+#[test]
+fn init_workspace() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  create_dir_all("platformer_modules/gameplay")?;
+  create_dir_all("platformer_modules/tools/editor")?;
+  create_dir_all("platformer_modules/tools/cli")?;
+  write(
+    "platformer_modules/Cargo.toml",
+    "[workspace]\nmembers = [\"gameplay\", \"tools/*\"]\n",
+  )?;
+  write(
+    "platformer_modules/gameplay/godot-rust-helper.toml",
+    "[general]\nname = \"gameplay\"\n",
+  )?;
+  write(
+    "platformer_modules/tools/editor/godot-rust-helper.toml",
+    "[general]\nname = \"editor\"\n",
+  )?;
+
+  set_current_dir("platformer_modules").expect("Unable to change to workspace directory");
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("init")
+    .output()
+    .expect("Unable to execute cargo run");
+  let stdout = String::from_utf8_lossy(&output.stdout);
+
+  assert_eq!(
+    read_to_string("godot-rust-helper.toml").expect("Unable to read config"),
+    "[workspace]\nmembers = [\"gameplay\", \"tools/editor\"]\n"
+  );
+  assert_eq!(
+    stdout.contains("Skipping tools/cli since it doesn't have a godot-rust-helper.toml config"),
+    true
+  );
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
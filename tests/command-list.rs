@@ -0,0 +1,210 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use assert_cmd::prelude::*;
+
+use std::env::set_current_dir;
+use std::error::Error;
+use std::fs::{create_dir_all, remove_file, write};
+use std::process::Command;
+
+mod test_utilities;
+use test_utilities::{cleanup_test_files, init_test};
+
+// It should list the modules along with their files and the library's artifact.
+#[test]
+fn list_modules_and_artifact() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--targets=windows,linux")
+    .arg("--output-path")
+    .arg("platformer/godot-rust-helper-output")
+    .arg("--nativescript-path")
+    .arg("platformer/godot-rust-helper-scripts");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("MainScene")
+    .output()
+    .expect("Unable to execute cargo run");
+  remove_file("../platformer/godot-rust-helper-scripts/main_scene.gdns")?;
+
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("list")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let stdout = String::from_utf8(output.stdout)?;
+
+  assert_eq!(
+    stdout.contains("platformer_modules (targets: windows, linux)"),
+    true
+  );
+  assert_eq!(stdout.contains("not deployed"), true);
+  assert_eq!(stdout.contains("MainScene (Node, runtime)"), true);
+  assert_eq!(stdout.contains("  source: src/main_scene.rs\n"), true);
+  assert_eq!(
+    stdout.contains("  gdns: res://godot-rust-helper-scripts/main_scene.gdns (missing)"),
+    true
+  );
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should list the modules as JSON when --json is used.
+#[test]
+fn list_modules_json() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--output-path")
+    .arg("platformer/godot-rust-helper-output")
+    .arg("--nativescript-path")
+    .arg("platformer/godot-rust-helper-scripts");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Player")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("list")
+    .arg("--json")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let stdout = String::from_utf8(output.stdout)?;
+
+  assert_eq!(stdout.contains("\"name\": \"platformer_modules\""), true);
+  assert_eq!(stdout.contains("\"artifact_deployed\": false"), true);
+  assert_eq!(stdout.contains("\"name\": \"Player\""), true);
+  assert_eq!(stdout.contains("\"base\": \"Node\""), true);
+  assert_eq!(stdout.contains("\"registration\": \"runtime\""), true);
+  assert_eq!(stdout.contains("\"source\": \"src/player.rs\""), true);
+  assert_eq!(stdout.contains("\"source_exists\": true"), true);
+  assert_eq!(
+    stdout.contains("\"gdns\": \"res://godot-rust-helper-scripts/player.gdns\""),
+    true
+  );
+  assert_eq!(stdout.contains("\"gdns_exists\": true"), true);
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should ask for one of the libraries instead of failing to parse the config when used at the root of a workspace.
+#[test]
+fn list_in_workspace_root() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  create_dir_all("platformer_modules")?;
+  write(
+    "platformer_modules/Cargo.toml",
+    "[workspace]\nmembers = []\n",
+  )?;
+
+  set_current_dir("platformer_modules").expect("Unable to change to workspace directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("init")
+    .output()
+    .expect("Unable to execute cargo run");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("new")
+    .arg("gameplay")
+    .arg("../platformer")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("list")
+    .output()
+    .expect("Unable to execute cargo run");
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  let stderr = String::from_utf8_lossy(&output.stderr);
+
+  assert_eq!(output.status.success(), false);
+  assert_eq!(
+    stdout.contains(
+      "The list command has to be used inside of one of the workspace's libraries: gameplay"
+    ),
+    true
+  );
+  assert_eq!(stderr.contains("Unable to parse config"), false);
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should report a workspace config that can't be parsed instead of panicking.
+#[test]
+fn list_in_malformed_workspace_root() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  create_dir_all("platformer_modules")?;
+  write(
+    "platformer_modules/godot-rust-helper.toml",
+    "[workspace]\nmembers = \"gameplay\"\n",
+  )?;
+
+  set_current_dir("platformer_modules").expect("Unable to change to workspace directory");
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("list")
+    .output()
+    .expect("Unable to execute cargo run");
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  let stderr = String::from_utf8_lossy(&output.stderr);
+
+  assert_eq!(output.status.success(), false);
+  assert_eq!(
+    stdout.contains("Unable to parse the workspace config"),
+    true
+  );
+  assert_eq!(stderr.contains("panicked"), false);
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
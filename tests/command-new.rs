@@ -1,7 +1,10 @@
+#![allow(clippy::bool_assert_comparison)]
+
 use assert_cmd::prelude::*;
 
+use std::env::set_current_dir;
 use std::error::Error;
-use std::fs::read_to_string;
+use std::fs::{create_dir_all, read_to_string, write};
 use std::path::Path;
 use std::process::Command;
 
@@ -22,12 +25,14 @@ fn new_has_correct_cargo_toml() -> Result<(), Box<dyn Error>> {
     read_to_string("platformer_modules/Cargo.toml").expect("Unable to read Cargo.toml");
   let cargo_toml_split = cargo_toml.split("\n").collect::<Vec<&str>>();
 
-  assert_eq!(cargo_toml_split[6], "[lib]");
-  assert_eq!(cargo_toml_split[7], "crate-type = [\"cdylib\"]");
-  assert_eq!(cargo_toml_split[9], "[dependencies]");
-  assert_eq!(cargo_toml_split[10], "gdnative = \"0.9.1\"");
+  assert_eq!(cargo_toml_split[0], "[package]");
+  assert_eq!(cargo_toml_split[3], "edition = \"2021\"");
+  assert_eq!(cargo_toml_split[5], "[lib]");
+  assert_eq!(cargo_toml_split[6], "crate-type = [\"cdylib\"]");
+  assert_eq!(cargo_toml_split[8], "[dependencies]");
+  assert_eq!(cargo_toml_split[9], "gdnative = \"0.9.1\"");
   assert_eq!(
-    cargo_toml_split[11],
+    cargo_toml_split[10],
     "godot_rust_helper_ext = { git = \"https://github.com/robertcorponoi/godot_rust_helper_ext\" }"
   );
 
@@ -366,3 +371,302 @@ fn new_has_correct_gdnlib_all_targets() -> Result<(), Box<dyn Error>> {
 
   Ok(())
 }
+
+// It should create a GDExtension library for Godot 4 when --godot 4 is used.
+#[test]
+fn new_godot_4_library() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--targets")
+    .arg("windows,linux")
+    .arg("--godot")
+    .arg("4");
+
+  cmd.assert().success();
+
+  let cargo_toml =
+    read_to_string("platformer_modules/Cargo.toml").expect("Unable to read Cargo.toml");
+  let config =
+    read_to_string("platformer_modules/godot-rust-helper.toml").expect("Unable to read config");
+  let lib_file = read_to_string("platformer_modules/src/lib.rs").expect("Unable to read lib file");
+  let gdextension = read_to_string("platformer/platformer_modules.gdextension")
+    .expect("Unable to read gdextension file");
+
+  assert_eq!(
+    cargo_toml.contains("[dependencies]\ngodot = \"0.2\"\n"),
+    true
+  );
+  assert_eq!(cargo_toml.contains("gdnative"), false);
+  assert_eq!(config.contains("godot_version = \"4\"\n"), true);
+  assert_eq!(lib_file.starts_with("use godot::prelude::*;\n"), true);
+  assert_eq!(
+    lib_file.contains("#[gdextension]\nunsafe impl ExtensionLibrary for Extension {}\n"),
+    true
+  );
+  assert_eq!(
+    gdextension,
+    "[configuration]\n\nentry_symbol = \"gdext_rust_init\"\ncompatibility_minimum = 4.1\nreloadable = true\n\n[libraries]\n\nwindows.debug.x86_64 = \"res://platformer_modules.dll\"\nwindows.release.x86_64 = \"res://platformer_modules.dll\"\nlinux.debug.x86_64 = \"res://libplatformer_modules.so\"\nlinux.release.x86_64 = \"res://libplatformer_modules.so\"\n"
+  );
+  assert_eq!(
+    Path::new("platformer/platformer_modules.gdnlib").exists(),
+    false
+  );
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should detect that the Godot project is a Godot 4 project from its project.godot file.
+#[test]
+fn new_detects_godot_4_project() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  std::fs::write("platformer/project.godot", "config_version=5\n")?;
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd.arg("new").arg("platformer_modules").arg("platformer");
+
+  cmd.assert().success();
+
+  let config =
+    read_to_string("platformer_modules/godot-rust-helper.toml").expect("Unable to read config");
+
+  assert_eq!(config.contains("godot_version = \"4\"\n"), true);
+  assert_eq!(
+    Path::new("platformer/platformer_modules.gdextension").exists(),
+    true
+  );
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should leave the godot_rust_helper_ext dependency out when --no-ext is used.
+#[test]
+fn new_no_ext() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--no-ext");
+
+  cmd.assert().success();
+
+  let cargo_toml =
+    read_to_string("platformer_modules/Cargo.toml").expect("Unable to read Cargo.toml");
+  let config =
+    read_to_string("platformer_modules/godot-rust-helper.toml").expect("Unable to read config");
+
+  assert_eq!(
+    cargo_toml.ends_with("[dependencies]\ngdnative = \"0.9.1\"\n"),
+    true
+  );
+  assert_eq!(config.contains("[dependencies]\next = false\n"), true);
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should write the dependency sources passed to --gdnative-dep and --ext-dep to the Cargo.toml file and config.
+#[test]
+fn new_dependency_specs() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--gdnative-dep")
+    .arg("{ git = \"https://github.com/godot-rust/godot-rust\", features = [\"serde\"] }")
+    .arg("--ext-dep")
+    .arg("0.1");
+
+  cmd.assert().success();
+
+  let cargo_toml =
+    read_to_string("platformer_modules/Cargo.toml").expect("Unable to read Cargo.toml");
+  let cargo_toml_split = cargo_toml.split("\n").collect::<Vec<&str>>();
+  let config =
+    read_to_string("platformer_modules/godot-rust-helper.toml").expect("Unable to read config");
+
+  assert_eq!(cargo_toml_split[8], "[dependencies]");
+  assert_eq!(
+    cargo_toml_split[9],
+    "gdnative = { git = \"https://github.com/godot-rust/godot-rust\", features = [\"serde\"] }"
+  );
+  assert_eq!(cargo_toml_split[10], "godot_rust_helper_ext = \"0.1\"");
+
+  assert_eq!(
+    config.contains("[dependencies]\ngodot_rust_helper_ext = \"0.1\"\n\n[dependencies.gdnative]\ngit = \"https://github.com/godot-rust/godot-rust\"\nfeatures = [\"serde\"]\n"),
+    true
+  );
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should reject a dependency table that doesn't say where the dependency comes from.
+#[test]
+fn new_invalid_dependency_spec() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--gdnative-dep")
+    .arg("{ features = [\"serde\"] }");
+
+  cmd.assert().failure();
+
+  assert_eq!(Path::new("platformer_modules").exists(), false);
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should create the crate with the edition, authors, and license that were passed and initialize a git repository.
+#[test]
+fn new_crate_options() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--edition")
+    .arg("2018")
+    .arg("--authors")
+    .arg("Ann <ann@example.com>,Bob")
+    .arg("--license")
+    .arg("MIT")
+    .arg("--git");
+
+  cmd.assert().success();
+
+  let cargo_toml =
+    read_to_string("platformer_modules/Cargo.toml").expect("Unable to read Cargo.toml");
+  let cargo_toml_split = cargo_toml.split("\n").collect::<Vec<&str>>();
+  let gitignore =
+    read_to_string("platformer_modules/.gitignore").expect("Unable to read .gitignore");
+
+  assert_eq!(cargo_toml_split[0], "[package]");
+  assert_eq!(cargo_toml_split[1], "name = \"platformer_modules\"");
+  assert_eq!(cargo_toml_split[2], "version = \"0.1.0\"");
+  assert_eq!(
+    cargo_toml_split[3],
+    "authors = [\"Ann <ann@example.com>\", \"Bob\"]"
+  );
+  assert_eq!(cargo_toml_split[4], "edition = \"2018\"");
+  assert_eq!(cargo_toml_split[5], "license = \"MIT\"");
+  assert_eq!(cargo_toml_split[7], "[lib]");
+  assert_eq!(gitignore, "/target\n");
+  assert_eq!(Path::new("platformer_modules/.git").exists(), true);
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should create the crate without a git repository by default.
+#[test]
+fn new_without_git() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd.arg("new").arg("platformer_modules").arg("platformer");
+
+  cmd.assert().success();
+
+  let cargo_toml =
+    read_to_string("platformer_modules/Cargo.toml").expect("Unable to read Cargo.toml");
+
+  assert_eq!(cargo_toml.contains("edition = \"2021\"\n"), true);
+  assert_eq!(Path::new("platformer_modules/.gitignore").exists(), true);
+  assert_eq!(Path::new("platformer_modules/.git").exists(), false);
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should not create a library whose name can't be used as a crate name.
+#[test]
+fn new_invalid_crate_name() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd.arg("new").arg("2d_modules").arg("platformer");
+
+  cmd.assert().failure();
+
+  assert_eq!(Path::new("2d_modules").exists(), false);
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should add a library that's created at the root of a workspace to the workspace's members.
+#[test]
+fn new_in_workspace() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  create_dir_all("platformer_modules/tools")?;
+  write(
+    "platformer_modules/Cargo.toml",
+    "[workspace]\nmembers = [\"tools/*\"]\nresolver = \"2\"\n",
+  )?;
+  write(
+    "platformer_modules/godot-rust-helper.toml",
+    "[workspace]\nmembers = []\n",
+  )?;
+
+  set_current_dir("platformer_modules").expect("Unable to change to workspace directory");
+  for library in &["gameplay", "tools/editor"] {
+    Command::new("cargo")
+      .arg("run")
+      .arg("--manifest-path=../../Cargo.toml")
+      .arg("new")
+      .arg(library)
+      .arg("../platformer")
+      .output()
+      .expect("Unable to execute cargo run");
+  }
+
+  assert_eq!(
+    read_to_string("godot-rust-helper.toml").expect("Unable to read config"),
+    "[workspace]\nmembers = [\"gameplay\", \"tools/editor\"]\n"
+  );
+  assert_eq!(
+    read_to_string("Cargo.toml").expect("Unable to read Cargo.toml"),
+    "[workspace]\nmembers = [\"tools/*\", \"gameplay\"]\nresolver = \"2\"\n"
+  );
+  assert_eq!(Path::new("gameplay/godot-rust-helper.toml").exists(), true);
+  assert_eq!(
+    Path::new("tools/editor/godot-rust-helper.toml").exists(),
+    true
+  );
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
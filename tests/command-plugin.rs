@@ -1,3 +1,5 @@
+#![allow(clippy::bool_assert_comparison)]
+
 use assert_cmd::prelude::*;
 
 use std::error::Error;
@@ -30,6 +32,8 @@ fn plugin_has_correct_cargo_toml() -> Result<(), Box<dyn Error>> {
     read_to_string("directory_browser/Cargo.toml").expect("Unable to read Cargo.toml");
   let cargo_toml_split = cargo_toml.split("\n").collect::<Vec<&str>>();
 
+  assert_eq!(cargo_toml_split[0], "[package]");
+  assert_eq!(cargo_toml_split[3], "authors = [\"Bob\"]");
   assert_eq!(cargo_toml_split[6], "[lib]");
   assert_eq!(cargo_toml_split[7], "crate-type = [\"cdylib\"]");
   assert_eq!(cargo_toml_split[9], "[dependencies]");
@@ -516,9 +520,9 @@ fn plugin_has_correct_lib_file() -> Result<(), Box<dyn Error>> {
     read_to_string("directory_browser/src/lib.rs").expect("Unable to read lib file");
   let plugin_lib_file_split = plugin_lib_file.split("\n").collect::<Vec<&str>>();
 
-  assert_eq!(plugin_lib_file_split[3], "mod directory_browser;");
+  assert_eq!(plugin_lib_file_split[4], "mod directory_browser;");
   assert_eq!(
-    plugin_lib_file_split[6],
+    plugin_lib_file_split[9],
     "\thandle.add_tool_class::<directory_browser::DirectoryBrowser>();"
   );
 
@@ -526,3 +530,53 @@ fn plugin_has_correct_lib_file() -> Result<(), Box<dyn Error>> {
 
   Ok(())
 }
+
+// It should create a GDExtension plugin for Godot 4 whose base class is registered as an editor plugin.
+#[test]
+fn plugin_godot_4() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("plugin")
+    .arg("Directory Browser")
+    .arg("directory_browser")
+    .arg("platformer")
+    .arg("--godot")
+    .arg("4");
+
+  cmd.assert().success();
+
+  let base_script = read_to_string("directory_browser/src/directory_browser.rs")
+    .expect("Unable to read plugin's base script");
+  let base_script_split = base_script.split("\n").collect::<Vec<&str>>();
+
+  assert_eq!(
+    base_script_split[0],
+    "use godot::classes::{IEditorPlugin, EditorPlugin};"
+  );
+  assert_eq!(
+    base_script_split[4],
+    "#[class(tool, editor_plugin, base=EditorPlugin)]"
+  );
+  assert_eq!(
+    base_script_split[10],
+    "impl IEditorPlugin for DirectoryBrowser {"
+  );
+  assert_eq!(
+    Path::new("platformer/addons/directory_browser/directory_browser.gdextension").exists(),
+    true
+  );
+  assert_eq!(
+    Path::new("platformer/addons/directory_browser/plugin.cfg").exists(),
+    false
+  );
+  assert_eq!(
+    Path::new("platformer/addons/directory_browser/directory_browser.gdns").exists(),
+    false
+  );
+
+  cleanup_test_files();
+
+  Ok(())
+}
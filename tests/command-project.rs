@@ -0,0 +1,475 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use assert_cmd::prelude::*;
+
+use std::env::set_current_dir;
+use std::error::Error;
+use std::fs::{create_dir_all, read_to_string, remove_file, write};
+use std::path::Path;
+use std::process::Command;
+
+mod test_utilities;
+use test_utilities::{cleanup_test_files, init_test, DELIMITER};
+
+/// Creates the level editor's Godot project next to the game's.
+fn create_editor_project() {
+  create_dir_all("platformer_editor").expect("Unable to create Godot project directory");
+  write("platformer_editor/project.godot", "").expect("Unable to create project.godot file");
+}
+
+// It should create the library file and the gdns files of the existing modules in the other project.
+#[test]
+fn project_add() -> Result<(), Box<dyn Error>> {
+  init_test();
+  create_editor_project();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd.arg("new").arg("platformer_modules").arg("platformer");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Hello")
+    .output()
+    .expect("Unable to execute cargo run");
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("project")
+    .arg("../platformer_editor")
+    .arg("--output-path")
+    .arg("bin")
+    .arg("--nativescript-path")
+    .arg("scripts")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let config = read_to_string("godot-rust-helper.toml").expect("Unable to read config");
+  let gdnlib_file = read_to_string("../platformer_editor/bin/platformer_modules.gdnlib")
+    .expect("Unable to read gdnlib file");
+  let hello_gdns_file =
+    read_to_string("../platformer_editor/scripts/hello.gdns").expect("Unable to read gdns file");
+
+  assert_eq!(output.status.success(), true);
+  assert_eq!(config.contains("[[paths.projects]]\n"), true);
+  assert_eq!(
+    config.contains(&format!(
+      "platformer_editor{}bin\"\nnativescript = ",
+      DELIMITER
+    )),
+    true
+  );
+  assert_eq!(
+    gdnlib_file.contains("Windows.64=\"res://bin/platformer_modules.dll\""),
+    true
+  );
+  assert_eq!(
+    hello_gdns_file.contains("[ext_resource path=\"res://bin/platformer_modules.gdnlib\""),
+    true
+  );
+  assert_eq!(Path::new("../platformer/hello.gdns").exists(), true);
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should create and remove the gdns files of modules in every project.
+#[test]
+fn project_create_and_destroy() -> Result<(), Box<dyn Error>> {
+  init_test();
+  create_editor_project();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd.arg("new").arg("platformer_modules").arg("platformer");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("project")
+    .arg("../platformer_editor")
+    .output()
+    .expect("Unable to execute cargo run");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Hello")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  assert_eq!(Path::new("../platformer/hello.gdns").exists(), true);
+  assert_eq!(Path::new("../platformer_editor/hello.gdns").exists(), true);
+
+  // A scene in the editor project that uses the module keeps it from being destroyed.
+  write(
+    "../platformer_editor/level.tscn",
+    "[ext_resource path=\"res://hello.gdns\" type=\"Script\" id=1]\n",
+  )?;
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("destroy")
+    .arg("Hello")
+    .output()
+    .expect("Unable to execute cargo run");
+  let stdout = String::from_utf8_lossy(&output.stdout);
+
+  assert_eq!(output.status.success(), false);
+  assert_eq!(
+    stdout.contains("res://hello.gdns is still referenced in"),
+    true
+  );
+  assert_eq!(stdout.contains("  level.tscn"), true);
+  assert_eq!(Path::new("../platformer_editor/hello.gdns").exists(), true);
+
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("destroy")
+    .arg("Hello")
+    .arg("--force")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  assert_eq!(Path::new("../platformer/hello.gdns").exists(), false);
+  assert_eq!(Path::new("../platformer_editor/hello.gdns").exists(), false);
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should only use the icon of a module in the project that it was copied into.
+#[test]
+fn project_create_with_icon() -> Result<(), Box<dyn Error>> {
+  init_test();
+  create_editor_project();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd.arg("new").arg("platformer_modules").arg("platformer");
+
+  cmd.assert().success();
+
+  write("enemy.svg", "<svg xmlns=\"http://www.w3.org/2000/svg\"/>")?;
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("project")
+    .arg("../platformer_editor")
+    .output()
+    .expect("Unable to execute cargo run");
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Enemy")
+    .arg("--named")
+    .arg("--icon")
+    .arg("../enemy.svg")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let gdns_file = read_to_string("../platformer/enemy.gdns").expect("Unable to read gdns file");
+  let editor_gdns_file =
+    read_to_string("../platformer_editor/enemy.gdns").expect("Unable to read gdns file");
+
+  assert_eq!(output.status.success(), true);
+  assert_eq!(Path::new("../platformer/enemy.svg").exists(), true);
+  assert_eq!(
+    gdns_file.contains("script_class_icon_path = \"res://enemy.svg\""),
+    true
+  );
+  assert_eq!(Path::new("../platformer_editor/enemy.svg").exists(), false);
+  assert_eq!(
+    editor_gdns_file.contains("script_class_name = \"Enemy\""),
+    true
+  );
+  assert_eq!(editor_gdns_file.contains("script_class_icon_path"), false);
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  remove_file("enemy.svg")?;
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should rename the gdns files of the module and the references to them in every project.
+#[test]
+fn project_rename() -> Result<(), Box<dyn Error>> {
+  init_test();
+  create_editor_project();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd.arg("new").arg("platformer_modules").arg("platformer");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("project")
+    .arg("../platformer_editor")
+    .output()
+    .expect("Unable to execute cargo run");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Hello")
+    .output()
+    .expect("Unable to execute cargo run");
+  write(
+    "../platformer_editor/level.tscn",
+    "[ext_resource path=\"res://hello.gdns\" type=\"Script\" id=1]\n",
+  )?;
+
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("rename")
+    .arg("Hello")
+    .arg("Greeter")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let editor_gdns_file =
+    read_to_string("../platformer_editor/greeter.gdns").expect("Unable to read gdns file");
+  let level_file =
+    read_to_string("../platformer_editor/level.tscn").expect("Unable to read scene file");
+
+  assert_eq!(output.status.success(), true);
+  assert_eq!(Path::new("../platformer/hello.gdns").exists(), false);
+  assert_eq!(Path::new("../platformer/greeter.gdns").exists(), true);
+  assert_eq!(Path::new("../platformer_editor/hello.gdns").exists(), false);
+  assert_eq!(editor_gdns_file.contains("class_name = \"Greeter\""), true);
+  assert_eq!(level_file.contains("path=\"res://greeter.gdns\""), true);
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should rename and then destroy the module in each of the projects that the library is deployed to.
+#[test]
+fn project_rename_and_destroy_in_several_projects() -> Result<(), Box<dyn Error>> {
+  init_test();
+  create_editor_project();
+  create_dir_all("platformer_tools").expect("Unable to create Godot project directory");
+  write("platformer_tools/project.godot", "").expect("Unable to create project.godot file");
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd.arg("new").arg("platformer_modules").arg("platformer");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  for project in &["../platformer_editor", "../platformer_tools"] {
+    Command::new("cargo")
+      .arg("run")
+      .arg("--manifest-path=../../Cargo.toml")
+      .arg("project")
+      .arg(project)
+      .output()
+      .expect("Unable to execute cargo run");
+  }
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Hello")
+    .output()
+    .expect("Unable to execute cargo run");
+  write(
+    "../platformer_tools/level.tscn",
+    "[ext_resource path=\"res://hello.gdns\" type=\"Script\" id=1]\n",
+  )?;
+
+  let config = read_to_string("godot-rust-helper.toml").expect("Unable to read config");
+
+  assert_eq!(config.matches("[[paths.projects]]").count(), 2);
+
+  let rename_output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("rename")
+    .arg("Hello")
+    .arg("Greeter")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let level_file =
+    read_to_string("../platformer_tools/level.tscn").expect("Unable to read scene file");
+
+  assert_eq!(rename_output.status.success(), true);
+  for project in &[
+    "../platformer",
+    "../platformer_editor",
+    "../platformer_tools",
+  ] {
+    let gdns_file =
+      read_to_string(format!("{}/greeter.gdns", project)).expect("Unable to read gdns file");
+
+    assert_eq!(
+      Path::new(&format!("{}/hello.gdns", project)).exists(),
+      false
+    );
+    assert_eq!(gdns_file.contains("class_name = \"Greeter\""), true);
+  }
+  assert_eq!(level_file.contains("path=\"res://greeter.gdns\""), true);
+
+  remove_file("../platformer_tools/level.tscn")?;
+  let destroy_output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("destroy")
+    .arg("Greeter")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  assert_eq!(destroy_output.status.success(), true);
+  for project in &[
+    "../platformer",
+    "../platformer_editor",
+    "../platformer_tools",
+  ] {
+    assert_eq!(
+      Path::new(&format!("{}/greeter.gdns", project)).exists(),
+      false
+    );
+  }
+  assert_eq!(Path::new("src/greeter.rs").exists(), false);
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should create the missing gdns files and report the orphaned ones in every project.
+#[test]
+fn project_sync() -> Result<(), Box<dyn Error>> {
+  init_test();
+  create_editor_project();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd.arg("new").arg("platformer_modules").arg("platformer");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("project")
+    .arg("../platformer_editor")
+    .output()
+    .expect("Unable to execute cargo run");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Hello")
+    .output()
+    .expect("Unable to execute cargo run");
+  remove_file("../platformer_editor/hello.gdns")?;
+  write(
+    "../platformer_editor/old.gdns",
+    "[ext_resource path=\"res://platformer_modules.gdnlib\" type=\"GDNativeLibrary\" id=1]\n",
+  )?;
+
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("sync")
+    .output()
+    .expect("Unable to execute cargo run");
+  let stdout = String::from_utf8_lossy(&output.stdout);
+
+  assert_eq!(output.status.success(), true);
+  assert_eq!(Path::new("../platformer_editor/hello.gdns").exists(), true);
+  assert_eq!(stdout.contains("orphaned file"), true);
+  assert_eq!(stdout.contains("old.gdns"), true);
+  assert_eq!(Path::new("../platformer_editor/old.gdns").exists(), true);
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should stop deploying to the project without removing its files.
+#[test]
+fn project_remove() -> Result<(), Box<dyn Error>> {
+  init_test();
+  create_editor_project();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd.arg("new").arg("platformer_modules").arg("platformer");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  let config = read_to_string("godot-rust-helper.toml").expect("Unable to read config");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("project")
+    .arg("../platformer_editor")
+    .output()
+    .expect("Unable to execute cargo run");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("project")
+    .arg("../platformer_editor")
+    .arg("--remove")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  assert_eq!(
+    read_to_string("godot-rust-helper.toml").expect("Unable to read config"),
+    config
+  );
+  assert_eq!(
+    Path::new("../platformer_editor/platformer_modules.gdnlib").exists(),
+    true
+  );
+
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("project")
+    .arg("../platformer")
+    .output()
+    .expect("Unable to execute cargo run");
+  let stdout = String::from_utf8_lossy(&output.stdout);
+
+  assert_eq!(output.status.success(), false);
+  assert_eq!(stdout.contains("The library is already deployed to"), true);
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
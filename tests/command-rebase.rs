@@ -0,0 +1,152 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use assert_cmd::prelude::*;
+
+use std::env::set_current_dir;
+use std::error::Error;
+use std::fs::{read_to_string, write};
+use std::process::Command;
+
+mod test_utilities;
+use test_utilities::{cleanup_test_files, init_test};
+
+// It should recreate the library file with the targets in the config when no targets are passed.
+#[test]
+fn rebase_keeps_config_targets() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--targets")
+    .arg("windows,linux");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("rebase")
+    .arg("../platformer")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let gdnlib_file =
+    read_to_string("../platformer/platformer_modules.gdnlib").expect("Unable to read gdnlib file");
+
+  assert_eq!(output.status.success(), true);
+  assert_eq!(
+    gdnlib_file.contains(
+      "[entry]\n\nX11.64=\"res://libplatformer_modules.so\"\nWindows.64=\"res://platformer_modules.dll\"\n"
+    ),
+    true
+  );
+  assert_eq!(
+    gdnlib_file.contains("\nX11.64=[  ]\nWindows.64=[  ]\n"),
+    true
+  );
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should report a target in the config that a gdextension file can't be created for instead of panicking.
+#[test]
+fn rebase_godot_4_invalid_target() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--godot")
+    .arg("4");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  let config = read_to_string("godot-rust-helper.toml").expect("Unable to read config");
+  write(
+    "godot-rust-helper.toml",
+    config.replace(
+      "targets = [\"windows\"]",
+      "targets = [\"windows\", \"android\"]",
+    ),
+  )
+  .expect("Unable to write config");
+
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("rebase")
+    .arg("../platformer")
+    .output()
+    .expect("Unable to execute cargo run");
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  let stderr = String::from_utf8_lossy(&output.stderr);
+
+  assert_eq!(output.status.success(), false);
+  assert_eq!(
+    stdout.contains("An invalid target was specified: android"),
+    true
+  );
+  assert_eq!(stderr.contains("panicked"), false);
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should report a target in the config that a gdnlib file can't be created for instead of panicking.
+#[test]
+fn rebase_godot_3_invalid_target() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd.arg("new").arg("platformer_modules").arg("platformer");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  let config = read_to_string("godot-rust-helper.toml").expect("Unable to read config");
+  write(
+    "godot-rust-helper.toml",
+    config.replace(
+      "targets = [\"windows\"]",
+      "targets = [\"windows\", \"android\"]",
+    ),
+  )
+  .expect("Unable to write config");
+
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("rebase")
+    .arg("../platformer")
+    .output()
+    .expect("Unable to execute cargo run");
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  let stderr = String::from_utf8_lossy(&output.stderr);
+
+  assert_eq!(output.status.success(), false);
+  assert_eq!(
+    stdout.contains("An invalid target was specified: android"),
+    true
+  );
+  assert_eq!(stderr.contains("panicked"), false);
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
@@ -0,0 +1,351 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use assert_cmd::prelude::*;
+
+use std::env::set_current_dir;
+use std::error::Error;
+use std::fs::{read_to_string, write};
+use std::path::Path;
+use std::process::Command;
+
+mod test_utilities;
+use test_utilities::{cleanup_test_files, init_test};
+
+// It should rename the module's file, struct, config entry, and lib entry.
+#[test]
+fn rename_module_source_and_config() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--output-path")
+    .arg("platformer/godot-rust-helper-output")
+    .arg("--nativescript-path")
+    .arg("platformer/godot-rust-helper-scripts");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Player")
+    .output()
+    .expect("Unable to execute cargo run");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("rename")
+    .arg("Player")
+    .arg("MainHero")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let lib_file = read_to_string("src/lib.rs").expect("Unable to read lib file");
+  let lib_file_split = lib_file.split("\n").collect::<Vec<&str>>();
+
+  let config_file = read_to_string("godot-rust-helper.toml").expect("Unable to read config file");
+  let config_split = config_file.split("\n").collect::<Vec<&str>>();
+
+  let mod_file = read_to_string("src/main_hero.rs").expect("Unable to read module file");
+
+  assert_eq!(lib_file_split[4], "mod main_hero;");
+  assert_eq!(
+    lib_file_split[9],
+    "\thandle.add_class::<main_hero::MainHero>();"
+  );
+  assert_eq!(config_split[3], "modules = [\"MainHero\"]");
+  assert_eq!(Path::new("src/player.rs").exists(), false);
+  assert_eq!(mod_file.contains("pub struct MainHero;"), true);
+  assert_eq!(mod_file.contains("impl MainHero {"), true);
+  assert_eq!(mod_file.contains("Player"), false);
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should move the gdns file and update the scenes that reference it.
+#[test]
+fn rename_module_gdns_and_scenes() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--output-path")
+    .arg("platformer/godot-rust-helper-output")
+    .arg("--nativescript-path")
+    .arg("platformer/godot-rust-helper-scripts");
+
+  cmd.assert().success();
+
+  write(
+    "platformer/level.tscn",
+    "[gd_scene load_steps=2 format=2]\n\n[ext_resource path=\"res://godot-rust-helper-scripts/player.gdns\" type=\"Script\" id=1]\n\n[node name=\"Player\" type=\"Node\"]\nscript = ExtResource( 1 )\n",
+  )?;
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Player")
+    .output()
+    .expect("Unable to execute cargo run");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("rename")
+    .arg("Player")
+    .arg("MainHero")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let old_gdns_file = Path::new("../platformer/godot-rust-helper-scripts/player.gdns");
+  let gdns_file = read_to_string("../platformer/godot-rust-helper-scripts/main_hero.gdns")
+    .expect("Unable to read gdns file");
+  let gdns_file_split = gdns_file.split("\n").collect::<Vec<&str>>();
+
+  let scene_file = read_to_string("../platformer/level.tscn").expect("Unable to read scene file");
+  let scene_file_split = scene_file.split("\n").collect::<Vec<&str>>();
+
+  assert_eq!(old_gdns_file.exists(), false);
+  assert_eq!(gdns_file_split[6], "resource_name = \"MainHero\"");
+  assert_eq!(gdns_file_split[7], "class_name = \"MainHero\"");
+  assert_eq!(
+    scene_file_split[2],
+    "[ext_resource path=\"res://godot-rust-helper-scripts/main_hero.gdns\" type=\"Script\" id=1]"
+  );
+  assert_eq!(scene_file_split[4], "[node name=\"Player\" type=\"Node\"]");
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should only rename the struct and the code that uses it, not comments, strings, or other items with the same
+// name.
+#[test]
+fn rename_module_keeps_other_names() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd.arg("new").arg("platformer_modules").arg("platformer");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Player")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let mod_file = read_to_string("src/player.rs").expect("Unable to read module file");
+  write(
+    "src/player.rs",
+    mod_file.replace(
+      "godot_print!(\"hello, world.\");",
+      "// The Player is ready.\n\t\tgodot_print!(\"Player\");\n\t\tlet _kind = Kind::Player;",
+    ) + "\nenum Kind {\n\tPlayer,\n}\n",
+  )
+  .expect("Unable to write module file");
+
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("rename")
+    .arg("Player")
+    .arg("Hero")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let mod_file = read_to_string("src/hero.rs").expect("Unable to read module file");
+
+  assert_eq!(mod_file.contains("pub struct Hero;"), true);
+  assert_eq!(mod_file.contains("impl Hero {"), true);
+  assert_eq!(mod_file.contains("user_data::LocalCellData<Hero>"), true);
+  assert_eq!(mod_file.contains("-> Self {\n\t\tHero\n\t}"), true);
+  assert_eq!(mod_file.contains("// The Player is ready."), true);
+  assert_eq!(mod_file.contains("godot_print!(\"Player\");"), true);
+  assert_eq!(mod_file.contains("Kind::Player;"), true);
+  assert_eq!(mod_file.contains("enum Kind {\n\tPlayer,\n}"), true);
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should not overwrite a file that already has the module's new file name.
+#[test]
+fn rename_module_existing_file() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd.arg("new").arg("platformer_modules").arg("platformer");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Player")
+    .output()
+    .expect("Unable to execute cargo run");
+  write("src/hero.rs", "// Notes about the hero.\n").expect("Unable to write file");
+  let config = read_to_string("godot-rust-helper.toml").expect("Unable to read config");
+
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("rename")
+    .arg("Player")
+    .arg("Hero")
+    .output()
+    .expect("Unable to execute cargo run");
+  let stdout = String::from_utf8_lossy(&output.stdout);
+
+  assert_eq!(output.status.success(), false);
+  assert_eq!(stdout.contains("hero.rs already exists"), true);
+  assert_eq!(
+    read_to_string("src/hero.rs").expect("Unable to read file"),
+    "// Notes about the hero.\n"
+  );
+  assert_eq!(Path::new("src/player.rs").exists(), true);
+  assert_eq!(
+    read_to_string("godot-rust-helper.toml").expect("Unable to read config"),
+    config
+  );
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should point the plugin.cfg file to the new gdns file when the plugin's base module is renamed.
+#[test]
+fn rename_plugin_base_module() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("plugin")
+    .arg("Directory Browser")
+    .arg("directory_browser")
+    .arg("platformer");
+
+  cmd.assert().success();
+
+  set_current_dir("directory_browser").expect("Unable to change to plugin directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("rename")
+    .arg("DirectoryBrowser")
+    .arg("FileBrowser")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let plugin_cfg = read_to_string("../platformer/addons/directory_browser/plugin.cfg")
+    .expect("Unable to read plugin.cfg");
+
+  assert_eq!(plugin_cfg.contains("script=\"file_browser.gdns\""), true);
+  assert_eq!(plugin_cfg.contains("directory_browser.gdns"), false);
+  assert_eq!(
+    Path::new("../platformer/addons/directory_browser/file_browser.gdns").exists(),
+    true
+  );
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should keep the class prefix on the Godot class name and leave it off the struct.
+#[test]
+fn rename_module_with_class_prefix() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--output-path")
+    .arg("platformer/godot-rust-helper-output")
+    .arg("--nativescript-path")
+    .arg("platformer/godot-rust-helper-scripts")
+    .arg("--class-prefix")
+    .arg("Rs")
+    .arg("--gdnative")
+    .arg("0.10");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Timer")
+    .output()
+    .expect("Unable to execute cargo run");
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("rename")
+    .arg("Timer")
+    .arg("Clock")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let lib_file = read_to_string("src/lib.rs").expect("Unable to read lib file");
+  let config_file = read_to_string("godot-rust-helper.toml").expect("Unable to read config file");
+  let mod_file = read_to_string("src/clock.rs").expect("Unable to read module file");
+  let gdns_file = read_to_string("../platformer/godot-rust-helper-scripts/clock.gdns")
+    .expect("Unable to read gdns file");
+
+  assert_eq!(output.status.success(), true);
+  assert_eq!(
+    lib_file.contains("handle.add_class_as::<clock::Clock>(\"RsClock\".to_string());"),
+    true
+  );
+  assert_eq!(config_file.contains("modules = [\"Clock\"]"), true);
+  assert_eq!(
+    config_file.contains("[modules.Clock]\nclass_name = \"RsClock\"\n"),
+    true
+  );
+  assert_eq!(mod_file.contains("pub struct Clock;"), true);
+  assert_eq!(gdns_file.contains("class_name = \"RsClock\""), true);
+  assert_eq!(
+    Path::new("../platformer/godot-rust-helper-scripts/timer.gdns").exists(),
+    false
+  );
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
@@ -0,0 +1,156 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use assert_cmd::prelude::*;
+
+use std::env::set_current_dir;
+use std::error::Error;
+use std::fs::{read_to_string, remove_file, write};
+use std::path::Path;
+use std::process::Command;
+
+mod test_utilities;
+use test_utilities::{cleanup_test_files, init_test};
+
+// It should recreate missing gdns files, fix stale library paths, and regenerate the lib file.
+#[test]
+fn sync_restore_generated_files() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--output-path")
+    .arg("platformer/godot-rust-helper-output")
+    .arg("--nativescript-path")
+    .arg("platformer/godot-rust-helper-scripts");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Player")
+    .output()
+    .expect("Unable to execute cargo run");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Enemy")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let player_gdns_path = "../platformer/godot-rust-helper-scripts/player.gdns";
+  let enemy_gdns_path = "../platformer/godot-rust-helper-scripts/enemy.gdns";
+
+  remove_file(player_gdns_path)?;
+  let enemy_gdns = read_to_string(enemy_gdns_path)?;
+  write(
+    enemy_gdns_path,
+    enemy_gdns.replace("res://godot-rust-helper-output/", "res://old-output/"),
+  )?;
+  let lib_file = read_to_string("src/lib.rs")?;
+  write("src/lib.rs", lib_file.replace("mod enemy;\n", ""))?;
+
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("sync")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let lib_file = read_to_string("src/lib.rs").expect("Unable to read lib file");
+  let lib_file_split = lib_file.split("\n").collect::<Vec<&str>>();
+
+  let player_gdns = read_to_string(player_gdns_path).expect("Unable to read gdns file");
+  let enemy_gdns = read_to_string(enemy_gdns_path).expect("Unable to read gdns file");
+  let enemy_gdns_split = enemy_gdns.split("\n").collect::<Vec<&str>>();
+
+  assert_eq!(lib_file_split[4], "mod player;");
+  assert_eq!(lib_file_split[5], "mod enemy;");
+  assert_eq!(player_gdns.contains("class_name = \"Player\""), true);
+  assert_eq!(
+    enemy_gdns_split[2],
+    "[ext_resource path=\"res://godot-rust-helper-output/platformer_modules.gdnlib\" type=\"GDNativeLibrary\" id=1]"
+  );
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should report orphaned files and only remove them when --prune is used.
+#[test]
+fn sync_prune_orphaned_files() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--output-path")
+    .arg("platformer/godot-rust-helper-output")
+    .arg("--nativescript-path")
+    .arg("platformer/godot-rust-helper-scripts");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Player")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let config_file = read_to_string("godot-rust-helper.toml")?;
+  write(
+    "godot-rust-helper.toml",
+    config_file.replace("modules = [\"Player\"]", "modules = []"),
+  )?;
+
+  let mod_file_path = Path::new("src/player.rs");
+  let gdns_file_path = Path::new("../platformer/godot-rust-helper-scripts/player.gdns");
+
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("sync")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let stdout = String::from_utf8(output.stdout)?;
+  let lib_file = read_to_string("src/lib.rs").expect("Unable to read lib file");
+
+  assert_eq!(stdout.contains("orphaned file"), true);
+  assert_eq!(stdout.contains("player.rs"), true);
+  assert_eq!(stdout.contains("player.gdns"), true);
+  assert_eq!(lib_file.contains("player"), false);
+  assert_eq!(mod_file_path.exists(), true);
+  assert_eq!(gdns_file_path.exists(), true);
+
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("sync")
+    .arg("--prune")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  assert_eq!(mod_file_path.exists(), false);
+  assert_eq!(gdns_file_path.exists(), false);
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
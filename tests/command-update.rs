@@ -0,0 +1,328 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use assert_cmd::prelude::*;
+
+use std::env::set_current_dir;
+use std::error::Error;
+use std::fs::{read_to_string, write};
+use std::process::Command;
+
+mod test_utilities;
+use test_utilities::{cleanup_test_files, init_test};
+
+// It should bump gdnative and convert the modules and lib file to the newer version.
+#[test]
+fn update_gdnative_version() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd.arg("new").arg("platformer_modules").arg("platformer");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Hello")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let mod_file = read_to_string("src/hello.rs").expect("Unable to read module file");
+  write(
+    "src/hello.rs",
+    mod_file.replace(
+      "\tfn new(",
+      "\tfn register(builder: &ClassBuilder<Self>) {\n\t\tbuilder.add_signal(Signal { name: \"hit\", args: &[] });\n\t}\n\n\tfn new(",
+    ),
+  )
+  .expect("Unable to write module file");
+
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("update")
+    .arg("--gdnative")
+    .arg("0.10")
+    .output()
+    .expect("Unable to execute cargo run");
+  let stdout = String::from_utf8_lossy(&output.stdout);
+
+  let cargo_toml = read_to_string("Cargo.toml").expect("Unable to read Cargo.toml");
+  let config = read_to_string("godot-rust-helper.toml").expect("Unable to read config");
+  let lib_file = read_to_string("src/lib.rs").expect("Unable to read lib file");
+  let mod_file = read_to_string("src/hello.rs").expect("Unable to read module file");
+
+  assert_eq!(cargo_toml.contains("gdnative = \"0.10\"\n"), true);
+  assert_eq!(config.contains("gdnative_version = \"0.10\"\n"), true);
+
+  assert_eq!(
+    lib_file.contains("fn init(handle: gdnative::init::InitHandle) {"),
+    true
+  );
+
+  assert_eq!(
+    mod_file.contains("use gdnative::export::user_data;\n"),
+    true
+  );
+  assert_eq!(mod_file.contains("use gdnative::prelude::*;\n"), true);
+  assert_eq!(mod_file.contains("#[methods]\nimpl Hello {"), true);
+  assert_eq!(mod_file.contains("\t#[method]\n"), true);
+  assert_eq!(mod_file.contains("#[base] _owner: &Node"), true);
+  assert_eq!(mod_file.contains("gdnative::methods"), false);
+
+  assert_eq!(stdout.contains("src/hello.rs:"), true);
+  assert_eq!(stdout.contains("add_signal"), true);
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should regenerate the class registrations and the gdns class names from the config when a class prefix is set.
+#[test]
+fn update_gdnative_version_with_class_prefix() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd.arg("new").arg("platformer_modules").arg("platformer");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  for name in &["Player", "Enemy"] {
+    Command::new("cargo")
+      .arg("run")
+      .arg("--manifest-path=../../Cargo.toml")
+      .arg("create")
+      .arg(name)
+      .output()
+      .expect("Unable to execute cargo run");
+  }
+
+  // Enemy is given a prefixed name by hand, which gdnative 0.9 can't register it under.
+  let config = read_to_string("godot-rust-helper.toml").expect("Unable to read config");
+  write(
+    "godot-rust-helper.toml",
+    format!(
+      "{}\n[modules.Enemy]\nclass_name = \"RsEnemy\"\n",
+      config.replace("[general]\n", "[general]\nclass_prefix = \"Rs\"\n")
+    ),
+  )
+  .expect("Unable to write config");
+
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("update")
+    .arg("--gdnative")
+    .arg("0.10")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let lib_file = read_to_string("src/lib.rs").expect("Unable to read lib file");
+  let player_gdns_file =
+    read_to_string("../platformer/player.gdns").expect("Unable to read gdns file");
+  let enemy_gdns_file =
+    read_to_string("../platformer/enemy.gdns").expect("Unable to read gdns file");
+
+  assert_eq!(output.status.success(), true);
+  assert_eq!(
+    lib_file.contains("\thandle.add_class::<player::Player>();\n"),
+    true
+  );
+  assert_eq!(
+    lib_file.contains("\thandle.add_class_as::<enemy::Enemy>(\"RsEnemy\".to_string());\n"),
+    true
+  );
+  assert_eq!(player_gdns_file.contains("class_name = \"Player\""), true);
+  assert_eq!(
+    enemy_gdns_file.contains("resource_name = \"RsEnemy\""),
+    true
+  );
+  assert_eq!(enemy_gdns_file.contains("class_name = \"RsEnemy\""), true);
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should only change the version of gdnative and keep the rest of the Cargo.toml file as it is.
+#[test]
+fn update_gdnative_keeps_cargo_toml() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd.arg("new").arg("platformer_modules").arg("platformer");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  let cargo_toml = "[package]\nname = \"platformer_modules\"\nversion = \"0.1.0\"\nedition = \"2018\"\nlicense = \"MIT\"\n\n[lib]\ncrate-type = [\"cdylib\", \"rlib\"]\n\n[dependencies]\n# The bindings.\ngdnative = { version = \"0.9.1\", features = [\"serde\"] } # keep serde\nrand = \"0.8\"\n\n[profile.release]\nlto = true\n";
+  write("Cargo.toml", cargo_toml).expect("Unable to write Cargo.toml");
+
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("update")
+    .arg("--gdnative")
+    .arg("0.10")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  assert_eq!(
+    read_to_string("Cargo.toml").expect("Unable to read Cargo.toml"),
+    cargo_toml.replace("version = \"0.9.1\"", "version = \"0.10\"")
+  );
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should not change a library that already uses the gdnative version.
+#[test]
+fn update_gdnative_same_version() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd
+    .arg("new")
+    .arg("platformer_modules")
+    .arg("platformer")
+    .arg("--gdnative")
+    .arg("0.10");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  let lib_file = read_to_string("src/lib.rs").expect("Unable to read lib file");
+
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("update")
+    .arg("--gdnative")
+    .arg("0.10")
+    .output()
+    .expect("Unable to execute cargo run");
+  let stdout = String::from_utf8_lossy(&output.stdout);
+
+  assert_eq!(
+    stdout.contains("The library already uses gdnative 0.10"),
+    true
+  );
+  assert_eq!(
+    read_to_string("src/lib.rs").expect("Unable to read lib file"),
+    lib_file
+  );
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should add the generated region markers to a lib file that doesn't have them and keep the rest of the file.
+#[test]
+fn update_lib_file_without_markers() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd.arg("new").arg("platformer_modules").arg("platformer");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Player")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let lib_file = "#[macro_use]\nextern crate gdnative;\nuse std::fmt;\n\nmod player;\nmod helpers;\n\nfn init(handle: gdnative::nativescript::InitHandle) {\n\thandle.add_class::<player::Player>();\n}\n\n// my custom code\ngodot_init!(init);";
+  write("src/lib.rs", lib_file).expect("Unable to write lib file");
+
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("update")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  assert_eq!(output.status.success(), true);
+  assert_eq!(
+    read_to_string("src/lib.rs").expect("Unable to read lib file"),
+    "#[macro_use]\nextern crate gdnative;\nuse std::fmt;\n\n// godot_rust_helper: begin modules\nmod player;\n// godot_rust_helper: end modules\nmod helpers;\n\nfn init(handle: gdnative::nativescript::InitHandle) {\n\t// godot_rust_helper: begin classes\n\thandle.add_class::<player::Player>();\n\t// godot_rust_helper: end classes\n}\n\n// my custom code\ngodot_init!(init);"
+  );
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
+
+// It should leave a lib file without markers as it is when the markers can't be added around the generated code.
+#[test]
+fn update_lib_file_without_markers_scattered() -> Result<(), Box<dyn Error>> {
+  init_test();
+
+  let mut cmd = Command::cargo_bin("godot_rust_helper")?;
+  cmd.arg("new").arg("platformer_modules").arg("platformer");
+
+  cmd.assert().success();
+
+  set_current_dir("platformer_modules").expect("Unable to change to library directory");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Player")
+    .output()
+    .expect("Unable to execute cargo run");
+  Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("create")
+    .arg("Enemy")
+    .output()
+    .expect("Unable to execute cargo run");
+
+  let lib_file = "#[macro_use]\nextern crate gdnative;\n\nmod player;\nmod helpers;\nmod enemy;\n\nfn init(handle: gdnative::nativescript::InitHandle) {\n\thandle.add_class::<player::Player>();\n\thandle.add_class::<enemy::Enemy>();\n}\n\ngodot_init!(init);";
+  write("src/lib.rs", lib_file).expect("Unable to write lib file");
+
+  let output = Command::new("cargo")
+    .arg("run")
+    .arg("--manifest-path=../../Cargo.toml")
+    .arg("update")
+    .output()
+    .expect("Unable to execute cargo run");
+  let stdout = String::from_utf8_lossy(&output.stdout);
+
+  assert_eq!(output.status.success(), false);
+  assert_eq!(
+    stdout.contains("The lib file can't be updated because the `// godot_rust_helper: begin modules` marker is missing"),
+    true
+  );
+  assert_eq!(
+    read_to_string("src/lib.rs").expect("Unable to read lib file"),
+    lib_file
+  );
+
+  set_current_dir("../").expect("Unable to change to parent directory");
+
+  cleanup_test_files();
+
+  Ok(())
+}
@@ -1,3 +1,5 @@
+#![allow(clippy::needless_return)]
+
 use std::fs::{remove_dir_all, remove_file};
 use std::path::Path;
 
@@ -105,6 +107,13 @@ pub fn cleanup_test_files() {
     remove_dir_all("platformer-modules").expect("Unable to remove library dir");
   }
 
+  if Path::new("platformer_editor").exists() {
+    remove_dir_all("platformer_editor").expect("Unable to remove other Godot project dir");
+  }
+  if Path::new("platformer_tools").exists() {
+    remove_dir_all("platformer_tools").expect("Unable to remove other Godot project dir");
+  }
+
   if Path::new("directory_browser").exists() {
     remove_dir_all("directory_browser").expect("Unable to remove plugin dir");
   }